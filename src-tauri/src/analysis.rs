@@ -0,0 +1,765 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tokio_util::sync::CancellationToken;
+use walkdir::WalkDir;
+
+use crate::zip_handler::is_export_excluded;
+
+/// 每处理多少个文件报告一次进度
+const PROGRESS_INTERVAL: usize = 50;
+
+/// 分析任务的进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisProgress {
+    pub task_id: String,
+    pub phase: String,
+    pub current: usize,
+    pub total: usize,
+}
+
+/// 进度回调：(阶段, 已处理数, 总数)
+pub type ProgressFn<'a> = &'a dyn Fn(&str, usize, usize);
+
+/// 问题的严重程度，error 会阻止导出，warning 只作提示
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// 问题的类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueCategory {
+    /// pack.mcmeta 或 pack.png
+    Pack,
+    PackFormat,
+    Json,
+    Image,
+    Reference,
+}
+
+/// 材质包问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackIssue {
+    /// 相对材质包根目录的路径
+    pub file: String,
+    pub severity: IssueSeverity,
+    pub category: IssueCategory,
+    pub message: String,
+}
+
+impl PackIssue {
+    fn new(severity: IssueSeverity, category: IssueCategory, file: &str, message: String) -> Self {
+        Self {
+            file: file.to_string(),
+            severity,
+            category,
+            message,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
+}
+
+/// 内容相同的一组文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub size: u64,
+    pub files: Vec<String>,
+}
+
+/// 未被任何资源文件引用的方块和物品贴图
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedTexturesReport {
+    pub textures_checked: usize,
+    pub unused: Vec<String>,
+}
+
+/// JSON 语法错误及其位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonSyntaxError {
+    pub file: String,
+    /// 从 1 开始的行号和列号
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for JsonSyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // 读取失败时没有位置
+        match self.line {
+            0 => write!(f, "{}", self.message),
+            line => write!(
+                f,
+                "Invalid JSON at line {} column {}: {}",
+                line, self.column, self.message
+            ),
+        }
+    }
+}
+
+fn cancelled() -> String {
+    "Analysis cancelled".to_string()
+}
+
+/// 收集材质包内的文件（相对路径使用 /），排除编辑器元数据
+fn collect_files(pack_path: &Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = WalkDir::new(pack_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let rel = e.path().strip_prefix(pack_path).ok()?;
+            if is_export_excluded(rel) {
+                return None;
+            }
+            Some((
+                rel.to_string_lossy().replace('\\', "/"),
+                e.path().to_path_buf(),
+            ))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// 逐个处理文件，定期检查取消并报告进度
+fn for_each_file<'a, T>(
+    phase: &str,
+    files: &'a [T],
+    cancel: &CancellationToken,
+    on_progress: ProgressFn,
+    mut visit: impl FnMut(&'a T),
+) -> Result<(), String> {
+    on_progress(phase, 0, files.len());
+    for (i, file) in files.iter().enumerate() {
+        if i % PROGRESS_INTERVAL == 0 {
+            if cancel.is_cancelled() {
+                return Err(cancelled());
+            }
+            on_progress(phase, i, files.len());
+        }
+        visit(file);
+    }
+    on_progress(phase, files.len(), files.len());
+    Ok(())
+}
+
+/// 检查 JSON 语法，返回 (行, 列, 不含位置的错误描述)
+fn json_syntax_error(content: &str) -> Option<(usize, usize, String)> {
+    if content.starts_with('\u{feff}') {
+        return Some((
+            1,
+            1,
+            "File starts with a UTF-8 BOM, save it without BOM".to_string(),
+        ));
+    }
+    let error = serde_json::from_str::<serde_json::de::IgnoredAny>(content).err()?;
+    let message = error.to_string();
+    let suffix = format!(" at line {} column {}", error.line(), error.column());
+    let message = message.strip_suffix(&suffix).unwrap_or(&message);
+    let message = match message {
+        "trailing comma" => "Trailing comma before a closing bracket".to_string(),
+        "EOF while parsing an object" | "EOF while parsing a list" => {
+            "Unexpected end of file, a closing bracket is missing".to_string()
+        }
+        "EOF while parsing a value" => "File is empty or ends unexpectedly".to_string(),
+        other => other.to_string(),
+    };
+    Some((error.line(), error.column().max(1), message))
+}
+
+/// 检查单个 JSON 文件，语法正确时返回 None
+pub fn validate_json_file(path: &Path, rel: &str) -> Result<Option<JsonSyntaxError>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", rel, e))?;
+    Ok(
+        json_syntax_error(&content).map(|(line, column, message)| JsonSyntaxError {
+            file: rel.to_string(),
+            line,
+            column,
+            message,
+        }),
+    )
+}
+
+/// 检查单个 JSON 文件，读取失败也作为没有位置的错误返回
+fn check_json_file(path: &Path, rel: &str) -> Option<JsonSyntaxError> {
+    validate_json_file(path, rel).unwrap_or_else(|message| {
+        Some(JsonSyntaxError {
+            file: rel.to_string(),
+            line: 0,
+            column: 0,
+            message,
+        })
+    })
+}
+
+fn is_json_file(rel: &str) -> bool {
+    let ext = Path::new(rel)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    matches!(ext.as_str(), "json" | "mcmeta")
+}
+
+/// 检查材质包内所有 JSON 和 .mcmeta 文件，返回每个格式错误的文件
+pub fn validate_all_json(pack_path: &Path) -> Vec<JsonSyntaxError> {
+    use rayon::prelude::*;
+
+    let files: Vec<(String, PathBuf)> = collect_files(pack_path)
+        .into_iter()
+        .filter(|(rel, _)| is_json_file(rel))
+        .collect();
+    files
+        .par_iter()
+        .filter_map(|(rel, path)| check_json_file(path, rel))
+        .collect()
+}
+
+/// 导出前的综合检查：在 `validate_pack` 的基础上检查 pack.png、目标 pack_format 和悬空引用
+///
+/// 传入 `target_format` 时检查 pack.mcmeta 是否支持该格式；
+/// minecraft 命名空间的悬空引用可能来自原版，不报告
+pub fn preflight_pack(
+    pack_path: &Path,
+    target_format: Option<i32>,
+) -> Result<Vec<PackIssue>, String> {
+    let cancel = CancellationToken::new();
+    let no_progress = |_: &str, _: usize, _: usize| {};
+    let mut issues = validate_pack(pack_path, &cancel, &no_progress)?;
+
+    if !pack_path.join("pack.png").is_file() {
+        issues.push(PackIssue::new(
+            IssueSeverity::Warning,
+            IssueCategory::Pack,
+            "pack.png",
+            "pack.png not found, the pack will show a default icon".to_string(),
+        ));
+    }
+
+    // pack.mcmeta 缺失或无法解析时已由 validate_pack 报告
+    if let Some(target) = target_format {
+        let mcmeta = fs::read_to_string(pack_path.join("pack.mcmeta"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok());
+        if let Some(mcmeta) = mcmeta {
+            let formats = crate::pack_parser::parse_pack_formats(&mcmeta);
+            if let Some(actual) = formats.pack_format.filter(|_| !formats.supports(target)) {
+                issues.push(PackIssue::new(
+                    IssueSeverity::Warning,
+                    IssueCategory::PackFormat,
+                    "pack.mcmeta",
+                    format!(
+                        "pack_format {} does not match the target format {}",
+                        actual, target
+                    ),
+                ));
+            }
+        }
+    }
+
+    let analysis = analyze_pack(pack_path, &cancel, &no_progress)?;
+    for reference in analysis.missing_references {
+        if reference.may_be_vanilla {
+            continue;
+        }
+        issues.push(PackIssue::new(
+            IssueSeverity::Warning,
+            IssueCategory::Reference,
+            &reference.file,
+            format!(
+                "{} points to missing file {}",
+                reference.reference, reference.expected_path
+            ),
+        ));
+    }
+
+    Ok(issues)
+}
+
+/// 检查 pack.mcmeta、JSON 语法和图片是否可读
+pub fn validate_pack(
+    pack_path: &Path,
+    cancel: &CancellationToken,
+    on_progress: ProgressFn,
+) -> Result<Vec<PackIssue>, String> {
+    let mut issues = Vec::new();
+
+    match fs::read_to_string(pack_path.join("pack.mcmeta")) {
+        Ok(content) => {
+            // 语法错误在下面逐文件检查时报告
+            let has_format = serde_json::from_str::<Value>(&content)
+                .map(|json| {
+                    json.pointer("/pack/pack_format")
+                        .and_then(|v| v.as_i64())
+                        .is_some()
+                })
+                .unwrap_or(true);
+            if !has_format {
+                issues.push(PackIssue::new(
+                    IssueSeverity::Error,
+                    IssueCategory::PackFormat,
+                    "pack.mcmeta",
+                    "Missing pack.pack_format".to_string(),
+                ));
+            }
+        }
+        Err(_) => issues.push(PackIssue::new(
+            IssueSeverity::Error,
+            IssueCategory::Pack,
+            "pack.mcmeta",
+            "pack.mcmeta not found".to_string(),
+        )),
+    }
+
+    let files = collect_files(pack_path);
+    for_each_file("validating", &files, cancel, on_progress, |(rel, path)| {
+        let ext = Path::new(rel)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        let problem = match ext.as_str() {
+            "json" | "mcmeta" => {
+                check_json_file(path, rel).map(|error| (IssueCategory::Json, error.to_string()))
+            }
+            "png" | "tga" => image::image_dimensions(path)
+                .err()
+                .map(|e| (IssueCategory::Image, format!("Unreadable image: {}", e))),
+            _ => None,
+        };
+
+        if let Some((category, message)) = problem {
+            issues.push(PackIssue::new(IssueSeverity::Error, category, rel, message));
+        }
+    })?;
+
+    Ok(issues)
+}
+
+fn hash_content(data: &[u8]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 查找内容完全相同的文件，先按大小分组，只对大小相同的文件比较内容
+pub fn find_duplicate_files(
+    pack_path: &Path,
+    cancel: &CancellationToken,
+    on_progress: ProgressFn,
+) -> Result<Vec<DuplicateGroup>, String> {
+    let files = collect_files(pack_path);
+
+    let mut by_size: HashMap<u64, Vec<&(String, PathBuf)>> = HashMap::new();
+    for_each_file("collecting", &files, cancel, on_progress, |file| {
+        if let Ok(metadata) = fs::metadata(&file.1) {
+            if metadata.len() > 0 {
+                by_size.entry(metadata.len()).or_default().push(file);
+            }
+        }
+    })?;
+
+    let candidates: Vec<(u64, &(String, PathBuf))> = by_size
+        .iter()
+        .filter(|(_, group)| group.len() > 1)
+        .flat_map(|(size, group)| group.iter().map(move |file| (*size, *file)))
+        .collect();
+
+    // 按 (大小, 哈希) 分组，组内再逐字节比较以排除哈希碰撞
+    let mut by_hash: HashMap<(u64, u64), Vec<Vec<(String, Vec<u8>)>>> = HashMap::new();
+    for_each_file(
+        "comparing",
+        &candidates,
+        cancel,
+        on_progress,
+        |(size, (rel, path))| {
+            let data = match fs::read(path) {
+                Ok(data) => data,
+                Err(_) => return,
+            };
+            let buckets = by_hash.entry((*size, hash_content(&data))).or_default();
+            match buckets.iter_mut().find(|bucket| bucket[0].1 == data) {
+                Some(bucket) => bucket.push((rel.clone(), Vec::new())),
+                None => buckets.push(vec![(rel.clone(), data)]),
+            }
+        },
+    )?;
+
+    let mut groups: Vec<DuplicateGroup> = by_hash
+        .into_iter()
+        .flat_map(|((size, _), buckets)| {
+            buckets
+                .into_iter()
+                .filter(|bucket| bucket.len() > 1)
+                .map(move |bucket| {
+                    let mut files: Vec<String> = bucket.into_iter().map(|(rel, _)| rel).collect();
+                    files.sort();
+                    DuplicateGroup { size, files }
+                })
+        })
+        .collect();
+    groups.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.files.cmp(&b.files)));
+
+    Ok(groups)
+}
+
+/// 指向不存在文件的引用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DanglingReference {
+    /// 包含该引用的文件
+    pub file: String,
+    pub reference: String,
+    /// 引用解析后应存在的文件
+    pub expected_path: String,
+    /// minecraft 命名空间的引用可能指向原版资源
+    pub may_be_vanilla: bool,
+}
+
+/// 材质包引用检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackAnalysis {
+    pub textures_checked: usize,
+    /// textures/block 和 textures/item 下未被任何文件引用的贴图
+    pub unused_textures: Vec<String>,
+    pub missing_references: Vec<DanglingReference>,
+}
+
+/// 将 "命名空间:路径" 解析为 assets/<命名空间>/<folder>/<路径><suffix>，返回 (路径, 命名空间)
+fn resource_file(reference: &str, folder: &str, suffix: &str) -> (String, String) {
+    let (namespace, path) = reference
+        .split_once(':')
+        .unwrap_or(("minecraft", reference));
+    (
+        format!("assets/{}/{}/{}{}", namespace, folder, path, suffix),
+        namespace.to_string(),
+    )
+}
+
+/// 递归收集 JSON 中所有字符串类型的 model 字段（方块状态、物品定义和模型 overrides）
+fn collect_model_fields<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                match child {
+                    Value::String(model) if key == "model" => out.push(model),
+                    _ => collect_model_fields(child, out),
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_model_fields(item, out)),
+        _ => {}
+    }
+}
+
+/// 找出单个资源文件中的引用，返回 (引用, 应存在的文件, 命名空间)
+fn file_references(rel: &str, json: &Value) -> Vec<(String, String, String)> {
+    let mut refs = Vec::new();
+    let parts: Vec<&str> = rel.split('/').collect();
+    let kind = if parts.len() == 3 && parts[2] == "sounds.json" {
+        "sounds"
+    } else {
+        parts.get(2).copied().unwrap_or("")
+    };
+
+    let mut push = |reference: &str, folder: &str, suffix: &str| {
+        let (path, namespace) = resource_file(reference, folder, suffix);
+        refs.push((reference.to_string(), path, namespace));
+    };
+
+    match kind {
+        "models" | "blockstates" | "items" => {
+            let mut models = Vec::new();
+            collect_model_fields(json, &mut models);
+            for model in models {
+                push(model, "models", ".json");
+            }
+            if kind == "models" {
+                if let Some(parent) = json.get("parent").and_then(|p| p.as_str()) {
+                    if !parent
+                        .trim_start_matches("minecraft:")
+                        .starts_with("builtin/")
+                    {
+                        push(parent, "models", ".json");
+                    }
+                }
+                if let Some(Value::Object(textures)) = json.get("textures") {
+                    for texture in textures.values().filter_map(|v| v.as_str()) {
+                        if !texture.starts_with('#') {
+                            push(texture, "textures", ".png");
+                        }
+                    }
+                }
+            }
+        }
+        "font" => {
+            let providers = json.get("providers").and_then(|p| p.as_array());
+            for provider in providers.into_iter().flatten() {
+                let field = |name: &str| provider.get(name).and_then(|v| v.as_str());
+                let (key, folder, suffix) =
+                    match field("type").unwrap_or("").trim_start_matches("minecraft:") {
+                        "bitmap" => ("file", "textures", ""),
+                        "ttf" => ("file", "font", ""),
+                        "reference" => ("id", "font", ".json"),
+                        _ => continue,
+                    };
+                if let Some(reference) = field(key) {
+                    push(reference, folder, suffix);
+                }
+            }
+        }
+        "sounds" => {
+            let events = json
+                .as_object()
+                .into_iter()
+                .flat_map(|events| events.values());
+            let sounds = events
+                .filter_map(|event| event.get("sounds")?.as_array())
+                .flatten();
+            for sound in sounds {
+                let name = match sound {
+                    Value::String(name) => Some(name.as_str()),
+                    // type 为 event 时引用的是其他声音事件，不是文件
+                    Value::Object(entry)
+                        if entry.get("type").and_then(|t| t.as_str()) != Some("event") =>
+                    {
+                        entry.get("name").and_then(|n| n.as_str())
+                    }
+                    _ => None,
+                };
+                if let Some(name) = name {
+                    push(name, "sounds", ".ogg");
+                }
+            }
+        }
+        _ => {}
+    }
+    refs
+}
+
+/// 材质包内所有资源文件中的引用
+struct PackReferences {
+    /// 被引用的文件（相对材质包根目录）
+    referenced: HashSet<String>,
+    missing: Vec<DanglingReference>,
+}
+
+/// 扫描模型、方块状态、物品定义、字体和 sounds.json，收集引用的文件和指向不存在文件的引用
+fn collect_references(
+    files: &[(String, PathBuf)],
+    cancel: &CancellationToken,
+    on_progress: ProgressFn,
+) -> Result<PackReferences, String> {
+    let existing: HashSet<&str> = files.iter().map(|(rel, _)| rel.as_str()).collect();
+
+    let sources: Vec<&(String, PathBuf)> = files
+        .iter()
+        .filter(|(rel, _)| {
+            let parts: Vec<&str> = rel.split('/').collect();
+            parts.first() == Some(&"assets")
+                && rel.ends_with(".json")
+                && match parts.len() {
+                    3 => parts[2] == "sounds.json",
+                    0..=2 => false,
+                    _ => matches!(parts[2], "models" | "blockstates" | "items" | "font"),
+                }
+        })
+        .collect();
+
+    let mut referenced: HashSet<String> = HashSet::new();
+    let mut missing = Vec::new();
+    for_each_file(
+        "scanning_references",
+        &sources,
+        cancel,
+        on_progress,
+        |(rel, path)| {
+            let json = fs::read_to_string(path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<Value>(&content).ok());
+            let Some(json) = json else { return };
+
+            for (reference, expected_path, namespace) in file_references(rel, &json) {
+                if !existing.contains(expected_path.as_str()) {
+                    missing.push(DanglingReference {
+                        file: rel.clone(),
+                        reference,
+                        expected_path: expected_path.clone(),
+                        may_be_vanilla: namespace == "minecraft",
+                    });
+                }
+                referenced.insert(expected_path);
+            }
+        },
+    )?;
+
+    Ok(PackReferences {
+        referenced,
+        missing,
+    })
+}
+
+/// 返回检查的贴图数量和 textures/block、textures/item 下未被引用的贴图
+fn unused_textures(
+    files: &[(String, PathBuf)],
+    referenced: &HashSet<String>,
+) -> (usize, Vec<String>) {
+    let textures: Vec<&String> = files
+        .iter()
+        .map(|(rel, _)| rel)
+        .filter(|rel| {
+            let mut parts = rel.split('/').skip(2);
+            parts.next() == Some("textures")
+                && matches!(parts.next(), Some("block" | "item"))
+                && rel.ends_with(".png")
+        })
+        .collect();
+    // 破坏进度、水、岩浆、火等贴图由游戏直接加载，不经过模型引用
+    let unused = textures
+        .iter()
+        .filter(|rel| !referenced.contains(rel.as_str()))
+        .filter(|rel| {
+            !crate::constants::ENGINE_TEXTURE_PREFIXES
+                .iter()
+                .any(|prefix| rel.starts_with(prefix))
+        })
+        .map(|rel| rel.to_string())
+        .collect();
+    (textures.len(), unused)
+}
+
+/// 查找 textures/block 和 textures/item 下未被任何模型、方块状态、物品定义或字体引用的贴图
+///
+/// 其他目录的贴图（GUI、实体等）由游戏直接按路径读取，不参与检查
+pub fn find_unused_textures(
+    pack_path: &Path,
+    cancel: &CancellationToken,
+    on_progress: ProgressFn,
+) -> Result<UnusedTexturesReport, String> {
+    let files = collect_files(pack_path);
+    let references = collect_references(&files, cancel, on_progress)?;
+    let (textures_checked, unused) = unused_textures(&files, &references.referenced);
+    Ok(UnusedTexturesReport {
+        textures_checked,
+        unused,
+    })
+}
+
+/// 交叉检查模型、方块状态、物品定义、字体和 sounds.json 中的引用与实际文件
+///
+/// 返回未被引用的方块和物品贴图，以及指向不存在文件的引用
+pub fn analyze_pack(
+    pack_path: &Path,
+    cancel: &CancellationToken,
+    on_progress: ProgressFn,
+) -> Result<PackAnalysis, String> {
+    let files = collect_files(pack_path);
+    let references = collect_references(&files, cancel, on_progress)?;
+    let (textures_checked, unused_textures) = unused_textures(&files, &references.referenced);
+
+    Ok(PackAnalysis {
+        textures_checked,
+        unused_textures,
+        missing_references: references.missing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unused_textures_counts_font_references_and_skips_engine_textures() {
+        let pack =
+            std::env::temp_dir().join(format!("little100_unused_textures_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&pack);
+        let assets = pack.join("assets/minecraft");
+        for dir in ["models/block", "font", "textures/block", "textures/item"] {
+            fs::create_dir_all(assets.join(dir)).unwrap();
+        }
+        fs::write(
+            assets.join("models/block/stone.json"),
+            r#"{"textures": {"all": "block/stone"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            assets.join("font/default.json"),
+            r#"{"providers": [{"type": "bitmap", "file": "minecraft:item/glyphs.png"}]}"#,
+        )
+        .unwrap();
+        for texture in [
+            "block/stone",
+            "block/dirt",
+            "block/destroy_stage_0",
+            "block/water_still",
+            "item/glyphs",
+        ] {
+            fs::write(assets.join(format!("textures/{}.png", texture)), b"").unwrap();
+        }
+
+        let cancel = CancellationToken::new();
+        let no_progress = |_: &str, _: usize, _: usize| {};
+        let report = find_unused_textures(&pack, &cancel, &no_progress).unwrap();
+        let analysis = analyze_pack(&pack, &cancel, &no_progress).unwrap();
+        let _ = fs::remove_dir_all(&pack);
+
+        assert_eq!(report.textures_checked, 5);
+        assert_eq!(
+            report.unused,
+            vec!["assets/minecraft/textures/block/dirt.png"]
+        );
+        assert_eq!(analysis.unused_textures, report.unused);
+    }
+
+    #[test]
+    fn test_json_syntax_error_reports_trailing_comma_position() {
+        let content = "{\n  \"parent\": \"block/cube_all\",\n}";
+        let (line, column, message) = json_syntax_error(content).unwrap();
+        assert_eq!((line, column), (3, 1));
+        assert_eq!(message, "Trailing comma before a closing bracket");
+    }
+
+    #[test]
+    fn test_json_syntax_error_maps_unexpected_eof() {
+        let (line, _, message) = json_syntax_error("{\n  \"textures\": {\n").unwrap();
+        assert_eq!(line, 3);
+        assert_eq!(
+            message,
+            "Unexpected end of file, a closing bracket is missing"
+        );
+        let (_, _, message) = json_syntax_error("").unwrap();
+        assert_eq!(message, "File is empty or ends unexpectedly");
+    }
+
+    #[test]
+    fn test_json_syntax_error_rejects_bom_and_accepts_valid_json() {
+        assert_eq!(
+            json_syntax_error("\u{feff}{}"),
+            Some((
+                1,
+                1,
+                "File starts with a UTF-8 BOM, save it without BOM".to_string()
+            ))
+        );
+        assert_eq!(json_syntax_error("{\"pack\": {\"pack_format\": 34}}"), None);
+    }
+
+    #[test]
+    fn test_json_syntax_error_message_has_no_position_suffix() {
+        let (line, column, message) = json_syntax_error("{\"a\": tru}").unwrap();
+        assert_eq!(line, 1);
+        assert!(column > 1);
+        assert!(!message.contains(" at line "), "{}", message);
+    }
+}
@@ -0,0 +1,263 @@
+use base64::{engine::general_purpose, Engine as _};
+use image::{GenericImageView, ImageFormat};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 动画帧：单独的帧索引，或带自定义时长的帧
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AnimationFrame {
+    Index(u32),
+    Timed {
+        index: u32,
+        /// 省略时使用 frametime
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        time: Option<u32>,
+    },
+}
+
+impl AnimationFrame {
+    pub fn index(&self) -> u32 {
+        match self {
+            AnimationFrame::Index(index) | AnimationFrame::Timed { index, .. } => *index,
+        }
+    }
+
+    pub fn time(&self) -> Option<u32> {
+        match self {
+            AnimationFrame::Index(_) => None,
+            AnimationFrame::Timed { time, .. } => *time,
+        }
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// .png.mcmeta 中的 animation 对象
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnimationConfig {
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub interpolate: bool,
+    /// 帧宽度，省略时按游戏规则推断
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// 每帧默认时长（游戏刻），省略时为 1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frametime: Option<u32>,
+    /// 播放顺序，省略时按帧索引依次播放
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frames: Option<Vec<AnimationFrame>>,
+}
+
+/// 贴图对应的 .mcmeta 路径（<贴图>.png.mcmeta）
+pub fn mcmeta_path(texture_path: &Path) -> PathBuf {
+    let mut path = texture_path.as_os_str().to_owned();
+    path.push(".mcmeta");
+    PathBuf::from(path)
+}
+
+/// 按游戏规则计算帧尺寸和帧数，返回 (帧宽, 帧高, 帧数)
+///
+/// 未指定宽高时帧为边长等于贴图短边的正方形；帧按从左到右、从上到下的顺序编号
+pub fn frame_layout(
+    image_width: u32,
+    image_height: u32,
+    config: &AnimationConfig,
+) -> Result<(u32, u32, u32), String> {
+    let (width, height) = match (config.width, config.height) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, image_height),
+        (None, Some(height)) => (image_width, height),
+        (None, None) => {
+            let size = image_width.min(image_height);
+            (size, size)
+        }
+    };
+
+    if width == 0 || height == 0 {
+        return Err("Frame width and height must be greater than zero".to_string());
+    }
+    if image_width % width != 0 || image_height % height != 0 {
+        return Err(format!(
+            "Texture size {}x{} is not a multiple of the frame size {}x{}",
+            image_width, image_height, width, height
+        ));
+    }
+
+    Ok((
+        width,
+        height,
+        (image_width / width) * (image_height / height),
+    ))
+}
+
+/// 校验帧时长和帧索引是否在贴图实际的帧数范围内
+pub fn validate_animation(
+    image_width: u32,
+    image_height: u32,
+    config: &AnimationConfig,
+) -> Result<(), String> {
+    let (_, _, frame_count) = frame_layout(image_width, image_height, config)?;
+
+    if config.frametime == Some(0) {
+        return Err("frametime must be greater than zero".to_string());
+    }
+    for frame in config.frames.iter().flatten() {
+        if frame.index() >= frame_count {
+            return Err(format!(
+                "Frame index {} is out of range, the texture has {} frames",
+                frame.index(),
+                frame_count
+            ));
+        }
+        if frame.time() == Some(0) {
+            return Err(format!("Frame {} has a time of zero", frame.index()));
+        }
+    }
+
+    Ok(())
+}
+
+fn read_mcmeta(path: &Path) -> Result<Option<Value>, String> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read mcmeta: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse mcmeta: {}", e))
+}
+
+/// 读取贴图的动画设置，没有 .mcmeta 或其中没有 animation 时返回 None
+pub fn read_animation(texture_path: &Path) -> Result<Option<AnimationConfig>, String> {
+    let mcmeta = match read_mcmeta(&mcmeta_path(texture_path))? {
+        Some(mcmeta) => mcmeta,
+        None => return Ok(None),
+    };
+    match mcmeta.get("animation") {
+        Some(animation) => serde_json::from_value(animation.clone())
+            .map(Some)
+            .map_err(|e| format!("Invalid animation section: {}", e)),
+        None => Ok(None),
+    }
+}
+
+/// 校验并保存贴图的动画设置，保留 .mcmeta 中的其他部分（如 texture、villager）
+pub fn save_animation(texture_path: &Path, config: &AnimationConfig) -> Result<(), String> {
+    let (width, height) = image::image_dimensions(texture_path)
+        .map_err(|e| format!("Failed to read texture: {}", e))?;
+    validate_animation(width, height, config)?;
+
+    let path = mcmeta_path(texture_path);
+    let mut mcmeta = match read_mcmeta(&path)? {
+        Some(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let animation = serde_json::to_value(config)
+        .map_err(|e| format!("Failed to serialize animation: {}", e))?;
+    mcmeta.insert("animation".to_string(), animation);
+
+    let content = serde_json::to_string_pretty(&Value::Object(mcmeta))
+        .map_err(|e| format!("Failed to serialize mcmeta: {}", e))?;
+    fs::write(&path, content).map_err(|e| crate::error::io_error("Failed to write mcmeta", e))
+}
+
+/// 每个游戏刻的毫秒数
+const TICK_MS: u32 = 50;
+
+/// 预览中的一帧
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviewFrame {
+    /// 贴图中的帧索引
+    pub index: u32,
+    /// base64 编码的 PNG
+    pub image: String,
+    /// 时长（游戏刻）
+    pub time: u32,
+    pub duration_ms: u32,
+}
+
+/// 按播放顺序排列的动画帧
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnimationPreview {
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub interpolate: bool,
+    /// 是否读取到了 .mcmeta 中的动画设置
+    pub has_mcmeta: bool,
+    pub frames: Vec<PreviewFrame>,
+}
+
+/// 将贴图切分为动画帧，按 .mcmeta 中的 frames 和 frametime 排列
+///
+/// 没有 .mcmeta 时按竖向排列的正方形帧处理，尺寸无法整除时整张图作为一帧
+pub fn animation_preview(texture_path: &Path) -> Result<AnimationPreview, String> {
+    let config = read_animation(texture_path)?;
+    let has_mcmeta = config.is_some();
+    let config = config.unwrap_or_default();
+
+    let img = image::open(texture_path).map_err(|e| format!("Failed to open image: {}", e))?;
+    let (image_width, image_height) = img.dimensions();
+    let (frame_width, frame_height, frame_count) =
+        match frame_layout(image_width, image_height, &config) {
+            Ok(layout) => layout,
+            Err(_) if !has_mcmeta => (image_width, image_height, 1),
+            Err(e) => return Err(e),
+        };
+
+    let frametime = config.frametime.unwrap_or(1).max(1);
+    let order: Vec<(u32, u32)> = match &config.frames {
+        Some(frames) => frames
+            .iter()
+            .map(|frame| (frame.index(), frame.time().unwrap_or(frametime)))
+            .collect(),
+        None => (0..frame_count).map(|index| (index, frametime)).collect(),
+    };
+
+    let columns = image_width / frame_width;
+    let mut encoded: HashMap<u32, String> = HashMap::new();
+    let mut frames = Vec::with_capacity(order.len());
+    for (index, time) in order {
+        if index >= frame_count {
+            return Err(format!(
+                "Frame index {} is out of range, the texture has {} frames",
+                index, frame_count
+            ));
+        }
+        let image = match encoded.get(&index) {
+            Some(image) => image.clone(),
+            None => {
+                let x = (index % columns) * frame_width;
+                let y = (index / columns) * frame_height;
+                let mut buffer = Vec::new();
+                img.crop_imm(x, y, frame_width, frame_height)
+                    .write_to(&mut std::io::Cursor::new(&mut buffer), ImageFormat::Png)
+                    .map_err(|e| format!("Failed to encode frame: {}", e))?;
+                let image = general_purpose::STANDARD.encode(&buffer);
+                encoded.insert(index, image.clone());
+                image
+            }
+        };
+        frames.push(PreviewFrame {
+            index,
+            image,
+            time: time.max(1),
+            duration_ms: time.max(1) * TICK_MS,
+        });
+    }
+
+    Ok(AnimationPreview {
+        frame_width,
+        frame_height,
+        interpolate: config.interpolate,
+        has_mcmeta,
+        frames,
+    })
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 方块状态引用的单个模型
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVariant {
+    /// 模型 ID，如 minecraft:block/stone
+    pub model: String,
+    /// 绕 X 轴旋转角度（0、90、180、270）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uvlock: Option<bool>,
+    /// 随机选择时的权重，只在模型列表中有效
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+}
+
+/// 单个模型，或按权重随机选择的模型列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VariantList {
+    Single(ModelVariant),
+    Weighted(Vec<ModelVariant>),
+}
+
+impl VariantList {
+    fn models(&self) -> Vec<&ModelVariant> {
+        match self {
+            VariantList::Single(variant) => vec![variant],
+            VariantList::Weighted(variants) => variants.iter().collect(),
+        }
+    }
+}
+
+/// multipart 中的一项，`when` 省略时总是应用
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipartCase {
+    /// 条件，如 {"facing": "north"}、{"OR": [...]}
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Value>,
+    pub apply: VariantList,
+}
+
+/// assets/<命名空间>/blockstates/<名称>.json，variants 和 multipart 二选一
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockstateDef {
+    /// 键为状态字符串，如 "facing=north,half=top"，无状态时为 ""
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variants: Option<BTreeMap<String, VariantList>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multipart: Option<Vec<MultipartCase>>,
+}
+
+/// 保存方块状态的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockstateResult {
+    /// 相对材质包根目录的路径
+    pub path: String,
+    /// 无法在材质包中找到的模型引用（可能来自原版）
+    pub warnings: Vec<String>,
+}
+
+/// 命名空间只能使用游戏允许的字符
+pub fn is_valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty()
+        && namespace != "."
+        && namespace != ".."
+        && namespace
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.'))
+}
+
+/// 命名空间和路径只能使用游戏允许的字符
+fn check_resource_id(namespace: &str, name: &str) -> Result<(), String> {
+    if !is_valid_namespace(namespace) {
+        return Err(format!("Invalid namespace: {}", namespace));
+    }
+    let valid_name = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.' | '/'))
+        && name.split('/').all(|part| !part.is_empty() && part != "..");
+    if !valid_name {
+        return Err(format!("Invalid blockstate name: {}", name));
+    }
+    Ok(())
+}
+
+fn relative_path(namespace: &str, name: &str) -> String {
+    format!("assets/{}/blockstates/{}.json", namespace, name)
+}
+
+fn blockstate_path(pack_path: &Path, namespace: &str, name: &str) -> Result<PathBuf, String> {
+    check_resource_id(namespace, name)?;
+    Ok(pack_path.join(relative_path(namespace, name)))
+}
+
+fn validate_def(def: &BlockstateDef) -> Result<(), String> {
+    match (&def.variants, &def.multipart) {
+        (Some(_), Some(_)) => {
+            Err("A blockstate cannot have both variants and multipart".to_string())
+        }
+        (None, None) => Err("A blockstate needs either variants or multipart".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// 检查引用的模型是否存在于材质包中，返回悬空引用的警告
+fn dangling_models(pack_path: &Path, def: &BlockstateDef) -> Vec<String> {
+    let variants = def.variants.iter().flat_map(|variants| variants.values());
+    let parts = def.multipart.iter().flatten().map(|case| &case.apply);
+
+    let mut warnings = Vec::new();
+    for variant in variants.chain(parts).flat_map(|list| list.models()) {
+        let file = crate::models::model_file(pack_path, &variant.model);
+        let message = format!("Model not found in pack: {}", variant.model);
+        if !file.is_file() && !warnings.contains(&message) {
+            warnings.push(message);
+        }
+    }
+    warnings
+}
+
+fn write_blockstate(
+    pack_path: &Path,
+    path: &Path,
+    namespace: &str,
+    name: &str,
+    def: &BlockstateDef,
+) -> Result<BlockstateResult, String> {
+    validate_def(def)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create blockstates directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(def)
+        .map_err(|e| format!("Failed to serialize blockstate: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write blockstate: {}", e))?;
+
+    Ok(BlockstateResult {
+        path: relative_path(namespace, name),
+        warnings: dangling_models(pack_path, def),
+    })
+}
+
+/// 创建方块状态文件，文件已存在时报错
+pub fn create_blockstate(
+    pack_path: &Path,
+    namespace: &str,
+    name: &str,
+    def: &BlockstateDef,
+) -> Result<BlockstateResult, String> {
+    let path = blockstate_path(pack_path, namespace, name)?;
+    if path.exists() {
+        return Err(format!(
+            "Blockstate already exists: {}",
+            relative_path(namespace, name)
+        ));
+    }
+    write_blockstate(pack_path, &path, namespace, name, def)
+}
+
+/// 读取方块状态文件
+pub fn get_blockstate(
+    pack_path: &Path,
+    namespace: &str,
+    name: &str,
+) -> Result<BlockstateDef, String> {
+    let path = blockstate_path(pack_path, namespace, name)?;
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read blockstate: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse blockstate: {}", e))
+}
+
+/// 覆盖已有的方块状态文件
+pub fn update_blockstate(
+    pack_path: &Path,
+    namespace: &str,
+    name: &str,
+    def: &BlockstateDef,
+) -> Result<BlockstateResult, String> {
+    let path = blockstate_path(pack_path, namespace, name)?;
+    if !path.is_file() {
+        return Err(format!(
+            "Blockstate not found: {}",
+            relative_path(namespace, name)
+        ));
+    }
+    write_blockstate(pack_path, &path, namespace, name, def)
+}
@@ -0,0 +1,492 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::zip_handler::is_export_excluded;
+
+/// 需要检查大小写的资源目录（assets/<ns>/ 下）
+const CHECKED_DIRS: &[&str] = &["textures", "models", "blockstates", "items"];
+
+/// 文件名大小写问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CasingIssue {
+    /// file_name：文件路径含大写字母；reference：引用与磁盘上的大小写不一致
+    pub kind: String,
+    pub file: String,
+    pub reference: Option<String>,
+    pub expected: Option<String>,
+    pub message: String,
+}
+
+/// 文件名大小写检查报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CasingReport {
+    pub files_checked: usize,
+    pub references_checked: usize,
+    pub issues: Vec<CasingIssue>,
+}
+
+/// 重命名记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRename {
+    pub from: String,
+    pub to: String,
+}
+
+/// 引用更新记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceUpdate {
+    pub file: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// 文件名规范化结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizeResult {
+    pub dry_run: bool,
+    pub renamed: Vec<FileRename>,
+    pub references_updated: Vec<ReferenceUpdate>,
+    /// 小写后与其他文件重名而跳过的文件
+    pub conflicts: Vec<String>,
+}
+
+/// 引用的资源类型
+#[derive(Debug, Clone, Copy)]
+enum RefKind {
+    Model,
+    Texture,
+}
+
+impl RefKind {
+    fn folder(&self) -> &'static str {
+        match self {
+            RefKind::Model => "models",
+            RefKind::Texture => "textures",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            RefKind::Model => "json",
+            RefKind::Texture => "png",
+        }
+    }
+}
+
+/// 收集需检查的文件（相对材质包根目录，使用 /）
+fn collect_checked_files(pack_path: &Path) -> Vec<String> {
+    let mut files: Vec<String> = WalkDir::new(pack_path.join("assets"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let rel = e.path().strip_prefix(pack_path).ok()?;
+            if is_export_excluded(rel) {
+                return None;
+            }
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            let dir = rel.split('/').nth(2)?;
+            CHECKED_DIRS.contains(&dir).then_some(rel)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// 包含引用的 JSON 文件所在的资源目录（models、blockstates、items）
+fn resource_dir(relative: &str) -> Option<&str> {
+    relative
+        .split('/')
+        .nth(2)
+        .filter(|dir| matches!(*dir, "models" | "blockstates" | "items"))
+}
+
+/// 将引用解析为相对材质包根目录的文件路径
+fn reference_path(reference: &str, kind: RefKind) -> Option<String> {
+    if reference.starts_with('#') || reference.starts_with("builtin/") {
+        return None;
+    }
+    let (namespace, path) = reference.split_once(':').unwrap_or(("minecraft", reference));
+    Some(format!(
+        "assets/{}/{}/{}.{}",
+        namespace,
+        kind.folder(),
+        path,
+        kind.extension()
+    ))
+}
+
+/// 根据磁盘上的实际路径生成引用，保留原引用是否带命名空间的写法
+fn reference_for(original: &str, actual: &str, kind: RefKind) -> Option<String> {
+    let rest = actual.strip_prefix("assets/")?;
+    let (namespace, rest) = rest.split_once('/')?;
+    let path = rest
+        .strip_prefix(kind.folder())?
+        .strip_prefix('/')?
+        .strip_suffix(kind.extension())?
+        .strip_suffix('.')?;
+
+    if original.contains(':') || namespace != "minecraft" {
+        Some(format!("{}:{}", namespace, path))
+    } else {
+        Some(path.to_string())
+    }
+}
+
+/// 遍历 JSON 中的模型和贴图引用
+fn visit_references(dir: &str, json: &mut Value, visit: &mut dyn FnMut(RefKind, &mut String)) {
+    if dir == "models" {
+        if let Some(Value::String(parent)) = json.get_mut("parent") {
+            visit(RefKind::Model, parent);
+        }
+        if let Some(Value::Object(textures)) = json.get_mut("textures") {
+            for value in textures.values_mut() {
+                if let Value::String(texture) = value {
+                    visit(RefKind::Texture, texture);
+                }
+            }
+        }
+    } else {
+        // blockstates 和 items 中的模型引用都在 "model" 字段
+        visit_model_keys(json, visit);
+    }
+}
+
+fn visit_model_keys(value: &mut Value, visit: &mut dyn FnMut(RefKind, &mut String)) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if key == "model" {
+                    if let Value::String(model) = &mut *child {
+                        visit(RefKind::Model, model);
+                        continue;
+                    }
+                }
+                visit_model_keys(child, visit);
+            }
+        }
+        Value::Array(items) => {
+            for child in items {
+                visit_model_keys(child, visit);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 读取需检查引用的 JSON 文件
+fn read_json(pack_path: &Path, relative: &str) -> Option<Value> {
+    if !relative.ends_with(".json") {
+        return None;
+    }
+    let content = fs::read_to_string(pack_path.join(relative)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// 路径是否含大写字母
+fn has_uppercase(relative: &str) -> bool {
+    relative.chars().any(|c| c.is_ascii_uppercase())
+}
+
+/// 检查贴图、模型等文件名中的大写字母，以及大小写与磁盘不一致的引用
+pub fn check_filename_casing(pack_path: &Path) -> Result<CasingReport, String> {
+    let files = collect_checked_files(pack_path);
+    let existing: HashSet<&str> = files.iter().map(|f| f.as_str()).collect();
+    let mut by_lowercase: HashMap<String, &str> = HashMap::new();
+    for file in &files {
+        by_lowercase.insert(file.to_lowercase(), file);
+    }
+
+    let mut issues = Vec::new();
+    let mut references_checked = 0;
+
+    for file in &files {
+        if has_uppercase(file) {
+            let extension_upper = Path::new(file)
+                .extension()
+                .map(|ext| has_uppercase(&ext.to_string_lossy()))
+                .unwrap_or(false);
+            issues.push(CasingIssue {
+                kind: "file_name".to_string(),
+                file: file.clone(),
+                reference: None,
+                expected: Some(file.to_lowercase()),
+                message: if extension_upper {
+                    "File extension is not lowercase".to_string()
+                } else {
+                    "File path contains uppercase letters".to_string()
+                },
+            });
+        }
+
+        let dir = match resource_dir(file) {
+            Some(dir) => dir,
+            None => continue,
+        };
+        let mut json = match read_json(pack_path, file) {
+            Some(json) => json,
+            None => continue,
+        };
+
+        visit_references(dir, &mut json, &mut |kind, reference| {
+            let target = match reference_path(reference, kind) {
+                Some(target) => target,
+                None => return,
+            };
+            references_checked += 1;
+
+            // 大小写完全一致；忽略大小写也找不到时说明引用的是原版等包外资源
+            if existing.contains(target.as_str()) {
+                return;
+            }
+            if let Some(actual) = by_lowercase.get(&target.to_lowercase()) {
+                issues.push(CasingIssue {
+                    kind: "reference".to_string(),
+                    file: file.clone(),
+                    reference: Some(reference.clone()),
+                    expected: reference_for(reference, actual, kind),
+                    message: format!("Reference does not match on-disk casing: {}", actual),
+                });
+            }
+        });
+    }
+
+    Ok(CasingReport {
+        files_checked: files.len(),
+        references_checked,
+        issues,
+    })
+}
+
+/// 将文件名改为小写并更新模型和方块状态中的引用，`dry_run` 时只返回将要进行的修改
+pub fn normalize_filenames(pack_path: &Path, dry_run: bool) -> Result<NormalizeResult, String> {
+    let files = collect_checked_files(pack_path);
+
+    let mut groups: HashMap<String, Vec<&str>> = HashMap::new();
+    for file in &files {
+        groups.entry(file.to_lowercase()).or_default().push(file);
+    }
+
+    // 小写路径 -> 规范化后的路径；重名的文件保持原样且不参与引用修正
+    let mut renamed = Vec::new();
+    let mut conflicts = Vec::new();
+    let mut final_paths: HashMap<String, String> = HashMap::new();
+    for (lowercase, group) in &groups {
+        if group.len() > 1 {
+            conflicts.extend(group.iter().filter(|f| has_uppercase(f)).map(|f| f.to_string()));
+            continue;
+        }
+        let file = group[0];
+        if file != lowercase.as_str() {
+            renamed.push(FileRename {
+                from: file.to_string(),
+                to: lowercase.clone(),
+            });
+        }
+        final_paths.insert(lowercase.clone(), lowercase.clone());
+    }
+    renamed.sort_by(|a, b| a.from.cmp(&b.from));
+    conflicts.sort();
+
+    let renamed_to: HashMap<&str, &str> = renamed
+        .iter()
+        .map(|r| (r.from.as_str(), r.to.as_str()))
+        .collect();
+
+    // 计算每个 JSON 文件中需要更新的引用
+    let mut references_updated = Vec::new();
+    let mut rewritten: Vec<(String, Value)> = Vec::new();
+    for file in &files {
+        let dir = match resource_dir(file) {
+            Some(dir) => dir,
+            None => continue,
+        };
+        let mut json = match read_json(pack_path, file) {
+            Some(json) => json,
+            None => continue,
+        };
+        let final_file = renamed_to.get(file.as_str()).copied().unwrap_or(file.as_str());
+
+        let mut changed = false;
+        visit_references(dir, &mut json, &mut |kind, reference| {
+            let target = match reference_path(reference, kind) {
+                Some(target) => target,
+                None => return,
+            };
+            let actual = match final_paths.get(&target.to_lowercase()) {
+                Some(actual) if *actual != target => actual,
+                _ => return,
+            };
+            if let Some(updated) = reference_for(reference, actual, kind) {
+                references_updated.push(ReferenceUpdate {
+                    file: final_file.to_string(),
+                    from: reference.clone(),
+                    to: updated.clone(),
+                });
+                *reference = updated;
+                changed = true;
+            }
+        });
+
+        if changed {
+            rewritten.push((final_file.to_string(), json));
+        }
+    }
+
+    if !dry_run {
+        let dir_renames = rename_uppercase_dirs(pack_path, &renamed, &conflicts)?;
+        for rename in &renamed {
+            let from = remap_path(&rename.from, &dir_renames);
+            if from != rename.to {
+                rename_case_safe(pack_path, &from, &rename.to)?;
+            }
+        }
+        for (file, json) in &rewritten {
+            let content = serde_json::to_string_pretty(json)
+                .map_err(|e| format!("Failed to serialize {}: {}", file, e))?;
+            fs::write(pack_path.join(file), content)
+                .map_err(|e| format!("Failed to write {}: {}", file, e))?;
+        }
+        for rename in &renamed {
+            remove_empty_parents(pack_path, &remap_path(&rename.from, &dir_renames));
+        }
+    }
+
+    Ok(NormalizeResult {
+        dry_run,
+        renamed,
+        references_updated,
+        conflicts,
+    })
+}
+
+/// 按已执行的目录重命名换算路径
+fn remap_path(relative: &str, dir_renames: &[(String, String)]) -> String {
+    let mut path = relative.to_string();
+    for (from, to) in dir_renames {
+        if let Some(rest) = path.strip_prefix(from.as_str()) {
+            if rest.is_empty() || rest.starts_with('/') {
+                path = format!("{}{}", to, rest);
+            }
+        }
+    }
+    path
+}
+
+/// 将含大写字母的目录改为小写，由浅到深进行，返回按顺序执行的 (原路径, 新路径)
+///
+/// 只移动文件时大小写不敏感的文件系统会保留原目录名，所以目录需要单独重命名；
+/// 包含重名文件的目录、或已存在同名小写目录（大小写敏感的文件系统）时跳过，由文件逐个移动
+fn rename_uppercase_dirs(
+    pack_path: &Path,
+    renamed: &[FileRename],
+    conflicts: &[String],
+) -> Result<Vec<(String, String)>, String> {
+    let mut dirs: Vec<String> = renamed
+        .iter()
+        .flat_map(|rename| {
+            let parts: Vec<&str> = rename.from.split('/').collect();
+            (1..parts.len())
+                .filter(|&depth| has_uppercase(parts[depth - 1]))
+                .map(move |depth| parts[..depth].join("/"))
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .filter(|dir| {
+            let prefix = format!("{}/", dir);
+            !conflicts.iter().any(|conflict| conflict.starts_with(&prefix))
+        })
+        .collect();
+    dirs.sort_by(|a, b| {
+        let depth = |path: &str| path.matches('/').count();
+        depth(a).cmp(&depth(b)).then_with(|| a.cmp(b))
+    });
+
+    let mut dir_renames = Vec::new();
+    for dir in dirs {
+        let current = remap_path(&dir, &dir_renames);
+        let (parent, name) = match current.rsplit_once('/') {
+            Some((parent, name)) => (Some(parent), name),
+            None => (None, current.as_str()),
+        };
+        let lowercase = name.to_lowercase();
+        let parent_path = parent.map(|p| pack_path.join(p)).unwrap_or_else(|| pack_path.to_path_buf());
+        let exists_separately = fs::read_dir(&parent_path)
+            .map(|entries| entries.flatten().any(|entry| entry.file_name() == lowercase.as_str()))
+            .unwrap_or(false);
+        if exists_separately {
+            continue;
+        }
+
+        let target = match parent {
+            Some(parent) => format!("{}/{}", parent, lowercase),
+            None => lowercase,
+        };
+        rename_case_safe(pack_path, &current, &target)?;
+        dir_renames.push((current, target));
+    }
+    Ok(dir_renames)
+}
+
+/// 重命名文件或目录，经过临时名称中转以兼容大小写不敏感的文件系统
+fn rename_case_safe(pack_path: &Path, from: &str, to: &str) -> Result<(), String> {
+    let source = pack_path.join(from);
+    let target = pack_path.join(to);
+    let temp = pack_path.join(format!("{}.little100-rename", from));
+
+    fs::rename(&source, &temp).map_err(|e| format!("Failed to rename {}: {}", from, e))?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory for {}: {}", to, e))?;
+    }
+    fs::rename(&temp, &target).map_err(|e| format!("Failed to rename {} to {}: {}", from, to, e))
+}
+
+/// 删除重命名后留下的空目录（直到 assets/）
+fn remove_empty_parents(pack_path: &Path, relative: &str) {
+    let assets = pack_path.join("assets");
+    let mut dir = pack_path.join(relative);
+    while let Some(parent) = dir.parent() {
+        if parent == assets || !parent.starts_with(&assets) {
+            break;
+        }
+        // 非空目录删除会失败，说明已到达仍在使用的目录
+        if fs::remove_dir(parent).is_err() {
+            break;
+        }
+        dir = parent.to_path_buf();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_renames_uppercase_directories() {
+        let dir = std::env::temp_dir().join(format!("little100_casing_{}", std::process::id()));
+        let textures = dir.join("assets/minecraft/textures/Block");
+        fs::create_dir_all(&textures).unwrap();
+        fs::write(textures.join("Stone.png"), b"png").unwrap();
+
+        let result = normalize_filenames(&dir, false);
+        let entries: Vec<String> = fs::read_dir(dir.join("assets/minecraft/textures"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let renamed_file = dir.join("assets/minecraft/textures/block/stone.png").is_file();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(result.unwrap().renamed.len(), 1);
+        assert_eq!(entries, vec!["block".to_string()]);
+        assert!(renamed_file);
+    }
+}
@@ -1,17 +1,20 @@
 use crate::constants;
+use crate::error::{io_error, AppError, AppResult};
 use crate::image_handler::{get_image_info, ImageInfo};
 use crate::pack_parser::{scan_pack_directory, PackInfo};
 use crate::pack_merger::{
     preview_merge, execute_merge_async, PackSourceType, MergePreview, MergeResult,
     MergeSourceInput, MergeConfig,
 };
-use crate::path_security::{resolve_pack_path, get_pack_base_path};
+use crate::path_security::{canonical_path, resolve_pack_path, get_pack_base_path};
 use crate::preloader::ImagePreloader;
 use crate::zip_handler::{
-    cleanup_temp_files, create_zip, extract_zip, get_temp_extract_dir, validate_pack_zip,
+    cleanup_temp_files, create_zip, extract_zip_subfolder, get_temp_extract_dir, inspect_pack_zip,
+    ImportError,
 };
 use font_kit::source::SystemSource;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -32,11 +35,31 @@ fn find_available_extract_path(parent: &Path, stem: &str) -> PathBuf {
     candidate
 }
 
+/// 已打开的材质包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenPack {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub active: bool,
+}
+
 /// 应用状态
 pub struct AppState {
     pub current_pack_path: Mutex<Option<PathBuf>>,
     pub current_pack_info: Mutex<Option<PackInfo>>,
     pub preloader: Arc<ImagePreloader>,
+    pub readonly_packs: Mutex<HashSet<PathBuf>>,
+    pub open_packs: Mutex<HashMap<String, (PathBuf, PackInfo)>>,
+    pub active_pack_id: Mutex<Option<String>>,
+    pub live_deploy: Mutex<Option<crate::deploy::LiveDeploy>>,
+    pub thread_pool: Mutex<Arc<rayon::ThreadPool>>,
+    /// 正在运行的分析任务的取消令牌
+    pub analysis_tokens: Mutex<HashMap<String, tokio_util::sync::CancellationToken>>,
+    /// 前端同步过来的历史记录设置
+    pub history_settings: Mutex<crate::history_manager::HistorySettings>,
+    /// 每个原版版本的缓存锁，避免并发下载解压同一版本
+    pub vanilla_cache_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
 }
 
 impl Default for AppState {
@@ -44,23 +67,376 @@ impl Default for AppState {
         Self {
             current_pack_path: Mutex::new(None),
             current_pack_info: Mutex::new(None),
-            preloader: Arc::new(ImagePreloader::new(constants::PRELOADER_CACHE_SIZE)),
+            preloader: Arc::new(ImagePreloader::new(constants::PRELOADER_CACHE_BYTES)),
+            readonly_packs: Mutex::new(HashSet::new()),
+            open_packs: Mutex::new(HashMap::new()),
+            active_pack_id: Mutex::new(None),
+            live_deploy: Mutex::new(None),
+            thread_pool: Mutex::new(Arc::new(
+                crate::preloader::build_thread_pool(crate::preloader::default_parallelism())
+                    .expect("Failed to build thread pool"),
+            )),
+            analysis_tokens: Mutex::new(HashMap::new()),
+            history_settings: Mutex::new(Default::default()),
+            vanilla_cache_locks: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// 注册已打开的材质包，路径已打开时复用原 id
+fn register_open_pack(state: &AppState, path: &Path, info: &PackInfo) -> String {
+    let mut open_packs = state.open_packs.lock();
+    let existing = open_packs
+        .iter()
+        .find(|(_, (p, _))| p == path)
+        .map(|(id, _)| id.clone());
+    let id = existing.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    open_packs.insert(id.clone(), (path.to_path_buf(), info.clone()));
+    id
+}
+
+/// 设置当前活动材质包
+fn set_active_pack(state: &AppState, path: &Path, info: PackInfo) -> String {
+    let id = register_open_pack(state, path, &info);
+    *state.active_pack_id.lock() = Some(id.clone());
+    *state.current_pack_path.lock() = Some(path.to_path_buf());
+    *state.current_pack_info.lock() = Some(info);
+    id
+}
+
+/// 获取指定材质包根目录，未指定时使用当前活动材质包
+fn pack_base_for(state: &AppState, pack_id: Option<&str>) -> Result<PathBuf, String> {
+    match pack_id {
+        Some(id) => state
+            .open_packs
+            .lock()
+            .get(id)
+            .map(|(path, _)| path.clone())
+            .ok_or_else(|| format!("Pack not open: {}", id)),
+        None => get_pack_base_path(&state.current_pack_path),
+    }
+}
+
+/// 解析指定材质包内的路径
+fn resolve_pack_path_for(
+    state: &AppState,
+    pack_id: Option<&str>,
+    file_path: &str,
+) -> Result<PathBuf, String> {
+    match pack_id {
+        Some(_) => {
+            let base = pack_base_for(state, pack_id)?;
+            crate::path_security::resolve_safe_path(file_path, Some(&base))
+        }
+        None => resolve_pack_path(file_path, &state.current_pack_path),
+    }
+}
+
+/// 解析材质包内的文件，返回 (材质包根目录, 相对路径)
+fn pack_relative_for(
+    state: &AppState,
+    pack_id: Option<&str>,
+    file_path: &str,
+) -> Result<(PathBuf, PathBuf), String> {
+    let base = pack_base_for(state, pack_id)?;
+    let full_path = resolve_pack_path_for(state, pack_id, file_path)?;
+    let relative = full_path
+        .strip_prefix(crate::path_security::normalize_path_public(&base))
+        .or_else(|_| full_path.strip_prefix(&base))
+        .map_err(|_| format!("Access denied: path '{}' is outside the pack", file_path))?
+        .to_path_buf();
+    Ok((base, relative))
+}
+
+/// 备份即将修改的路径，用于整包撤销（undo_last_operation），备份在阻塞线程中进行
+///
+/// 记录失败时只写日志，不影响操作本身
+async fn begin_operation(
+    state: &AppState,
+    pack_id: Option<&str>,
+    description: String,
+    paths: &[&str],
+) -> Option<(PathBuf, crate::history_manager::PendingOperation)> {
+    let resolve = || -> Result<_, String> {
+        let base = pack_base_for(state, pack_id)?;
+        let mut relative = Vec::new();
+        for path in paths {
+            let (_, rel) = pack_relative_for(state, pack_id, path)?;
+            relative.push(rel.to_string_lossy().replace('\\', "/"));
+        }
+        Ok((base, relative))
+    };
+    let begin = async {
+        let (base, relative) = resolve()?;
+        tokio::task::spawn_blocking(move || {
+            let pending = crate::history_manager::begin_operation(&base, description, &relative)?;
+            Ok::<_, String>((base, pending))
+        })
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+    };
+    begin
+        .await
+        .map_err(|e| log::warn!("Failed to record operation: {}", e))
+        .ok()
+}
+
+/// 操作成功后在阻塞线程中备份新状态并写入操作日志
+async fn commit_operation(pending: Option<(PathBuf, crate::history_manager::PendingOperation)>) {
+    let Some((base, pending)) = pending else {
+        return;
+    };
+    let result =
+        tokio::task::spawn_blocking(move || crate::history_manager::commit_operation(&base, pending))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))
+            .and_then(|result| result);
+    if let Err(e) = result {
+        log::warn!("Failed to record operation: {}", e);
+    }
+}
+
+/// 检查材质包是否可写
+fn ensure_pack_writable(state: &AppState, pack_id: Option<&str>) -> AppResult<()> {
+    let pack_path = match pack_id {
+        Some(_) => Some(pack_base_for(state, pack_id)?),
+        None => state.current_pack_path.lock().clone(),
+    };
+    match pack_path {
+        Some(path) => ensure_path_writable(state, &path),
+        None => Ok(()),
+    }
+}
+
+/// 检查指定路径的材质包是否可写，供按路径操作的命令使用
+pub(crate) fn ensure_path_writable(state: &AppState, pack_path: &Path) -> AppResult<()> {
+    if state.readonly_packs.lock().contains(pack_path) {
+        return Err(AppError::ReadOnly("Pack is read-only".to_string()));
+    }
+    Ok(())
+}
+
+/// 批量修改类命令的 dry_run 约定：默认只预览，返回将要进行的修改而不写入；
+/// 实际执行前检查材质包（`pack_id` 为 None 时为当前材质包）是否可写
+fn resolve_dry_run(state: &AppState, pack_id: Option<&str>, dry_run: Option<bool>) -> Result<bool, String> {
+    let dry_run = dry_run.unwrap_or(true);
+    if !dry_run {
+        ensure_pack_writable(state, pack_id)?;
+    }
+    Ok(dry_run)
+}
+
+/// 设置材质包只读
+#[tauri::command]
+pub async fn set_pack_readonly(
+    readonly: bool,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let path = pack_base_for(&state, pack_id.as_deref())?;
+    let mut readonly_packs = state.readonly_packs.lock();
+    if readonly {
+        readonly_packs.insert(path);
+    } else {
+        readonly_packs.remove(&path);
+    }
+    Ok(())
+}
+
+/// 获取材质包是否只读
+#[tauri::command]
+pub async fn is_pack_readonly(
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let path = pack_base_for(&state, pack_id.as_deref())?;
+    Ok(state.readonly_packs.lock().contains(&path))
+}
+
+/// 打开材质包文件夹（不改变当前活动材质包，除非指定 activate）
+#[tauri::command]
+pub async fn open_pack(
+    folder_path: String,
+    activate: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<OpenPack, String> {
+    let path = Path::new(&folder_path);
+
+    if !path.exists() {
+        return Err("Folder does not exist".to_string());
+    }
+
+    let pack_info = scan_pack_directory(path)?;
+    let name = pack_info.name.clone();
+    let active = activate.unwrap_or(false);
+
+    let id = if active {
+        set_active_pack(&state, path, pack_info)
+    } else {
+        register_open_pack(&state, path, &pack_info)
+    };
+
+    Ok(OpenPack {
+        id,
+        name,
+        path: path.to_string_lossy().to_string(),
+        active,
+    })
+}
+
+/// 关闭已打开的材质包
+#[tauri::command]
+pub async fn close_pack(pack_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    if state.open_packs.lock().remove(&pack_id).is_none() {
+        return Err(format!("Pack not open: {}", pack_id));
+    }
+
+    let mut active_id = state.active_pack_id.lock();
+    if active_id.as_deref() == Some(pack_id.as_str()) {
+        *active_id = None;
+        *state.current_pack_path.lock() = None;
+        *state.current_pack_info.lock() = None;
+    }
+
+    Ok(())
+}
+
+/// 列出所有已打开的材质包
+#[tauri::command]
+pub async fn list_open_packs(state: State<'_, AppState>) -> Result<Vec<OpenPack>, String> {
+    let active_id = state.active_pack_id.lock().clone();
+    let mut packs: Vec<OpenPack> = state
+        .open_packs
+        .lock()
+        .iter()
+        .map(|(id, (path, info))| OpenPack {
+            id: id.clone(),
+            name: info.name.clone(),
+            path: path.to_string_lossy().to_string(),
+            active: active_id.as_deref() == Some(id.as_str()),
+        })
+        .collect();
+    packs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(packs)
+}
+
+/// 切换当前活动材质包
+#[tauri::command]
+pub async fn switch_active_pack(
+    pack_id: String,
+    state: State<'_, AppState>,
+) -> Result<PackInfo, String> {
+    let (path, info) = state
+        .open_packs
+        .lock()
+        .get(&pack_id)
+        .cloned()
+        .ok_or_else(|| format!("Pack not open: {}", pack_id))?;
+
+    set_active_pack(&state, &path, info.clone());
+    Ok(info)
+}
+
+/// 在两个已打开的材质包之间复制文件或文件夹
+#[tauri::command]
+pub async fn copy_between_packs(
+    src_pack: String,
+    src_path: String,
+    dest_pack: String,
+    dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    ensure_pack_writable(&state, Some(&dest_pack))?;
+    let src = resolve_pack_path_for(&state, Some(&src_pack), &src_path)?;
+    let dest = resolve_pack_path_for(&state, Some(&dest_pack), &dest_path)?;
+
+    tokio::task::spawn_blocking(move || {
+        let (canonical_src, canonical_dest) = (canonical_path(&src), canonical_path(&dest));
+        if canonical_src == canonical_dest {
+            return Err("Source and destination are the same path".to_string());
+        }
+        if src.is_dir() && canonical_dest.starts_with(&canonical_src) {
+            return Err("Cannot copy a folder into itself".to_string());
+        }
+        let copied = copy_path_recursive(&src, &dest)?;
+        for entry in walkdir::WalkDir::new(&dest).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_file() {
+                crate::history_manager::notify_file_changed(entry.path());
+            }
+        }
+        Ok(copied)
+    })
+    .await
+    .map_err(|e| format!("Failed to copy: {}", e))?
+}
+
+/// 复制文件或文件夹，返回复制的文件数
+fn copy_path_recursive(src: &Path, dest: &Path) -> Result<usize, String> {
+    if src.is_file() {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        fs::copy(src, dest).map_err(|e| format!("Failed to copy file: {}", e))?;
+        crate::image_handler::invalidate_image_caches(dest);
+        return Ok(1);
+    }
+
+    if !src.is_dir() {
+        return Err(format!("Source does not exist: {}", src.display()));
+    }
+
+    let mut count = 0;
+    for entry in walkdir::WalkDir::new(src).into_iter().filter_map(|e| e.ok()) {
+        let rel = entry
+            .path()
+            .strip_prefix(src)
+            .map_err(|e| format!("Failed to strip prefix: {}", e))?;
+        let target = dest.join(rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        } else {
+            count += copy_path_recursive(entry.path(), &target)?;
         }
     }
+    Ok(count)
 }
 
 /// 导入材质包
+///
+/// 所有内容位于单个顶层目录时自动以该目录为根导入，并在 `flattened_root` 中返回该目录名；
+/// 其他嵌套情况返回 `McmetaNotAtRoot`，前端可传入 `subfolder` 从该目录重新导入；
+/// 模组资源返回 `ModAssets`，前端可传入 `wrap_pack_format` 只导入 assets/ 并生成 pack.mcmeta；
+/// 跳过的不安全条目等信息在 `import_report` 中返回
 #[tauri::command]
 pub async fn import_pack_zip(
     zip_path: String,
+    subfolder: Option<String>,
+    wrap_pack_format: Option<i32>,
     state: State<'_, AppState>,
-) -> Result<PackInfo, String> {
+) -> Result<PackInfo, ImportError> {
     let zip_path = Path::new(&zip_path);
 
     // 验证ZIP文件
-    if !validate_pack_zip(zip_path)? {
-        return Err("Invalid resource pack: pack.mcmeta not found".to_string());
-    }
+    let inspected = inspect_pack_zip(zip_path);
+    let wrap_pack_format = match (&inspected, &subfolder) {
+        (Err(ImportError::ModAssets { .. }), None) => wrap_pack_format,
+        _ => None,
+    };
+    let subfolder = match (inspected, subfolder) {
+        (Err(ImportError::ModAssets { .. }), None) if wrap_pack_format.is_some() => None,
+        (Ok(None), None) => None,
+        (Ok(Some(root)), None) => Some(root),
+        (Ok(Some(root)), Some(requested))
+        | (Err(ImportError::McmetaNotAtRoot { root }), Some(requested))
+            if root == requested =>
+        {
+            Some(root)
+        }
+        (Ok(_), Some(_)) => return Err(ImportError::NoMcmeta),
+        (Err(e), _) => return Err(e),
+    };
 
     let zip_canon = fs::canonicalize(zip_path)
         .map_err(|e| format!("无法访问 ZIP 文件: {}", e))?;
@@ -78,17 +454,31 @@ pub async fn import_pack_zip(
 
     let extract_path = find_available_extract_path(parent, &stem);
 
-    extract_zip(zip_path, &extract_path)?;
+    let skipped_entries = match wrap_pack_format {
+        Some(pack_format) => {
+            let roots = crate::version_downloader::normalize_extract_roots(None);
+            let summary =
+                crate::version_downloader::extract_from_jar(zip_path, &extract_path, &roots, None)?;
+            crate::pack_creator::write_pack_mcmeta(&extract_path, pack_format, &stem)?;
+            summary.skipped_entries
+        }
+        None => extract_zip_subfolder(zip_path, &extract_path, subfolder.as_deref())?,
+    };
 
     // 扫描材质包
     let mut pack_info = scan_pack_directory(&extract_path)?;
 
     // 添加解压路径
     pack_info.pack_path = Some(extract_path.to_string_lossy().to_string());
+    pack_info.import_report = Some(crate::zip_handler::ImportReport {
+        skipped_entries,
+        found_mcmeta: wrap_pack_format.is_none(),
+    });
+    pack_info.flattened_root = subfolder;
+    pack_info.format_warnings = crate::image_handler::find_format_mismatches(&extract_path);
 
     // 保存状态
-    *state.current_pack_path.lock() = Some(extract_path);
-    *state.current_pack_info.lock() = Some(pack_info.clone());
+    set_active_pack(&state, &extract_path, pack_info.clone());
 
     Ok(pack_info)
 }
@@ -118,11 +508,11 @@ pub async fn import_pack_folder(
     }
 
     // 扫描材质包
-    let pack_info = scan_pack_directory(folder_path)?;
+    let mut pack_info = scan_pack_directory(folder_path)?;
+    pack_info.format_warnings = crate::image_handler::find_format_mismatches(folder_path);
 
     // 保存状态
-    *state.current_pack_path.lock() = Some(folder_path.to_path_buf());
-    *state.current_pack_info.lock() = Some(pack_info.clone());
+    set_active_pack(&state, folder_path, pack_info.clone());
 
     Ok(pack_info)
 }
@@ -149,19 +539,103 @@ pub async fn get_current_pack_path(state: State<'_, AppState>) -> Result<String,
 pub async fn get_image_thumbnail(
     image_path: String,
     max_size: u32,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
-    let full_path = resolve_pack_path(&image_path, &state.current_pack_path)?;
-    crate::image_handler::create_thumbnail_async(full_path, max_size).await
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &image_path)?;
+    if let Some(data) = preloaded_thumbnail(&state, pack_id.as_deref(), &image_path, max_size) {
+        return Ok(data);
+    }
+    let writable = !state.readonly_packs.lock().contains(&base_path);
+    crate::image_handler::create_thumbnail_disk_cached_async(base_path, full_path, max_size, writable)
+        .await
+}
+
+/// 预加载尺寸的缩略图先从预加载缓存读取（只缓存当前材质包），未命中时返回 None
+fn preloaded_thumbnail(
+    state: &AppState,
+    pack_id: Option<&str>,
+    image_path: &str,
+    max_size: u32,
+) -> Option<String> {
+    if pack_id.is_some() || max_size != constants::PRELOAD_THUMBNAIL_SIZE {
+        return None;
+    }
+    let (base, relative) = pack_relative_for(state, None, image_path).ok()?;
+    state
+        .preloader
+        .get_cached(&relative.to_string_lossy(), &base.join(&relative))
+}
+
+/// 批量缩略图结果，失败的文件单独列出
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ThumbnailBatchResult {
+    pub thumbnails: HashMap<String, String>,
+    pub errors: HashMap<String, String>,
+}
+
+/// 批量获取缩略图，减少逐个调用的开销
+#[tauri::command]
+pub async fn get_thumbnails_batch(
+    paths: Vec<String>,
+    size: u32,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ThumbnailBatchResult, String> {
+    if paths.len() > constants::MAX_THUMBNAIL_BATCH_SIZE {
+        return Err(format!(
+            "Too many paths in batch: {} (max {})",
+            paths.len(),
+            constants::MAX_THUMBNAIL_BATCH_SIZE
+        ));
+    }
+
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    let mut errors = HashMap::new();
+    let mut entries = Vec::with_capacity(paths.len());
+
+    for path in paths {
+        match resolve_pack_path_for(&state, pack_id.as_deref(), &path) {
+            Ok(full_path) => entries.push((path, full_path)),
+            Err(e) => {
+                errors.insert(path, e);
+            }
+        }
+    }
+
+    let preloader = state.preloader.clone();
+    let pool = state.thread_pool.lock().clone();
+    let writable = !state.readonly_packs.lock().contains(&base_path);
+    let results = tokio::task::spawn_blocking(move || {
+        preloader.thumbnails_batch(entries, &base_path, size, writable, &pool)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?;
+
+    let mut thumbnails = HashMap::new();
+    for (path, result) in results {
+        match result {
+            Ok(data) => {
+                thumbnails.insert(path, data);
+            }
+            Err(e) => {
+                errors.insert(path, e);
+            }
+        }
+    }
+
+    Ok(ThumbnailBatchResult { thumbnails, errors })
 }
 
 #[tauri::command]
 pub async fn get_image_preview(
     image_path: String,
     size: String,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
-    let full_path = resolve_pack_path(&image_path, &state.current_pack_path)?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &image_path)?;
 
     let max_size = match size.as_str() {
         "thumbnail" => constants::IMAGE_SIZE_THUMBNAIL,
@@ -170,6 +644,9 @@ pub async fn get_image_preview(
         _ => constants::IMAGE_SIZE_DEFAULT,
     };
 
+    if let Some(data) = preloaded_thumbnail(&state, pack_id.as_deref(), &image_path, max_size) {
+        return Ok(data);
+    }
     crate::image_handler::create_thumbnail_async(full_path, max_size).await
 }
 
@@ -177,126 +654,937 @@ pub async fn get_image_preview(
 #[tauri::command]
 pub async fn get_image_details(
     image_path: String,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<ImageInfo, String> {
-    let full_path = resolve_pack_path(&image_path, &state.current_pack_path)?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &image_path)?;
     get_image_info(&full_path)
 }
 
-/// 导出材质包
+/// 获取图片的颜色类型、位深、透明度、调色板大小和动画帧数
 #[tauri::command]
-pub async fn export_pack(output_path: String, state: State<'_, AppState>) -> Result<(), String> {
-    let path = get_pack_base_path(&state.current_pack_path)?;
-    let output = Path::new(&output_path);
-    create_zip(&path, output)?;
-    Ok(())
-}
+pub async fn get_image_full_metadata(
+    image_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::image_handler::ImageMetadata, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &image_path)?;
 
-/// 清理临时文件
-#[tauri::command]
-pub async fn cleanup_temp() -> Result<(), String> {
-    cleanup_temp_files()
+    tokio::task::spawn_blocking(move || crate::image_handler::get_image_metadata(&full_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
 }
 
-/// 读取文件内容 
+/// 查找材质包中扩展名与内容不一致的图片
 #[tauri::command]
-pub async fn read_file_content(
-    file_path: String,
+pub async fn check_image_formats(
     state: State<'_, AppState>,
-) -> Result<String, String> {
-    let full_path = resolve_pack_path(&file_path, &state.current_pack_path)?;
-    tokio::fs::read_to_string(&full_path)
+) -> Result<Vec<crate::image_handler::FormatMismatch>, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+
+    tokio::task::spawn_blocking(move || crate::image_handler::find_format_mismatches(&base_path))
         .await
-        .map_err(|e| format!("Failed to read file: {}", e))
+        .map_err(|e| format!("Task join error: {}", e))
 }
 
-/// 读取文件内容
+/// 将图片重新编码为扩展名对应的格式
 #[tauri::command]
-pub async fn read_file_binary(
-    file_path: String,
+pub async fn fix_image_format(
+    image_path: String,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<Vec<u8>, String> {
-    let full_path = resolve_pack_path(&file_path, &state.current_pack_path)?;
-    tokio::fs::read(&full_path)
-        .await
-        .map_err(|e| format!("Failed to read file: {}", e))
+) -> Result<ImageInfo, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &image_path)?;
+
+    tokio::task::spawn_blocking(move || {
+        crate::image_handler::fix_image_format(&full_path)?;
+        crate::history_manager::notify_file_changed(&full_path);
+        get_image_info(&full_path)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
 }
 
+/// 获取图片的 R、G、B、A 及亮度直方图，默认忽略完全透明的像素
 #[tauri::command]
-pub async fn write_file_content(
-    file_path: String,
-    content: String,
+pub async fn get_image_histogram(
+    image_path: String,
+    include_transparent: Option<bool>,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let full_path = resolve_pack_path(&file_path, &state.current_pack_path)?;
+) -> Result<crate::image_handler::ImageHistogram, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &image_path)?;
+    let include_transparent = include_transparent.unwrap_or(false);
 
-    // 创建父目录
-    if let Some(parent) = full_path.parent() {
-        tokio::fs::create_dir_all(parent)
-            .await
-            .map_err(|e| format!("Failed to create directory: {}", e))?;
-    }
+    tokio::task::spawn_blocking(move || {
+        crate::image_handler::get_image_histogram(&full_path, include_transparent)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
 
-    tokio::fs::write(&full_path, content)
-        .await
-        .map_err(|e| format!("Failed to write file: {}", e))
+/// 在图片写回前保存历史记录，再执行 `edit`，返回编辑后的图片信息
+async fn edit_image_with_history<F>(
+    state: &AppState,
+    pack_id: Option<&str>,
+    image_path: &str,
+    edit: F,
+) -> Result<ImageInfo, String>
+where
+    F: FnOnce(&Path) -> Result<(), String> + Send + 'static,
+{
+    ensure_pack_writable(state, pack_id)?;
+    let (base_path, relative) = pack_relative_for(state, pack_id, image_path)?;
+    let history = *state.history_settings.lock();
+
+    tokio::task::spawn_blocking(move || {
+        let full_path = base_path.join(&relative);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        crate::history_manager::snapshot_image(&base_path, &relative, history)?;
+        edit(&full_path)?;
+        crate::history_manager::notify_file_changed(&full_path);
+        get_image_info(&full_path)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
 }
 
-/// 创建新文件
+/// 调整图片亮度、对比度和伽马（保留透明度），写入前保存历史记录
 #[tauri::command]
-pub async fn create_new_file(
-    file_path: String,
-    content: String,
+pub async fn adjust_levels(
+    image_path: String,
+    brightness: f32,
+    contrast: f32,
+    gamma: f32,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let full_path = resolve_pack_path(&file_path, &state.current_pack_path)?;
-
-    // 创建父目录
-    if let Some(parent) = full_path.parent() {
-        tokio::fs::create_dir_all(parent)
-            .await
-            .map_err(|e| format!("Failed to create directory: {}", e))?;
-    }
-
-    // 写入文件
-    tokio::fs::write(&full_path, content)
-        .await
-        .map_err(|e| format!("Failed to create file: {}", e))?;
-
-    Ok(())
+) -> Result<ImageInfo, String> {
+    edit_image_with_history(&state, pack_id.as_deref(), &image_path, move |path| {
+        crate::image_handler::adjust_levels(path, brightness, contrast, gamma)
+    })
+    .await
 }
 
-/// 创建新文件夹
+/// 水平或竖直翻转图片，写入前保存历史记录
 #[tauri::command]
-pub async fn create_new_folder(
-    folder_path: String,
+pub async fn flip_image(
+    image_path: String,
+    direction: crate::image_handler::FlipDirection,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let full_path = resolve_pack_path(&folder_path, &state.current_pack_path)?;
-    tokio::fs::create_dir_all(&full_path)
-        .await
-        .map_err(|e| format!("Failed to create folder: {}", e))?;
-    Ok(())
+) -> Result<ImageInfo, String> {
+    edit_image_with_history(&state, pack_id.as_deref(), &image_path, move |path| {
+        crate::image_handler::flip_image(path, direction)
+    })
+    .await
 }
 
-/// 删除文件
+/// 顺时针旋转图片 90、180 或 270 度，写入前保存历史记录
 #[tauri::command]
-pub async fn delete_file(file_path: String, state: State<'_, AppState>) -> Result<(), String> {
-    let full_path = resolve_pack_path(&file_path, &state.current_pack_path)?;
-
-    let metadata = tokio::fs::metadata(&full_path)
-        .await
-        .map_err(|e| format!("Failed to get file metadata: {}", e))?;
+pub async fn rotate_image(
+    image_path: String,
+    degrees: u32,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ImageInfo, String> {
+    edit_image_with_history(&state, pack_id.as_deref(), &image_path, move |path| {
+        crate::image_handler::rotate_image(path, degrees)
+    })
+    .await
+}
 
-    if metadata.is_dir() {
+/// 裁剪图片，写入前保存历史记录
+#[tauri::command]
+pub async fn crop_image(
+    image_path: String,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ImageInfo, String> {
+    edit_image_with_history(&state, pack_id.as_deref(), &image_path, move |path| {
+        crate::image_handler::crop_image(path, x, y, width, height)
+    })
+    .await
+}
+
+/// 批量色阶调整结果，失败的文件单独列出；预览时 `adjusted` 为将要调整的文件
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LevelsBatchResult {
+    pub dry_run: bool,
+    pub adjusted: Vec<String>,
+    pub errors: HashMap<String, String>,
+}
+
+/// 对文件夹中的图片（GIF 除外）批量调整色阶，`recursive` 为 true 时包含子文件夹，默认只预览
+#[tauri::command]
+pub async fn adjust_levels_batch(
+    folder_path: String,
+    brightness: f32,
+    contrast: f32,
+    gamma: f32,
+    recursive: Option<bool>,
+    dry_run: Option<bool>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<LevelsBatchResult, String> {
+    let dry_run = resolve_dry_run(&state, pack_id.as_deref(), dry_run)?;
+    crate::image_handler::levels_lut(brightness, contrast, gamma)?;
+    let (base_path, folder) = pack_relative_for(&state, pack_id.as_deref(), &folder_path)?;
+    let max_depth = if recursive.unwrap_or(false) { usize::MAX } else { 1 };
+    let history = *state.history_settings.lock();
+
+    tokio::task::spawn_blocking(move || {
+        let mut files: Vec<String> = walkdir::WalkDir::new(base_path.join(&folder))
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let rel = e.path().strip_prefix(&base_path).ok()?;
+                if crate::zip_handler::is_export_excluded(rel) {
+                    return None;
+                }
+                let ext = rel.extension()?.to_string_lossy().to_lowercase();
+                (ext != "gif" && constants::is_supported_image_ext(&ext))
+                    .then(|| rel.to_string_lossy().replace('\\', "/"))
+            })
+            .collect();
+        files.sort();
+        if dry_run {
+            return LevelsBatchResult { dry_run, adjusted: files, errors: HashMap::new() };
+        }
+
+        let mut adjusted = Vec::new();
+        let mut errors = HashMap::new();
+        for file in files {
+            let result = crate::history_manager::snapshot_image(&base_path, &file, history).and_then(|_| {
+                crate::image_handler::adjust_levels(&base_path.join(&file), brightness, contrast, gamma)
+            });
+            match result {
+                Ok(()) => {
+                    crate::history_manager::notify_file_changed(&base_path.join(&file));
+                    adjusted.push(file);
+                }
+                Err(e) => {
+                    errors.insert(file, e);
+                }
+            }
+        }
+
+        LevelsBatchResult { dry_run, adjusted, errors }
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))
+}
+
+/// 缩放后的图片尺寸
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResizedImage {
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 批量缩放结果，非图片文件列在 `skipped` 中，失败的文件单独列出；
+/// 预览时 `resized` 为将要缩放的文件及其目标尺寸
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResizeBatchResult {
+    pub dry_run: bool,
+    pub resized: Vec<ResizedImage>,
+    pub skipped: Vec<String>,
+    pub errors: HashMap<String, String>,
+}
+
+/// 并行批量缩放图片（GIF 除外），写入前保存历史记录，默认只预览
+#[tauri::command]
+pub async fn batch_resize_images(
+    paths: Vec<String>,
+    spec: crate::image_handler::ResizeSpec,
+    dry_run: Option<bool>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ResizeBatchResult, String> {
+    let dry_run = resolve_dry_run(&state, pack_id.as_deref(), dry_run)?;
+    spec.mode.validate()?;
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+    let mut errors = HashMap::new();
+    for path in paths {
+        let relative = match pack_relative_for(&state, pack_id.as_deref(), &path) {
+            Ok((_, relative)) => relative.to_string_lossy().replace('\\', "/"),
+            Err(e) => {
+                errors.insert(path, e);
+                continue;
+            }
+        };
+        let is_image = Path::new(&relative)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| ext != "gif" && constants::is_supported_image_ext(&ext));
+        if is_image && base_path.join(&relative).is_file() {
+            files.push(relative);
+        } else {
+            skipped.push(relative);
+        }
+    }
+
+    let operation = if dry_run {
+        None
+    } else {
+        // 动画贴图的 .mcmeta 可能随之更新
+        let mut touched: Vec<String> = files.clone();
+        touched.extend(
+            files
+                .iter()
+                .map(|file| format!("{}.mcmeta", file))
+                .filter(|mcmeta| base_path.join(mcmeta).is_file()),
+        );
+        let touched: Vec<&str> = touched.iter().map(String::as_str).collect();
+        begin_operation(
+            &state,
+            pack_id.as_deref(),
+            format!("Resize {} images", files.len()),
+            &touched,
+        )
+        .await
+    };
+    let history = *state.history_settings.lock();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let results: Vec<_> = files
+            .par_iter()
+            .map(|file| {
+                let path = base_path.join(file);
+                let result = if dry_run {
+                    crate::image_handler::resize_texture(&path, &spec, true)
+                } else {
+                    crate::history_manager::snapshot_image(&base_path, file, history)
+                        .and_then(|_| crate::image_handler::resize_texture(&path, &spec, false))
+                };
+                (file.clone(), result)
+            })
+            .collect();
+
+        let mut resized = Vec::new();
+        for (path, result) in results {
+            match result {
+                Ok((width, height)) => resized.push(ResizedImage { path, width, height }),
+                Err(e) => {
+                    errors.insert(path, e);
+                }
+            }
+        }
+
+        ResizeBatchResult { dry_run, resized, skipped, errors }
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?;
+    commit_operation(operation).await;
+
+    Ok(result)
+}
+
+/// 将材质包中的图片转换为 PNG、JPEG 或 WebP 并保存到 `dst_path`
+#[tauri::command]
+pub async fn convert_image(
+    src_path: String,
+    dst_path: String,
+    format: crate::image_handler::ConvertFormat,
+    quality: Option<u8>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::image_handler::ConvertResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let src = resolve_pack_path_for(&state, pack_id.as_deref(), &src_path)?;
+    let dst = resolve_pack_path_for(&state, pack_id.as_deref(), &dst_path)?;
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Convert image {}", src_path),
+        &[dst_path.as_str()],
+    )
+    .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        crate::image_handler::convert_image(&src, &dst, format, quality)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+
+    Ok(result)
+}
+
+/// 生成图片的灰度模板并保存到 `dst_path`，默认按亮度去色且不拉伸灰度
+#[tauri::command]
+pub async fn desaturate_image(
+    src_path: String,
+    dst_path: String,
+    method: Option<crate::image_handler::DesaturateMethod>,
+    normalize: Option<bool>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ImageInfo, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let src = resolve_pack_path_for(&state, pack_id.as_deref(), &src_path)?;
+    let dst = resolve_pack_path_for(&state, pack_id.as_deref(), &dst_path)?;
+    let method = method.unwrap_or_default();
+    let normalize = normalize.unwrap_or(false);
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Desaturate image {}", src_path),
+        &[dst_path.as_str()],
+    )
+    .await;
+
+    let info = tokio::task::spawn_blocking(move || {
+        crate::image_handler::desaturate_image(&src, &dst, method, normalize)?;
+        get_image_info(&dst)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+
+    Ok(info)
+}
+
+/// 用颜色给贴图染色并保存到 `dst_path`，返回输出路径
+///
+/// `strength` 默认为 1；`mask` 为 true 时只染色非透明的灰色像素
+#[tauri::command]
+pub async fn tint_image(
+    src_path: String,
+    dst_path: String,
+    hex_color: String,
+    strength: Option<f32>,
+    mask: Option<bool>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let src = resolve_pack_path_for(&state, pack_id.as_deref(), &src_path)?;
+    let dst = resolve_pack_path_for(&state, pack_id.as_deref(), &dst_path)?;
+    let strength = strength.unwrap_or(1.0);
+    let mask = mask.unwrap_or(false);
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Tint image {}", src_path),
+        &[dst_path.as_str()],
+    )
+    .await;
+
+    tokio::task::spawn_blocking(move || {
+        crate::image_handler::tint_image(&src, &dst, &hex_color, strength, mask)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+
+    Ok(dst_path)
+}
+
+/// 将 PNG 保存为调色板 PNG，颜色超过 `max_colors` 时需 `quantize` 为 true 才会量化
+#[tauri::command]
+pub async fn save_image_indexed(
+    image_path: String,
+    max_colors: usize,
+    quantize: Option<bool>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::indexed_png::IndexedSaveResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let (base_path, relative) = pack_relative_for(&state, pack_id.as_deref(), &image_path)?;
+    let quantize = quantize.unwrap_or(false);
+    let history = *state.history_settings.lock();
+
+    tokio::task::spawn_blocking(move || {
+        let full_path = base_path.join(&relative);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        crate::history_manager::snapshot_image(&base_path, &relative, history)?;
+        let result = crate::indexed_png::save_image_indexed(&full_path, max_colors, quantize)?;
+        crate::history_manager::notify_file_changed(&full_path);
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 检查字体定义对所需字符的覆盖情况，`preset` 可为 ascii、latin1 或 lang（语言文件中用到的字符）
+#[tauri::command]
+pub async fn font_coverage(
+    font_json: String,
+    required_chars: Option<String>,
+    preset: Option<String>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::font::FontCoverage, String> {
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    let font_path = resolve_pack_path_for(&state, pack_id.as_deref(), &font_json)?;
+
+    tokio::task::spawn_blocking(move || {
+        crate::font::font_coverage(
+            &base_path,
+            &font_path,
+            required_chars.as_deref(),
+            preset.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 读取字体定义（assets/<命名空间>/font/<名称>.json）
+#[tauri::command]
+pub async fn get_font_definition(
+    font_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::font::FontDef, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &font_path)?;
+    crate::font::get_font_definition(&full_path)
+}
+
+/// 校验并保存字体定义，返回材质包中找不到的贴图、字体等警告
+#[tauri::command]
+pub async fn update_font_definition(
+    font_path: String,
+    definition: crate::font::FontDef,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::font::FontDefResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let (base_path, relative) = pack_relative_for(&state, pack_id.as_deref(), &font_path)?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Edit font {}", font_path),
+        &[font_path.as_str()],
+    )
+    .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        crate::font::update_font_definition(&base_path, &relative, &definition)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+
+    Ok(result)
+}
+
+/// 按字形表生成 bitmap 字体提供器，`chars` 从 `start_codepoint` 开始依次填入，返回 JSON 供前端写入字体文件
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn generate_bitmap_font(
+    texture_path: String,
+    columns: u32,
+    rows: u32,
+    start_codepoint: u32,
+    ascent: i32,
+    height: Option<i32>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::font::FontProviderEntry, String> {
+    let (base_path, relative) = pack_relative_for(&state, pack_id.as_deref(), &texture_path)?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+
+    tokio::task::spawn_blocking(move || {
+        crate::font::generate_bitmap_font(
+            &base_path,
+            &relative,
+            columns,
+            rows,
+            start_codepoint,
+            ascent,
+            height,
+        )
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 导出材质包，`compression` 默认为 default，返回生成的 zip 大小
+///
+/// `preflight` 默认为 true，导出前运行 `validate_pack`，存在 error 级别的问题时拒绝导出；
+/// 只产生 warning 的引用分析不在导出时运行
+#[tauri::command]
+pub async fn export_pack(
+    output_path: String,
+    compression: Option<crate::zip_handler::CompressionChoice>,
+    preflight: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let output = PathBuf::from(output_path);
+    let compression = compression.unwrap_or_default();
+    let preflight = preflight.unwrap_or(true);
+    // 只读材质包不写入导出时间
+    let writable = ensure_path_writable(&state, &path).is_ok();
+
+    tokio::task::spawn_blocking(move || {
+        if preflight {
+            let no_progress = |_: &str, _: usize, _: usize| {};
+            let cancel = tokio_util::sync::CancellationToken::new();
+            let errors: Vec<_> = crate::analysis::validate_pack(&path, &cancel, &no_progress)?
+                .into_iter()
+                .filter(|issue| issue.is_error())
+                .collect();
+            if !errors.is_empty() {
+                let shown: Vec<String> = errors
+                    .iter()
+                    .take(constants::EXPORT_PREFLIGHT_ERRORS_SHOWN)
+                    .map(|issue| format!("{}: {}", issue.file, issue.message))
+                    .collect();
+                return Err(format!(
+                    "Pack has {} problems, fix them before exporting:\n{}",
+                    errors.len(),
+                    shown.join("\n")
+                ));
+            }
+        }
+        let size = create_zip(&path, &output, compression)?;
+        if writable {
+            let _ = crate::zip_handler::record_export_time(&path);
+        }
+        Ok::<_, String>(size)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 只导出选中的文件或文件夹（始终包含 pack.mcmeta 和 pack.png），用于发布增量更新
+#[tauri::command]
+pub async fn export_selected_files(
+    output_path: String,
+    paths: Vec<String>,
+    compression: Option<crate::zip_handler::CompressionChoice>,
+    state: State<'_, AppState>,
+) -> Result<crate::zip_handler::SelectedExportResult, String> {
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let output = PathBuf::from(output_path);
+    let compression = compression.unwrap_or_default();
+    let writable = ensure_path_writable(&state, &path).is_ok();
+
+    tokio::task::spawn_blocking(move || {
+        let result = crate::zip_handler::export_selected(&path, &output, &paths, compression)?;
+        if writable {
+            let _ = crate::zip_handler::record_export_time(&path);
+        }
+        Ok::<_, String>(result)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 列出自 `since`（RFC 3339）以来修改过的文件，省略时从上次导出算起
+#[tauri::command]
+pub async fn get_modified_files_since(
+    since: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::zip_handler::ModifiedFiles, String> {
+    let path = get_pack_base_path(&state.current_pack_path)?;
+
+    tokio::task::spawn_blocking(move || {
+        crate::zip_handler::modified_files_since(&path, since.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 按多个 pack_format 批量导出，通过 export-variants-progress 事件报告总进度
+#[tauri::command]
+pub async fn export_variants(
+    variants: Vec<crate::zip_handler::ExportVariant>,
+    output_dir: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::zip_handler::VariantExportResult>, String> {
+    use tauri::Emitter;
+
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let output_dir = PathBuf::from(output_dir);
+
+    tokio::task::spawn_blocking(move || {
+        let on_progress = |progress: &crate::zip_handler::VariantExportProgress| {
+            let _ = app_handle.emit("export-variants-progress", progress);
+        };
+        crate::zip_handler::export_variants(&path, &output_dir, &variants, Some(&on_progress))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 导出材质包到 `dest` 下与材质包同名的文件夹
+#[tauri::command]
+pub async fn export_to_folder(
+    dest: String,
+    overwrite: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::deploy::FolderExportResult, String> {
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let dest = PathBuf::from(dest);
+    let overwrite = overwrite.unwrap_or(false);
+
+    tokio::task::spawn_blocking(move || crate::deploy::export_to_folder(&path, &dest, overwrite))
+        .await
+        .map_err(|e| format!("Failed to export: {}", e))?
+}
+
+/// 获取默认的 resourcepacks 目录
+#[tauri::command]
+pub async fn get_default_resourcepacks_dir() -> Result<Option<String>, String> {
+    Ok(crate::deploy::default_resourcepacks_dir()
+        .map(|p| p.to_string_lossy().to_string()))
+}
+
+/// 开始实时部署：先完整导出，再监听变更增量同步
+#[tauri::command]
+pub async fn start_live_deploy(
+    dest_folder: String,
+    overwrite: Option<bool>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<crate::deploy::FolderExportResult, String> {
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let dest = PathBuf::from(dest_folder);
+
+    // 停止之前的部署
+    state.live_deploy.lock().take();
+
+    let export_path = path.clone();
+    let overwrite = overwrite.unwrap_or(false);
+    let result = tokio::task::spawn_blocking(move || {
+        crate::deploy::export_to_folder(&export_path, &dest, overwrite)
+    })
+    .await
+    .map_err(|e| format!("Failed to export: {}", e))??;
+
+    // 增量同步到实际导出的子文件夹
+    let live_dest = PathBuf::from(&result.output_path);
+    let live = crate::deploy::start_live_deploy(app_handle, path, live_dest)?;
+    *state.live_deploy.lock() = Some(live);
+
+    Ok(result)
+}
+
+/// 停止实时部署
+#[tauri::command]
+pub async fn stop_live_deploy(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.live_deploy.lock().take().is_some())
+}
+
+/// 查找本机的 resourcepacks 目录
+#[tauri::command]
+pub async fn find_minecraft_resourcepacks_dirs(
+) -> Result<Vec<crate::deploy::ResourcepacksDir>, String> {
+    tokio::task::spawn_blocking(crate::deploy::find_resourcepacks_dirs)
+        .await
+        .map_err(|e| format!("Failed to scan directories: {}", e))
+}
+
+/// 清理临时文件
+#[tauri::command]
+pub async fn cleanup_temp() -> Result<(), String> {
+    cleanup_temp_files()
+}
+
+/// 读取文件内容 
+#[tauri::command]
+pub async fn read_file_content(
+    file_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &file_path)?;
+    tokio::fs::read_to_string(&full_path)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to read file"))
+}
+
+/// 读取文件内容
+#[tauri::command]
+pub async fn read_file_binary(
+    file_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<u8>> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &file_path)?;
+    tokio::fs::read(&full_path)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to read file"))
+}
+
+#[tauri::command]
+pub async fn write_file_content(
+    file_path: String,
+    content: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &file_path)?;
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Write {}", file_path),
+        &[file_path.as_str()],
+    )
+    .await;
+
+    // 创建父目录
+    if let Some(parent) = full_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::from(e).context("Failed to create directory"))?;
+    }
+
+    tokio::fs::write(&full_path, content)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to write file"))?;
+    commit_operation(operation).await;
+
+    // 正常保存后草稿不再需要
+    if let Ok((base, relative)) = pack_relative_for(&state, pack_id.as_deref(), &file_path) {
+        let _ = crate::drafts::discard_draft(&base, &relative);
+    }
+
+    Ok(())
+}
+
+/// 保存编辑器中未保存内容的草稿，用于崩溃后恢复；只读材质包不保存草稿
+#[tauri::command]
+pub async fn autosave_draft(
+    file_path: String,
+    content: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let (base, relative) = pack_relative_for(&state, pack_id.as_deref(), &file_path)?;
+    if ensure_path_writable(&state, &base).is_err() {
+        return Ok(());
+    }
+    crate::drafts::save_draft(&base, &relative, &content)
+}
+
+/// 读取文件的草稿，没有草稿时返回 None
+#[tauri::command]
+pub async fn load_draft(
+    file_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let (base, relative) = pack_relative_for(&state, pack_id.as_deref(), &file_path)?;
+    crate::drafts::load_draft(&base, &relative)
+}
+
+/// 丢弃文件的草稿
+#[tauri::command]
+pub async fn discard_draft(
+    file_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let (base, relative) = pack_relative_for(&state, pack_id.as_deref(), &file_path)?;
+    crate::drafts::discard_draft(&base, &relative)
+}
+
+/// 创建新文件
+#[tauri::command]
+pub async fn create_new_file(
+    file_path: String,
+    content: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &file_path)?;
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Create {}", file_path),
+        &[file_path.as_str()],
+    )
+    .await;
+
+    // 创建父目录
+    if let Some(parent) = full_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::from(e).context("Failed to create directory"))?;
+    }
+
+    // 写入文件
+    tokio::fs::write(&full_path, content)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to create file"))?;
+    commit_operation(operation).await;
+
+    Ok(())
+}
+
+/// 创建新文件夹
+#[tauri::command]
+pub async fn create_new_folder(
+    folder_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &folder_path)?;
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Create folder {}", folder_path),
+        &[folder_path.as_str()],
+    )
+    .await;
+    tokio::fs::create_dir_all(&full_path)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to create folder"))?;
+    commit_operation(operation).await;
+    Ok(())
+}
+
+/// 删除文件
+#[tauri::command]
+pub async fn delete_file(
+    file_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &file_path)?;
+
+    let metadata = tokio::fs::metadata(&full_path)
+        .await
+        .map_err(|e| AppError::from(e).context("Failed to get file metadata"))?;
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Delete {}", file_path),
+        &[file_path.as_str()],
+    )
+    .await;
+
+    if metadata.is_dir() {
         tokio::fs::remove_dir_all(&full_path)
             .await
-            .map_err(|e| format!("Failed to delete folder: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to delete folder"))?;
     } else {
         tokio::fs::remove_file(&full_path)
             .await
-            .map_err(|e| format!("Failed to delete file: {}", e))?;
+            .map_err(|e| AppError::from(e).context("Failed to delete file"))?;
     }
+    commit_operation(operation).await;
 
     Ok(())
 }
@@ -306,18 +1594,59 @@ pub async fn delete_file(file_path: String, state: State<'_, AppState>) -> Resul
 pub async fn rename_file(
     old_path: String,
     new_path: String,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let full_old_path = resolve_pack_path(&old_path, &state.current_pack_path)?;
-    let full_new_path = resolve_pack_path(&new_path, &state.current_pack_path)?;
+) -> AppResult<()> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let full_old_path = resolve_pack_path_for(&state, pack_id.as_deref(), &old_path)?;
+    let full_new_path = resolve_pack_path_for(&state, pack_id.as_deref(), &new_path)?;
 
     tokio::fs::rename(&full_old_path, &full_new_path)
         .await
-        .map_err(|e| format!("Failed to rename file: {}", e))?;
+        .map_err(|e| AppError::from(e).context("Failed to rename file"))?;
+
+    let (base, old_relative) = pack_relative_for(&state, pack_id.as_deref(), &old_path)?;
+    let (_, new_relative) = pack_relative_for(&state, pack_id.as_deref(), &new_path)?;
+    if let Err(e) = crate::history_manager::record_rename(
+        &base,
+        format!("Rename {} to {}", old_path, new_path),
+        &old_relative.to_string_lossy().replace('\\', "/"),
+        &new_relative.to_string_lossy().replace('\\', "/"),
+    ) {
+        log::warn!("Failed to record operation: {}", e);
+    }
 
     Ok(())
 }
 
+/// 撤销整个材质包最近一次修改操作，返回该操作的描述
+#[tauri::command]
+pub async fn undo_last_operation(
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+
+    tokio::task::spawn_blocking(move || crate::history_manager::undo_last_operation(&base_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 重做最近一次撤销的操作，返回该操作的描述
+#[tauri::command]
+pub async fn redo_last_operation(
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+
+    tokio::task::spawn_blocking(move || crate::history_manager::redo_last_operation(&base_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
 /// 获取pack.mcmeta内容
 #[tauri::command]
 pub async fn get_pack_mcmeta(state: State<'_, AppState>) -> Result<String, String> {
@@ -328,14 +1657,126 @@ pub async fn get_pack_mcmeta(state: State<'_, AppState>) -> Result<String, Strin
         .map_err(|e| format!("Failed to read pack.mcmeta: {}", e))
 }
 
+/// pack_format 校验结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackFormatCheck {
+    pub expected_version: String,
+    /// 目标版本应使用的 pack_format
+    pub expected_format: i32,
+    /// pack.mcmeta 中的 pack_format，缺失时为 None
+    pub actual_format: Option<i32>,
+    pub matches: bool,
+    pub warning: Option<String>,
+    /// 目标版本使用的语言文件格式：json 或 lang（1.13 之前）
+    pub lang_format: String,
+    /// 需要转换为目标格式的语言文件
+    pub lang_files_to_convert: Vec<String>,
+}
+
+/// 导出前的综合检查，`target_version` 省略时不检查 pack_format 与目标版本是否一致
+#[tauri::command]
+pub async fn preflight_pack(
+    target_version: Option<String>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::analysis::PackIssue>, String> {
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    let target_format = match target_version {
+        Some(version) => Some(
+            crate::pack_parser::pack_format_for_version(&version)
+                .ok_or_else(|| format!("Unknown Minecraft version: {}", version))?,
+        ),
+        None => None,
+    };
+
+    tokio::task::spawn_blocking(move || crate::analysis::preflight_pack(&base_path, target_format))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 检查单个 JSON 文件的语法，正确时返回 None，否则返回错误的行列号和说明
+#[tauri::command]
+pub async fn validate_json(
+    path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::analysis::JsonSyntaxError>, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &path)?;
+    crate::analysis::validate_json_file(&full_path, &path)
+}
+
+/// 检查材质包内所有 JSON 和 .mcmeta 文件，返回每个格式错误的文件（跳过 .history 等编辑器目录）
+#[tauri::command]
+pub async fn validate_all_json(
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::analysis::JsonSyntaxError>, String> {
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+
+    tokio::task::spawn_blocking(move || crate::analysis::validate_all_json(&base_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))
+}
+
+/// 检查当前材质包的 pack_format 是否与目标版本一致
+#[tauri::command]
+pub async fn validate_pack_format(
+    expected_version: String,
+    state: State<'_, AppState>,
+) -> Result<PackFormatCheck, String> {
+    let expected_format = crate::pack_parser::pack_format_for_version(&expected_version)
+        .ok_or_else(|| format!("Unknown Minecraft version: {}", expected_version))?;
+
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let content = tokio::fs::read_to_string(path.join("pack.mcmeta"))
+        .await
+        .map_err(|e| format!("Failed to read pack.mcmeta: {}", e))?;
+    let mcmeta: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse pack.mcmeta: {}", e))?;
+    let formats = crate::pack_parser::parse_pack_formats(&mcmeta);
+    let actual_format = formats.pack_format;
+
+    let matches = formats.supports(expected_format);
+    let warning = match actual_format {
+        _ if matches => None,
+        Some(actual) => Some(format!(
+            "pack_format {} does not match Minecraft {}, use {}",
+            actual, expected_version, expected_format
+        )),
+        None => Some(format!(
+            "pack.mcmeta has no pack_format, Minecraft {} uses {}",
+            expected_version, expected_format
+        )),
+    };
+
+    let (lang_format, other_format) = if crate::lang::uses_legacy_lang(expected_format) {
+        ("lang", "json")
+    } else {
+        ("json", "lang")
+    };
+    let lang_files_to_convert = crate::lang::list_lang_files(&path, other_format);
+
+    Ok(PackFormatCheck {
+        expected_version,
+        expected_format,
+        actual_format,
+        matches,
+        warning,
+        lang_format: lang_format.to_string(),
+        lang_files_to_convert,
+    })
+}
+
 /// 更新pack.mcmeta
 #[tauri::command]
 pub async fn update_pack_mcmeta(content: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_pack_writable(&state, None)?;
     let path = get_pack_base_path(&state.current_pack_path)?;
     let mcmeta_path = path.join("pack.mcmeta");
     tokio::fs::write(&mcmeta_path, &content)
         .await
         .map_err(|e| format!("Failed to write pack.mcmeta: {}", e))?;
+    crate::history_manager::notify_file_changed(&mcmeta_path);
 
     // 重新扫描材质包
     let pack_info = scan_pack_directory(&path)?;
@@ -345,6 +1786,148 @@ pub async fn update_pack_mcmeta(content: String, state: State<'_, AppState>) ->
     Ok(())
 }
 
+/// 读取 pack.mcmeta 中的 supported_formats 和 overlays
+#[tauri::command]
+pub async fn get_pack_formats(
+    state: State<'_, AppState>,
+) -> Result<crate::pack_parser::PackFormats, String> {
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    crate::pack_parser::read_pack_formats(&path)
+}
+
+/// 更新 supported_formats 和 overlays，保留 pack.mcmeta 中的其他字段
+#[tauri::command]
+pub async fn update_pack_formats(
+    supported_formats: Option<crate::pack_parser::FormatRange>,
+    overlays: Vec<crate::pack_parser::OverlayEntry>,
+    state: State<'_, AppState>,
+) -> Result<crate::pack_parser::PackFormats, String> {
+    ensure_pack_writable(&state, None)?;
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let formats =
+        crate::pack_parser::write_pack_formats(&path, supported_formats.as_ref(), &overlays)?;
+    crate::history_manager::notify_file_changed(&path.join("pack.mcmeta"));
+
+    let pack_info = scan_pack_directory(&path)?;
+    *state.current_pack_info.lock() = Some(pack_info);
+
+    Ok(formats)
+}
+
+/// set_pack_icon 的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackIconResult {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 用图片生成 pack.png：居中裁剪为正方形后缩放为 128x128
+///
+/// src_image_path 为相对路径时表示材质包内的贴图，否则为外部图片
+#[tauri::command]
+pub async fn set_pack_icon(
+    src_image_path: String,
+    state: State<'_, AppState>,
+) -> Result<PackIconResult, String> {
+    ensure_pack_writable(&state, None)?;
+    let base = get_pack_base_path(&state.current_pack_path)?;
+    let src = if Path::new(&src_image_path).is_absolute() {
+        PathBuf::from(&src_image_path)
+    } else {
+        resolve_pack_path_for(&state, None, &src_image_path)?
+    };
+    if !src.is_file() {
+        return Err(format!("Image not found: {}", src_image_path));
+    }
+    let operation = begin_operation(&state, None, "Set pack icon".to_string(), &["pack.png"]).await;
+
+    let (width, height) = tokio::task::spawn_blocking(move || {
+        crate::image_handler::create_pack_icon(&src, &base.join("pack.png"))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+
+    Ok(PackIconResult { width, height })
+}
+
+/// 读取 pack.png 并返回 base64，没有图标时返回 None
+#[tauri::command]
+pub async fn get_pack_icon(state: State<'_, AppState>) -> Result<Option<String>, String> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let icon_path = get_pack_base_path(&state.current_pack_path)?.join("pack.png");
+    if !icon_path.is_file() {
+        return Ok(None);
+    }
+    let bytes = tokio::fs::read(&icon_path)
+        .await
+        .map_err(|e| format!("Failed to read pack.png: {}", e))?;
+    Ok(Some(general_purpose::STANDARD.encode(&bytes)))
+}
+
+/// 删除 pack.png，返回是否删除了文件
+#[tauri::command]
+pub async fn clear_pack_icon(state: State<'_, AppState>) -> Result<bool, String> {
+    ensure_pack_writable(&state, None)?;
+    let icon_path = get_pack_base_path(&state.current_pack_path)?.join("pack.png");
+    if !icon_path.is_file() {
+        return Ok(false);
+    }
+    let operation = begin_operation(&state, None, "Clear pack icon".to_string(), &["pack.png"]).await;
+    tokio::fs::remove_file(&icon_path)
+        .await
+        .map_err(|e| format!("Failed to delete pack.png: {}", e))?;
+    crate::image_handler::invalidate_image_caches(&icon_path);
+    commit_operation(operation).await;
+    Ok(true)
+}
+
+/// 注册新的 overlay 目录（同时创建该目录）
+#[tauri::command]
+pub async fn add_pack_overlay(
+    directory: String,
+    formats: crate::pack_parser::FormatRange,
+    state: State<'_, AppState>,
+) -> Result<crate::pack_parser::PackFormats, String> {
+    ensure_pack_writable(&state, None)?;
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let result = crate::pack_parser::add_pack_overlay(&path, &directory, formats)?;
+    crate::history_manager::notify_file_changed(&path.join("pack.mcmeta"));
+
+    let pack_info = scan_pack_directory(&path)?;
+    *state.current_pack_info.lock() = Some(pack_info);
+
+    Ok(result)
+}
+
+/// 为没有 pack.mcmeta 的模组资源生成 pack.mcmeta，使其成为材质包
+#[tauri::command]
+pub async fn wrap_mod_assets_as_pack(
+    pack_format: i32,
+    description: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<PackInfo, String> {
+    ensure_pack_writable(&state, None)?;
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    if path.join("pack.mcmeta").exists() {
+        return Err("pack.mcmeta already exists".to_string());
+    }
+
+    let description = description.unwrap_or_else(|| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
+    crate::pack_creator::write_pack_mcmeta(&path, pack_format, &description)?;
+
+    let mut pack_info = scan_pack_directory(&path)?;
+    pack_info.pack_path = Some(path.to_string_lossy().to_string());
+    *state.current_pack_info.lock() = Some(pack_info.clone());
+
+    Ok(pack_info)
+}
+
 /// 创建新材质包
 #[tauri::command]
 pub async fn create_new_pack(
@@ -359,15 +1942,54 @@ pub async fn create_new_pack(
 
     // 自动加载新创建的材质包
     let pack_info = crate::pack_parser::scan_pack_directory(path)?;
-    *state.current_pack_path.lock() = Some(path.to_path_buf());
-    *state.current_pack_info.lock() = Some(pack_info);
+    set_active_pack(&state, path, pack_info);
 
     Ok(())
 }
 
+/// 列出内置的材质包模板
+#[tauri::command]
+pub async fn list_pack_templates() -> Result<Vec<crate::pack_creator::PackTemplateInfo>, String> {
+    Ok(crate::pack_creator::list_pack_templates())
+}
+
+/// 根据模板创建新材质包，返回创建的路径
+///
+/// 指定 target_version 时按游戏版本决定 pack_format，否则使用 pack_format
+#[tauri::command]
+pub async fn create_pack_from_template(
+    template: String,
+    output_path: String,
+    pack_name: String,
+    pack_format: Option<i32>,
+    target_version: Option<String>,
+    description: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let template = crate::pack_creator::PackTemplate::from_id(&template)
+        .ok_or_else(|| format!("Unknown pack template: {}", template))?;
+    let pack_format = match target_version {
+        Some(version) => crate::pack_parser::pack_format_for_version(&version)
+            .ok_or_else(|| format!("Unknown Minecraft version: {}", version))?,
+        None => pack_format.ok_or("Either pack_format or target_version is required")?,
+    };
+    let description = description.unwrap_or(pack_name);
+
+    let path = std::path::Path::new(&output_path);
+    let created =
+        crate::pack_creator::create_pack_from_template(path, template, pack_format, &description)?;
+
+    // 自动加载新创建的材质包
+    let pack_info = crate::pack_parser::scan_pack_directory(path)?;
+    set_active_pack(&state, path, pack_info);
+
+    Ok(created)
+}
+
 /// 为物品创建模型
 #[tauri::command]
 pub async fn create_item_model(item_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    ensure_pack_writable(&state, None)?;
     let pack_path_guard = state.current_pack_path.lock();
     let pack_info_guard = state.current_pack_info.lock();
 
@@ -379,7 +2001,11 @@ pub async fn create_item_model(item_id: String, state: State<'_, AppState>) -> R
     drop(pack_path_guard);
     drop(pack_info_guard);
 
+    let files = crate::pack_creator::item_model_files(&item_id, pack_format);
+    let paths: Vec<&str> = files.iter().map(String::as_str).collect();
+    let operation = begin_operation(&state, None, format!("Create item model {}", item_id), &paths).await;
     crate::pack_creator::create_item_model(&path_clone, &item_id, pack_format)?;
+    commit_operation(operation).await;
 
     // 重新扫描材质包
     let new_pack_info = crate::pack_parser::scan_pack_directory(&path_clone)?;
@@ -394,11 +2020,16 @@ pub async fn create_block_model(
     block_id: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_pack_writable(&state, None)?;
     let pack_path_guard = state.current_pack_path.lock();
     let path = pack_path_guard.as_ref().ok_or("No pack loaded")?.clone();
     drop(pack_path_guard);
 
+    let files = crate::pack_creator::block_model_files(&block_id);
+    let paths: Vec<&str> = files.iter().map(String::as_str).collect();
+    let operation = begin_operation(&state, None, format!("Create block model {}", block_id), &paths).await;
     crate::pack_creator::create_block_model(&path, &block_id)?;
+    commit_operation(operation).await;
 
     // 重新扫描材质包
     let pack_info = crate::pack_parser::scan_pack_directory(&path)?;
@@ -413,6 +2044,7 @@ pub async fn create_multiple_item_models(
     item_ids: Vec<String>,
     state: State<'_, AppState>,
 ) -> Result<Vec<String>, String> {
+    ensure_pack_writable(&state, None)?;
     let pack_path_guard = state.current_pack_path.lock();
     let pack_info_guard = state.current_pack_info.lock();
 
@@ -439,6 +2071,7 @@ pub async fn create_multiple_block_models(
     block_ids: Vec<String>,
     state: State<'_, AppState>,
 ) -> Result<Vec<String>, String> {
+    ensure_pack_writable(&state, None)?;
     let pack_path_guard = state.current_pack_path.lock();
     let path = pack_path_guard.as_ref().ok_or("No pack loaded")?.clone();
     drop(pack_path_guard);
@@ -452,6 +2085,325 @@ pub async fn create_multiple_block_models(
     Ok(created)
 }
 
+/// 创建方块状态文件（assets/<命名空间>/blockstates/<名称>.json）
+#[tauri::command]
+pub async fn create_blockstate(
+    namespace: String,
+    name: String,
+    definition: crate::blockstates::BlockstateDef,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::blockstates::BlockstateResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+
+    tokio::task::spawn_blocking(move || {
+        let result = crate::blockstates::create_blockstate(&base_path, &namespace, &name, &definition)?;
+        crate::history_manager::notify_file_changed(&base_path.join(&result.path));
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 读取方块状态文件
+#[tauri::command]
+pub async fn get_blockstate(
+    namespace: String,
+    name: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::blockstates::BlockstateDef, String> {
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    crate::blockstates::get_blockstate(&base_path, &namespace, &name)
+}
+
+/// 覆盖已有的方块状态文件，返回材质包中找不到的模型引用
+#[tauri::command]
+pub async fn update_blockstate(
+    namespace: String,
+    name: String,
+    definition: crate::blockstates::BlockstateDef,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::blockstates::BlockstateResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+
+    tokio::task::spawn_blocking(move || {
+        let result = crate::blockstates::update_blockstate(&base_path, &namespace, &name, &definition)?;
+        crate::history_manager::notify_file_changed(&base_path.join(&result.path));
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 按文件顺序读取语言文件的条目
+#[tauri::command]
+pub async fn get_lang_entries(
+    path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<(String, String)>, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &path)?;
+    crate::lang::read_lang_entries(&full_path)
+}
+
+/// 修改语言文件并记录到操作历史
+async fn edit_lang_file<F>(
+    state: &AppState,
+    pack_id: Option<&str>,
+    path: &str,
+    description: String,
+    edit: F,
+) -> Result<(), String>
+where
+    F: FnOnce(&Path) -> Result<(), String> + Send + 'static,
+{
+    ensure_pack_writable(state, pack_id)?;
+    let full_path = resolve_pack_path_for(state, pack_id, path)?;
+    let operation = begin_operation(state, pack_id, description, &[path]).await;
+
+    tokio::task::spawn_blocking(move || edit(&full_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+    Ok(())
+}
+
+/// 修改语言条目的文本
+#[tauri::command]
+pub async fn update_lang_entry(
+    path: String,
+    key: String,
+    value: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let description = format!("Update {} in {}", key, path);
+    edit_lang_file(&state, pack_id.as_deref(), &path, description, move |file| {
+        crate::lang::update_lang_entry(file, &key, &value)
+    })
+    .await
+}
+
+/// 在语言文件末尾添加条目
+#[tauri::command]
+pub async fn add_lang_entry(
+    path: String,
+    key: String,
+    value: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let description = format!("Add {} to {}", key, path);
+    edit_lang_file(&state, pack_id.as_deref(), &path, description, move |file| {
+        crate::lang::add_lang_entry(file, &key, &value)
+    })
+    .await
+}
+
+/// 删除语言条目
+#[tauri::command]
+pub async fn delete_lang_entry(
+    path: String,
+    key: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let description = format!("Delete {} from {}", key, path);
+    edit_lang_file(&state, pack_id.as_deref(), &path, description, move |file| {
+        crate::lang::delete_lang_entry(file, &key)
+    })
+    .await
+}
+
+/// 对比参照语言（如 en_us.json），列出未翻译的键
+#[tauri::command]
+pub async fn find_missing_keys(
+    lang_path: String,
+    reference_lang_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::lang::MissingKeysReport, String> {
+    let lang = resolve_pack_path_for(&state, pack_id.as_deref(), &lang_path)?;
+    let reference = resolve_pack_path_for(&state, pack_id.as_deref(), &reference_lang_path)?;
+    crate::lang::find_missing_keys(&lang, &reference)
+}
+
+/// 转换语言文件格式并记录到操作历史
+async fn convert_lang_file<F>(
+    state: &AppState,
+    pack_id: Option<&str>,
+    path: &str,
+    extension: &str,
+    convert: F,
+) -> Result<crate::lang::LangConversion, String>
+where
+    F: FnOnce(&Path) -> Result<crate::lang::LangConversion, String> + Send + 'static,
+{
+    ensure_pack_writable(state, pack_id)?;
+    let full_path = resolve_pack_path_for(state, pack_id, path)?;
+    let output = Path::new(path).with_extension(extension);
+    let output = output.to_string_lossy().replace('\\', "/");
+    let operation = begin_operation(
+        state,
+        pack_id,
+        format!("Convert {} to {}", path, output),
+        &[output.as_str()],
+    )
+    .await;
+
+    let mut result = tokio::task::spawn_blocking(move || convert(&full_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+    result.path = PathBuf::from(output);
+    Ok(result)
+}
+
+/// 将 JSON 语言文件转换为 1.13 之前的 .lang 格式
+#[tauri::command]
+pub async fn convert_lang_json_to_properties(
+    path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::lang::LangConversion, String> {
+    convert_lang_file(&state, pack_id.as_deref(), &path, "lang", |file| {
+        crate::lang::convert_lang_json_to_properties(file)
+    })
+    .await
+}
+
+/// 将 .lang 语言文件转换为 JSON 格式
+#[tauri::command]
+pub async fn convert_lang_properties_to_json(
+    path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::lang::LangConversion, String> {
+    convert_lang_file(&state, pack_id.as_deref(), &path, "json", |file| {
+        crate::lang::convert_lang_properties_to_json(file)
+    })
+    .await
+}
+
+/// 读取模型的 elements（长方体、面和 UV）
+#[tauri::command]
+pub async fn get_model_elements(
+    model_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::ModelElement>, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &model_path)?;
+    crate::models::get_model_elements(&full_path)
+}
+
+/// 替换模型的 elements，返回无法解析的贴图变量等警告
+#[tauri::command]
+pub async fn update_model_elements(
+    model_path: String,
+    elements: Vec<crate::models::ModelElement>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::models::ModelUpdateResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let (base_path, relative) = pack_relative_for(&state, pack_id.as_deref(), &model_path)?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Edit model elements {}", model_path),
+        &[model_path.as_str()],
+    )
+    .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        crate::models::update_model_elements(&base_path, &relative, &elements)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+
+    Ok(result)
+}
+
+/// 读取物品模型的 overrides
+#[tauri::command]
+pub async fn list_item_overrides(
+    model_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::ItemOverride>, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &model_path)?;
+    crate::models::list_item_overrides(&full_path)
+}
+
+/// 向物品模型添加 override，返回更新后的列表和材质包中找不到的模型
+#[tauri::command]
+pub async fn add_item_override(
+    model_path: String,
+    predicate: std::collections::BTreeMap<String, serde_json::Number>,
+    model: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::models::ItemOverridesResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let (base_path, relative) = pack_relative_for(&state, pack_id.as_deref(), &model_path)?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Add override to {}", model_path),
+        &[model_path.as_str()],
+    )
+    .await;
+
+    let result = tokio::task::spawn_blocking(move || {
+        let item = crate::models::ItemOverride { predicate, model };
+        crate::models::add_item_override(&base_path, &relative, item)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+
+    Ok(result)
+}
+
+/// 将物品模型的 custom_model_data 重新编号为连续整数，默认只预览
+#[tauri::command]
+pub async fn compact_custom_model_data(
+    model_path: String,
+    dry_run: Option<bool>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::models::CustomModelDataChange>, String> {
+    let dry_run = resolve_dry_run(&state, pack_id.as_deref(), dry_run)?;
+    let (base_path, relative) = pack_relative_for(&state, pack_id.as_deref(), &model_path)?;
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let operation = if dry_run {
+        None
+    } else {
+        begin_operation(
+            &state,
+            pack_id.as_deref(),
+            format!("Renumber custom_model_data in {}", model_path),
+            &[model_path.as_str()],
+        )
+        .await
+    };
+
+    let changes = tokio::task::spawn_blocking(move || {
+        crate::models::compact_custom_model_data(&base_path, &relative, dry_run)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    commit_operation(operation).await;
+
+    Ok(changes)
+}
+
 /// 获取系统已安装的字体列表
 #[tauri::command]
 pub async fn get_system_fonts() -> Result<Vec<String>, String> {
@@ -481,8 +2433,19 @@ pub struct FileTreeNode {
     pub path: String,
     pub is_dir: bool,
     pub children: Option<Vec<FileTreeNode>>,
+    /// 已加载子节点时为子节点数量，未加载时为 None（用 count_children 查询）
     pub file_count: Option<usize>,
     pub loaded: bool,
+    /// 目录是否非空，用于在未加载时显示展开箭头
+    #[serde(default)]
+    pub has_children: bool,
+}
+
+/// 目录是否包含任何条目，只读取第一个条目
+fn dir_has_children(path: &Path) -> bool {
+    std::fs::read_dir(path)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false)
 }
 
 fn read_directory_tree_lazy(
@@ -494,17 +2457,12 @@ fn read_directory_tree_lazy(
     let entries =
         std::fs::read_dir(path).map_err(|e| format!("Failed to read directory: {}", e))?;
 
-    let mut entries: Vec<_> = entries
+    let entries: Vec<_> = entries
         .filter_map(|e| e.ok())
-        .collect();
-
-    entries.par_sort_unstable_by(|a, b| {
-        let name_a = a.file_name().to_string_lossy().to_string();
-        let name_b = b.file_name().to_string_lossy().to_string();
-        name_a.cmp(&name_b)
-    });
+        .collect();
 
-    let nodes: Vec<FileTreeNode> = entries
+    // 各条目（包括子目录的递归读取）并行处理，之后统一排序保证顺序稳定
+    let mut nodes: Vec<FileTreeNode> = entries
         .par_iter()
         .filter_map(|entry| {
             let entry_path = entry.path();
@@ -524,10 +2482,7 @@ fn read_directory_tree_lazy(
             }
 
             let node = if metadata.is_dir() {
-                let file_count = std::fs::read_dir(&entry_path)
-                    .map(|entries| entries.count())
-                    .unwrap_or(0);
-
+                // 超过深度的目录不读取内容，只检查是否为空
                 let children = if depth < max_depth {
                     read_directory_tree_lazy(
                         &entry_path,
@@ -538,14 +2493,19 @@ fn read_directory_tree_lazy(
                 } else {
                     None
                 };
+                let has_children = match &children {
+                    Some(children) => !children.is_empty(),
+                    None => dir_has_children(&entry_path),
+                };
 
                 FileTreeNode {
                     name,
                     path: relative_path,
                     is_dir: true,
+                    file_count: children.as_ref().map(|children| children.len()),
+                    loaded: children.is_some(),
                     children,
-                    file_count: Some(file_count),
-                    loaded: depth < max_depth,
+                    has_children,
                 }
             } else {
                 FileTreeNode {
@@ -555,6 +2515,7 @@ fn read_directory_tree_lazy(
                     children: None,
                     file_count: None,
                     loaded: true,
+                    has_children: false,
                 }
             };
 
@@ -562,13 +2523,46 @@ fn read_directory_tree_lazy(
         })
         .collect();
 
+    sort_tree_nodes(&mut nodes);
     Ok(nodes)
 }
 
+/// 文件夹在前，同类按名称排序
+fn sort_tree_nodes(nodes: &mut [FileTreeNode]) {
+    nodes.par_sort_unstable_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+}
+
+/// 统计目录中的条目数（不含 .little100），recursive 时把子目录分给多个线程递归统计文件数
+fn count_dir_entries(path: &Path, recursive: bool) -> Result<usize, String> {
+    let entries: Vec<_> = std::fs::read_dir(path)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name() != ".little100")
+        .collect();
+    if !recursive {
+        return Ok(entries.len());
+    }
+
+    Ok(entries
+        .par_iter()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => count_dir_entries(&entry.path(), true).unwrap_or(0),
+            _ => 1,
+        })
+        .sum())
+}
+
 /// 获取材质包的文件树结构
+///
+/// 默认只返回根目录的一层，depth 为额外展开的目录层数（最多 FILE_TREE_MAX_EAGER_DEPTH），
+/// 其余目录通过 load_folder_children 展开
 #[tauri::command]
-pub async fn get_file_tree(state: State<'_, AppState>) -> Result<FileTreeNode, String> {
+pub async fn get_file_tree(
+    depth: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<FileTreeNode, String> {
     let path = get_pack_base_path(&state.current_pack_path)?;
+    let depth = depth.unwrap_or(0).min(constants::FILE_TREE_MAX_EAGER_DEPTH);
     
     let pack_name = path
         .file_name()
@@ -577,23 +2571,20 @@ pub async fn get_file_tree(state: State<'_, AppState>) -> Result<FileTreeNode, S
         .to_string();
 
     // 锁已释放，安全执行目录遍历
-    let children = read_directory_tree_lazy(&path, &path, 0, 2)?;
-
-    let file_count = std::fs::read_dir(&path)
-        .map(|entries| entries.count())
-        .unwrap_or(0);
+    let children = read_directory_tree_lazy(&path, &path, 0, depth)?;
 
     Ok(FileTreeNode {
         name: pack_name,
         path: String::new(),
         is_dir: true,
+        file_count: Some(children.len()),
+        has_children: !children.is_empty(),
         children: Some(children),
-        file_count: Some(file_count),
         loaded: true,
     })
 }
 
-/// 懒加载指定文件夹的子节点
+/// 懒加载指定文件夹的子节点，只读取一层
 #[tauri::command]
 pub async fn load_folder_children(
     folder_path: String,
@@ -604,10 +2595,545 @@ pub async fn load_folder_children(
     let full_path = if folder_path.is_empty() {
         base_path.clone()
     } else {
-        base_path.join(&folder_path)
+        resolve_pack_path_for(&state, None, &folder_path)?
     };
 
-    read_directory_tree_lazy(&full_path, &base_path, 0, 1)
+    tokio::task::spawn_blocking(move || read_directory_tree_lazy(&full_path, &base_path, 0, 0))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 统计文件夹中直接包含的条目数（不含 .little100），不加载子节点；
+/// recursive 为 true 时并行统计所有子目录中的文件总数
+#[tauri::command]
+pub async fn count_children(
+    folder_path: String,
+    recursive: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let full_path = if folder_path.is_empty() {
+        get_pack_base_path(&state.current_pack_path)?
+    } else {
+        resolve_pack_path_for(&state, None, &folder_path)?
+    };
+    let recursive = recursive.unwrap_or(false);
+
+    tokio::task::spawn_blocking(move || count_dir_entries(&full_path, recursive))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 资源类型在命名空间节点下的显示顺序，其他目录按名称排在后面
+const ASSET_TREE_TYPES: &[&str] = &["textures", "models", "blockstates", "lang", "sounds", "font"];
+
+/// 命名空间节点：省略 assets/<命名空间>/ 前缀，直接列出各资源类型
+fn namespace_tree_node(base_path: &Path, namespace: &str) -> Result<FileTreeNode, String> {
+    let namespace_path = base_path.join("assets").join(namespace);
+    let mut entries: Vec<_> = std::fs::read_dir(&namespace_path)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .filter_map(|e| e.ok())
+        .collect();
+    entries.sort_by_key(|entry| {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let rank = ASSET_TREE_TYPES
+            .iter()
+            .position(|t| *t == name)
+            .unwrap_or(ASSET_TREE_TYPES.len());
+        (!entry.path().is_dir(), rank, name)
+    });
+
+    let children = entries
+        .iter()
+        .map(|entry| {
+            let entry_path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = format!("assets/{}/{}", namespace, name);
+            let node = if entry_path.is_dir() {
+                let children = read_directory_tree_lazy(&entry_path, base_path, 0, 0)?;
+                FileTreeNode {
+                    name,
+                    path,
+                    is_dir: true,
+                    file_count: Some(children.len()),
+                    has_children: !children.is_empty(),
+                    children: Some(children),
+                    loaded: true,
+                }
+            } else {
+                FileTreeNode {
+                    name,
+                    path,
+                    is_dir: false,
+                    children: None,
+                    file_count: None,
+                    loaded: true,
+                    has_children: false,
+                }
+            };
+            Ok(node)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(FileTreeNode {
+        name: namespace.to_string(),
+        path: format!("assets/{}", namespace),
+        is_dir: true,
+        file_count: Some(children.len()),
+        has_children: !children.is_empty(),
+        children: Some(children),
+        loaded: true,
+    })
+}
+
+/// 按命名空间和资源类型分组的文件树，节点的 path 仍是相对材质包根目录的真实路径，
+/// 更深的目录通过 load_folder_children 懒加载
+#[tauri::command]
+pub async fn get_asset_tree(state: State<'_, AppState>) -> Result<FileTreeNode, String> {
+    let path = get_pack_base_path(&state.current_pack_path)?;
+
+    tokio::task::spawn_blocking(move || {
+        let pack_name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let mut namespaces: Vec<String> = std::fs::read_dir(path.join("assets"))
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().is_dir())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        // minecraft 排在最前，其余命名空间按名称排序
+        namespaces.sort_by_key(|namespace| (namespace != "minecraft", namespace.clone()));
+
+        let children = namespaces
+            .iter()
+            .map(|namespace| namespace_tree_node(&path, namespace))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(FileTreeNode {
+            name: pack_name,
+            path: String::new(),
+            is_dir: true,
+            file_count: Some(children.len()),
+            has_children: !children.is_empty(),
+            children: Some(children),
+            loaded: true,
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 文件夹的递归总大小和文件数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderWeight {
+    pub path: String,
+    pub size: u64,
+    pub file_count: usize,
+}
+
+/// 一次遍历计算每个文件夹的递归大小：每个文件的大小累加到其所有上级目录
+fn compute_tree_weights(base_path: &Path) -> Vec<FolderWeight> {
+    use walkdir::WalkDir;
+
+    let mut weights: HashMap<String, (u64, usize)> = HashMap::new();
+    weights.insert(String::new(), (0, 0));
+
+    let walker = WalkDir::new(base_path).into_iter().filter_entry(|e| {
+        e.path()
+            .strip_prefix(base_path)
+            .map(|rel| !crate::zip_handler::is_export_excluded(rel))
+            .unwrap_or(true)
+    });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        let rel = match entry.path().strip_prefix(base_path) {
+            Ok(rel) if !rel.as_os_str().is_empty() => rel.to_string_lossy().replace('\\', "/"),
+            _ => continue,
+        };
+
+        if entry.file_type().is_dir() {
+            weights.entry(rel).or_insert((0, 0));
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        let mut folder = rel.as_str();
+        loop {
+            folder = folder.rfind('/').map(|i| &folder[..i]).unwrap_or("");
+            let weight = weights.entry(folder.to_string()).or_insert((0, 0));
+            weight.0 += size;
+            weight.1 += 1;
+            if folder.is_empty() {
+                break;
+            }
+        }
+    }
+
+    let mut weights: Vec<FolderWeight> = weights
+        .into_iter()
+        .map(|(path, (size, file_count))| FolderWeight {
+            path,
+            size,
+            file_count,
+        })
+        .collect();
+    weights.sort_by(|a, b| a.path.cmp(&b.path));
+    weights
+}
+
+/// 获取每个文件夹的递归总大小和文件数，供文件树显示体积热度（根目录的 path 为空）
+#[tauri::command]
+pub async fn get_tree_weights(state: State<'_, AppState>) -> Result<Vec<FolderWeight>, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+
+    tokio::task::spawn_blocking(move || compute_tree_weights(&base_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))
+}
+
+/// 一类资源的总大小
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizeCategory {
+    /// textures、models、sounds、lang、fonts 或 other
+    pub category: String,
+    pub size: u64,
+    pub file_count: usize,
+}
+
+/// 材质包中的一个文件及其大小
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSize {
+    pub path: String,
+    pub size: u64,
+}
+
+/// 材质包体积分布
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackSizeBreakdown {
+    /// 不含 .history 和 .little100
+    pub total_size: u64,
+    pub categories: Vec<SizeCategory>,
+    pub largest_files: Vec<FileSize>,
+    /// .history 占用的磁盘空间，仅在请求时统计
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_size: Option<u64>,
+}
+
+/// 按相对路径判断资源类别
+fn size_category(relative: &str) -> &'static str {
+    let parts: Vec<&str> = relative.split('/').collect();
+    if parts.first() != Some(&"assets") || parts.len() < 3 {
+        return "other";
+    }
+    match parts[2] {
+        "textures" => "textures",
+        "models" | "blockstates" | "items" => "models",
+        "sounds" | "sounds.json" => "sounds",
+        "lang" => "lang",
+        "font" => "fonts",
+        _ => "other",
+    }
+}
+
+fn compute_size_breakdown(
+    base_path: &Path,
+    top_n: usize,
+    include_history: bool,
+) -> PackSizeBreakdown {
+    use walkdir::WalkDir;
+
+    let mut categories: HashMap<&'static str, (u64, usize)> = HashMap::new();
+    let mut files = Vec::new();
+    let walker = WalkDir::new(base_path).into_iter().filter_entry(|e| {
+        e.path()
+            .strip_prefix(base_path)
+            .map(|rel| !crate::zip_handler::is_export_excluded(rel))
+            .unwrap_or(true)
+    });
+    for entry in walker.filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+        let Ok(rel) = entry.path().strip_prefix(base_path) else { continue };
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        let category = categories.entry(size_category(&rel)).or_insert((0, 0));
+        category.0 += size;
+        category.1 += 1;
+        files.push(FileSize { path: rel, size });
+    }
+
+    files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    let total_size = files.iter().map(|f| f.size).sum();
+    files.truncate(top_n);
+
+    let names = ["textures", "models", "sounds", "lang", "fonts", "other"];
+    let mut categories: Vec<SizeCategory> = names
+        .into_iter()
+        .map(|name| {
+            let (size, file_count) = categories.get(name).copied().unwrap_or((0, 0));
+            SizeCategory {
+                category: name.to_string(),
+                size,
+                file_count,
+            }
+        })
+        .collect();
+    categories.sort_by(|a, b| b.size.cmp(&a.size));
+
+    let history_size = include_history.then(|| {
+        WalkDir::new(base_path.join(".history"))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.metadata().map(|m| m.len()).unwrap_or(0))
+            .sum()
+    });
+
+    PackSizeBreakdown {
+        total_size,
+        categories,
+        largest_files: files,
+        history_size,
+    }
+}
+
+/// 按类别统计材质包体积并列出最大的文件（默认 20 个），`include_history` 为 true 时单独统计 .history
+#[tauri::command]
+pub async fn get_pack_size_breakdown(
+    top_n: Option<usize>,
+    include_history: Option<bool>,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<PackSizeBreakdown, String> {
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    let top_n = top_n.unwrap_or(constants::SIZE_BREAKDOWN_TOP_FILES);
+    let include_history = include_history.unwrap_or(false);
+
+    tokio::task::spawn_blocking(move || compute_size_breakdown(&base_path, top_n, include_history))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))
+}
+
+/// 在后台线程运行可取消的分析任务，通过 analysis-progress 事件报告进度
+async fn run_analysis<T, F>(
+    task_id: String,
+    app_handle: tauri::AppHandle,
+    state: &AppState,
+    analysis: F,
+) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&Path, &tokio_util::sync::CancellationToken, crate::analysis::ProgressFn) -> Result<T, String>
+        + Send
+        + 'static,
+{
+    use tauri::Emitter;
+
+    let path = get_pack_base_path(&state.current_pack_path)?;
+    let token = tokio_util::sync::CancellationToken::new();
+    state.analysis_tokens.lock().insert(task_id.clone(), token.clone());
+
+    let id = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let on_progress = |phase: &str, current: usize, total: usize| {
+            let progress = crate::analysis::AnalysisProgress {
+                task_id: id.clone(),
+                phase: phase.to_string(),
+                current,
+                total,
+            };
+            let _ = app_handle.emit("analysis-progress", &progress);
+        };
+        analysis(&path, &token, &on_progress)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e));
+
+    state.analysis_tokens.lock().remove(&task_id);
+    result?
+}
+
+/// 检查 pack.mcmeta、JSON 语法和图片是否可读，可通过 `cancel_analysis(task_id)` 取消
+#[tauri::command]
+pub async fn validate_pack(
+    task_id: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::analysis::PackIssue>, String> {
+    run_analysis(task_id, app_handle, &state, crate::analysis::validate_pack).await
+}
+
+/// 查找未被模型引用的方块和物品贴图，可通过 `cancel_analysis(task_id)` 取消
+#[tauri::command]
+pub async fn find_unused_textures(
+    task_id: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<crate::analysis::UnusedTexturesReport, String> {
+    run_analysis(task_id, app_handle, &state, crate::analysis::find_unused_textures).await
+}
+
+/// 查找内容完全相同的文件，可通过 `cancel_analysis(task_id)` 取消
+#[tauri::command]
+pub async fn find_duplicate_files(
+    task_id: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::analysis::DuplicateGroup>, String> {
+    run_analysis(task_id, app_handle, &state, crate::analysis::find_duplicate_files).await
+}
+
+/// 交叉检查材质包中的引用，返回未使用的贴图和指向不存在文件的引用，可通过 `cancel_analysis(task_id)` 取消
+#[tauri::command]
+pub async fn analyze_pack(
+    task_id: String,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<crate::analysis::PackAnalysis, String> {
+    run_analysis(task_id, app_handle, &state, crate::analysis::analyze_pack).await
+}
+
+/// 取消正在运行的分析任务，任务不存在时返回 false
+#[tauri::command]
+pub async fn cancel_analysis(task_id: String, state: State<'_, AppState>) -> Result<bool, String> {
+    match state.analysis_tokens.lock().get(&task_id) {
+        Some(token) => {
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// 检查并修复编辑器自己的元数据（.little100 和 .history），不修改材质包资源
+#[tauri::command]
+pub async fn repair_editor_metadata(
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::metadata_repair::MetadataRepairReport, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+    let dry_run = resolve_dry_run(&state, None, dry_run)?;
+    let max_count = state.history_settings.lock().max_count;
+
+    tokio::task::spawn_blocking(move || {
+        crate::metadata_repair::repair_editor_metadata(&base_path, dry_run, max_count)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 命名空间概览
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NamespaceSummary {
+    pub name: String,
+    pub counts: HashMap<String, usize>,
+    pub total_files: usize,
+}
+
+/// 列出 assets/ 下所有命名空间及各类资源文件数量
+#[tauri::command]
+pub async fn get_namespaces(state: State<'_, AppState>) -> Result<Vec<NamespaceSummary>, String> {
+    use walkdir::WalkDir;
+
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+    tokio::task::spawn_blocking(move || -> Result<Vec<NamespaceSummary>, String> {
+        let assets_dir = base_path.join("assets");
+
+        if !assets_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut namespaces: Vec<_> = fs::read_dir(&assets_dir)
+            .map_err(|e| format!("Failed to read assets directory: {}", e))?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.path())
+            .collect();
+        namespaces.sort();
+
+        let summaries = namespaces
+            .par_iter()
+            .map(|ns_path| {
+                let mut counts: HashMap<String, usize> = HashMap::new();
+                let mut total_files = 0;
+
+                for entry in WalkDir::new(ns_path).into_iter().filter_map(|e| e.ok()) {
+                    if !entry.file_type().is_file() {
+                        continue;
+                    }
+                    total_files += 1;
+
+                    // 按命名空间下的第一级目录分类（textures、models 等）
+                    let rel = entry.path().strip_prefix(ns_path).unwrap_or(entry.path());
+                    let category = if rel.components().count() > 1 {
+                        rel.components()
+                            .next()
+                            .map(|c| c.as_os_str().to_string_lossy().to_string())
+                            .unwrap_or_default()
+                    } else {
+                        "root".to_string()
+                    };
+                    *counts.entry(category).or_insert(0) += 1;
+                }
+
+                NamespaceSummary {
+                    name: ns_path
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                    counts,
+                    total_files,
+                }
+            })
+            .collect();
+
+        Ok(summaries)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 获取指定命名空间的文件树
+#[tauri::command]
+pub async fn get_namespace_tree(
+    namespace: String,
+    state: State<'_, AppState>,
+) -> AppResult<FileTreeNode> {
+    if namespace.is_empty()
+        || namespace.contains(['/', '\\'])
+        || namespace == "."
+        || namespace == ".."
+    {
+        return Err(AppError::Validation(format!("Invalid namespace: {}", namespace)));
+    }
+
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+    let ns_path = base_path.join("assets").join(&namespace);
+
+    if !ns_path.is_dir() {
+        return Err(AppError::NotFound(format!("Namespace not found: {}", namespace)));
+    }
+
+    let children = read_directory_tree_lazy(&ns_path, &base_path, 0, 2)?;
+    let file_count = children.len();
+
+    Ok(FileTreeNode {
+        name: namespace.clone(),
+        path: format!("assets/{}", namespace),
+        is_dir: true,
+        children: Some(children),
+        file_count: Some(file_count),
+        loaded: true,
+        has_children: file_count > 0,
+    })
 }
 
 /// 创建透明PNG图片
@@ -618,8 +3144,10 @@ pub async fn create_transparent_png(
     height: u32,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_pack_writable(&state, None)?;
     let full_path = resolve_pack_path(&file_path, &state.current_pack_path)?;
     crate::image_handler::create_transparent_png(&full_path, width, height)?;
+    crate::history_manager::notify_file_changed(&full_path);
     Ok(())
 }
 
@@ -628,11 +3156,13 @@ pub async fn create_transparent_png(
 pub async fn save_image(
     image_path: String,
     base64_data: String,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
     use base64::{engine::general_purpose, Engine as _};
 
-    let full_path = resolve_pack_path(&image_path, &state.current_pack_path)?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &image_path)?;
 
     // 解码base64数据
     let image_data = general_purpose::STANDARD
@@ -643,27 +3173,124 @@ pub async fn save_image(
     if let Some(parent) = full_path.parent() {
         tokio::fs::create_dir_all(parent)
             .await
-            .map_err(|e| format!("Failed to create directory: {}", e))?;
+            .map_err(|e| io_error("Failed to create directory", e))?;
     }
+    let operation = begin_operation(
+        &state,
+        pack_id.as_deref(),
+        format!("Save image {}", image_path),
+        &[image_path.as_str()],
+    )
+    .await;
+
+    // 写入文件（按扩展名编码，支持 .tga 等非 PNG 格式）
+    tokio::task::spawn_blocking(move || {
+        crate::image_handler::save_image_bytes(&full_path, &image_data)
+    })
+    .await
+    .map_err(|e| format!("Failed to save image: {}", e))??;
+    commit_operation(operation).await;
 
-    // 写入文件
-    tokio::fs::write(&full_path, image_data)
+    Ok(())
+}
+
+/// 将动态 GIF 导入为动画材质（PNG 动画条 + .mcmeta）
+#[tauri::command]
+pub async fn import_gif_as_animation(
+    gif_path: String,
+    target_path: String,
+    square_frames: bool,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::image_handler::GifAnimationResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &target_path)?;
+
+    tokio::task::spawn_blocking(move || {
+        let result = crate::image_handler::import_gif_as_animation(
+            Path::new(&gif_path),
+            &full_path,
+            square_frames,
+        )?;
+        crate::history_manager::notify_file_changed(&full_path);
+        crate::history_manager::notify_file_changed(&crate::animation::mcmeta_path(&full_path));
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 读取贴图的动画设置（<贴图>.png.mcmeta 中的 animation），没有时返回 None
+#[tauri::command]
+pub async fn get_animation_mcmeta(
+    texture_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Option<crate::animation::AnimationConfig>, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &texture_path)?;
+
+    tokio::task::spawn_blocking(move || crate::animation::read_animation(&full_path))
         .await
-        .map_err(|e| format!("Failed to save image: {}", e))?;
+        .map_err(|e| format!("Task join error: {}", e))?
+}
 
-    Ok(())
+/// 保存贴图的动画设置，帧索引必须在贴图的帧数范围内
+#[tauri::command]
+pub async fn save_animation_mcmeta(
+    texture_path: String,
+    config: crate::animation::AnimationConfig,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &texture_path)?;
+
+    tokio::task::spawn_blocking(move || {
+        crate::animation::save_animation(&full_path, &config)?;
+        crate::history_manager::notify_file_changed(&crate::animation::mcmeta_path(&full_path));
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
 }
 
-/// 获取版本清单
+/// 将动画贴图切分为按播放顺序排列的帧，供前端循环播放
 #[tauri::command]
-pub async fn get_minecraft_versions() -> Result<crate::version_downloader::VersionManifest, String>
-{
-    crate::version_downloader::fetch_version_manifest().await
+pub async fn get_animation_preview(
+    texture_path: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::animation::AnimationPreview, String> {
+    let full_path = resolve_pack_path_for(&state, pack_id.as_deref(), &texture_path)?;
+
+    tokio::task::spawn_blocking(move || crate::animation::animation_preview(&full_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 设置网络请求的连接超时和读取超时（秒）
+#[tauri::command]
+pub async fn set_network_timeouts(connect_secs: u64, read_secs: u64) -> Result<(), String> {
+    crate::version_downloader::set_http_timeouts(connect_secs, read_secs)
+}
+
+/// 获取版本清单，`mirror` 默认为官方源
+#[tauri::command]
+pub async fn get_minecraft_versions(
+    mirror: Option<crate::version_downloader::DownloadMirror>,
+) -> AppResult<crate::version_downloader::VersionManifest> {
+    let mirror = mirror.unwrap_or_default();
+    Ok(crate::version_downloader::fetch_version_manifest(&mirror).await?)
 }
 
 /// 下载指定的版本jar文件
 #[tauri::command]
-pub async fn download_minecraft_version(version_id: String) -> Result<String, String> {
+pub async fn download_minecraft_version(
+    version_id: String,
+    mirror: Option<crate::version_downloader::DownloadMirror>,
+    resume: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
     // 获取src-tauri目录的路径
     let exe_path = std::env::current_exe().map_err(|e| format!("Failed to get exe path: {}", e))?;
     let exe_dir = exe_path.parent().ok_or("Failed to get exe directory")?;
@@ -674,12 +3301,32 @@ pub async fn download_minecraft_version(version_id: String) -> Result<String, St
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
 
     // 下载版本
-    crate::version_downloader::download_version(&version_id, &temp_dir).await
+    let mirror = mirror.unwrap_or_default();
+    crate::version_downloader::download_version(
+        &version_id,
+        &temp_dir,
+        &mirror,
+        Some(&app_handle),
+        resume.unwrap_or(true),
+    )
+    .await
+}
+
+/// 列出指定版本可下载的语言代码
+#[tauri::command]
+pub async fn get_available_languages(
+    version_id: String,
+    mirror: Option<crate::version_downloader::DownloadMirror>,
+) -> Result<Vec<String>, String> {
+    let mirror = mirror.unwrap_or_default();
+    crate::version_downloader::list_available_languages(&version_id, &mirror).await
 }
 
 /// 下载最新的release版本
 #[tauri::command]
-pub async fn download_latest_minecraft_version() -> Result<String, String> {
+pub async fn download_latest_minecraft_version(
+    mirror: Option<crate::version_downloader::DownloadMirror>,
+) -> Result<String, String> {
     // 获取src-tauri目录的路径
     let exe_path = std::env::current_exe().map_err(|e| format!("Failed to get exe path: {}", e))?;
     let exe_dir = exe_path.parent().ok_or("Failed to get exe directory")?;
@@ -690,16 +3337,63 @@ pub async fn download_latest_minecraft_version() -> Result<String, String> {
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
 
     // 下载最新版本
-    crate::version_downloader::download_latest_release(&temp_dir).await
+    let mirror = mirror.unwrap_or_default();
+    crate::version_downloader::download_latest_release(&temp_dir, &mirror).await
 }
 
-/// 从jar文件中提取assets到指定目录
+/// 从jar文件中提取资源到指定目录，roots 默认为 ["assets/"]，可包含 "data/" 或根目录文件如 "version.json"
 #[tauri::command]
-pub async fn extract_assets_from_jar(jar_path: String, output_path: String) -> Result<(), String> {
+pub async fn extract_assets_from_jar(
+    jar_path: String,
+    output_path: String,
+    roots: Option<Vec<String>>,
+) -> Result<crate::version_downloader::ExtractionSummary, String> {
     let jar = Path::new(&jar_path);
     let output = Path::new(&output_path);
+    let roots = crate::version_downloader::normalize_extract_roots(roots);
+
+    crate::version_downloader::extract_from_jar(jar, output, &roots, None)
+}
+
+/// 从jar中读取单个文件，返回base64编码的内容
+#[tauri::command]
+pub async fn read_asset_from_jar(jar_path: String, inner_path: String) -> Result<String, String> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let bytes = tokio::task::spawn_blocking(move || {
+        crate::version_downloader::read_asset_from_jar(Path::new(&jar_path), &inner_path)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    Ok(general_purpose::STANDARD.encode(&bytes))
+}
+
+/// 列出jar中指定前缀下的文件，用于浏览原版资源
+#[tauri::command]
+pub async fn list_jar_assets(
+    jar_path: String,
+    prefix: Option<String>,
+) -> Result<Vec<crate::version_downloader::JarEntryInfo>, String> {
+    let prefix = prefix.unwrap_or_else(|| "assets/".to_string());
+
+    tokio::task::spawn_blocking(move || {
+        crate::version_downloader::list_jar_assets(Path::new(&jar_path), &prefix)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
 
-    crate::version_downloader::extract_assets_from_jar(jar, output)
+/// 按资源类别统计jar中的文件数量和大小，供选择性提取使用
+#[tauri::command]
+pub async fn summarize_jar_assets(
+    jar_path: String,
+) -> Result<Vec<crate::version_downloader::JarCategorySummary>, String> {
+    tokio::task::spawn_blocking(move || {
+        crate::version_downloader::summarize_jar_assets(Path::new(&jar_path))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
 }
 
 /// 下载版本并提取assets到材质包
@@ -708,6 +3402,10 @@ pub async fn download_and_extract_template(
     version_id: String,
     pack_path: String,
     keep_cache: bool,
+    roots: Option<Vec<String>>,
+    mirror: Option<crate::version_downloader::DownloadMirror>,
+    languages: Option<Vec<String>>,
+    map_language: Option<String>,
     manager: State<'_, std::sync::Arc<crate::download_manager::DownloadManager>>,
 ) -> Result<String, String> {
     // 获取temp目录
@@ -725,7 +3423,8 @@ pub async fn download_and_extract_template(
         format!("下载模板: {}", version_id),
         "template".to_string(),
         output.to_path_buf(),
-    ).await;
+    )
+    .await;
     
     // 克隆管理器用于异步任务
     let manager_clone = std::sync::Arc::clone(&manager);
@@ -733,6 +3432,9 @@ pub async fn download_and_extract_template(
     let version_id_clone = version_id.clone();
     let temp_dir_clone = temp_dir.clone();
     let output_clone = output.to_path_buf();
+    let roots = crate::version_downloader::normalize_extract_roots(roots);
+    let mirror = mirror.unwrap_or_default();
+    let languages = crate::version_downloader::LanguageSelection::new(languages, map_language);
 
     // 在后台启动下载任务
     tokio::spawn(async move {
@@ -741,13 +3443,16 @@ pub async fn download_and_extract_template(
             &temp_dir_clone,
             &output_clone,
             keep_cache,
+            &roots,
             task_id_clone,
             (*manager_clone).clone(),
+            &mirror,
+            &languages,
         )
         .await;
         
         if let Err(e) = result {
-            println!("模板下载失败: {}", e);
+            log::error!("模板下载失败: {}", e);
         }
     });
     
@@ -765,6 +3470,178 @@ pub async fn clear_template_cache() -> Result<(), String> {
     crate::version_downloader::clear_template_cache(&temp_dir)
 }
 
+/// 定位原版资源：优先使用指定路径，否则使用模板缓存中的 <version>.jar
+fn resolve_vanilla_source(
+    version_id: Option<&str>,
+    vanilla_path: Option<&str>,
+) -> Result<PathBuf, String> {
+    if let Some(path) = vanilla_path {
+        let path = PathBuf::from(path);
+        if !path.exists() {
+            return Err(format!("Vanilla source not found: {}", path.display()));
+        }
+        return Ok(path);
+    }
+
+    let version_id = version_id.ok_or("Either version_id or vanilla_path is required")?;
+    let exe_path = std::env::current_exe().map_err(|e| format!("Failed to get exe path: {}", e))?;
+    let exe_dir = exe_path.parent().ok_or("Failed to get exe directory")?;
+    let jar_path = exe_dir.join("temp").join(format!("{}.jar", version_id));
+
+    if !jar_path.is_file() {
+        return Err(format!(
+            "Vanilla jar for {} is not cached, download the template first",
+            version_id
+        ));
+    }
+    Ok(jar_path)
+}
+
+/// 生成相对原版的变更清单（新增 / 修改 / 与原版相同）
+#[tauri::command]
+pub async fn generate_change_manifest(
+    version_id: Option<String>,
+    vanilla_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::vanilla_diff::ChangeManifest, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+    let source = resolve_vanilla_source(version_id.as_deref(), vanilla_path.as_deref())?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut vanilla = crate::vanilla_diff::VanillaSource::open(&source)?;
+        crate::vanilla_diff::generate_change_manifest(&base_path, &mut vanilla)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 下载并解压指定版本的原版 assets/，按版本缓存在 temp/vanilla/ 下
+///
+/// 同一版本的检查、清理和解压按版本加锁串行进行
+async fn cached_vanilla_assets(
+    state: &AppState,
+    version_id: &str,
+    mirror: &crate::version_downloader::DownloadMirror,
+) -> Result<PathBuf, String> {
+    if version_id.is_empty() || version_id.contains(['/', '\\']) || version_id.contains("..") {
+        return Err(format!("Invalid version id: {}", version_id));
+    }
+    let lock = state
+        .vanilla_cache_locks
+        .lock()
+        .entry(version_id.to_string())
+        .or_default()
+        .clone();
+    let _guard = lock.lock().await;
+
+    let exe_path = std::env::current_exe().map_err(|e| format!("Failed to get exe path: {}", e))?;
+    let exe_dir = exe_path.parent().ok_or("Failed to get exe directory")?;
+    let temp_dir = exe_dir.join("temp");
+    let cache_root = temp_dir.join("vanilla");
+    let cache_dir = cache_root.join(version_id);
+    // 解压完成后才写入标记，中断的解压会在下次重新进行
+    let marker = cache_root.join(format!("{}.complete", version_id));
+    if marker.is_file() && cache_dir.is_dir() {
+        return Ok(cache_dir);
+    }
+
+    if cache_dir.exists() {
+        fs::remove_dir_all(&cache_dir)
+            .map_err(|e| format!("Failed to clear vanilla cache: {}", e))?;
+    }
+    fs::create_dir_all(&cache_dir)
+        .map_err(|e| format!("Failed to create vanilla cache directory: {}", e))?;
+
+    let roots = crate::version_downloader::normalize_extract_roots(None);
+    let languages = crate::version_downloader::LanguageSelection::new(Some(Vec::new()), None);
+    crate::version_downloader::download_and_extract_version(
+        version_id,
+        &temp_dir,
+        &cache_dir,
+        true,
+        &roots,
+        mirror,
+        None,
+        &languages,
+    )
+    .await?;
+
+    fs::write(&marker, version_id)
+        .map_err(|e| format!("Failed to write vanilla cache marker: {}", e))?;
+    Ok(cache_dir)
+}
+
+/// 对比当前材质包与原版，逐个文件返回新增、修改（附 SHA1）或与原版相同
+///
+/// 原版资源不存在时会先下载并解压，之后按版本复用
+#[tauri::command]
+pub async fn diff_against_vanilla(
+    version_id: String,
+    mirror: Option<crate::version_downloader::DownloadMirror>,
+    state: State<'_, AppState>,
+) -> Result<crate::vanilla_diff::VanillaDiffReport, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+    let mirror = mirror.unwrap_or_default();
+    let source = cached_vanilla_assets(&state, &version_id, &mirror).await?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut vanilla = crate::vanilla_diff::VanillaSource::open(&source)?;
+        crate::vanilla_diff::diff_against_vanilla(&base_path, &mut vanilla, &version_id)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 删除与原版相同的文件（默认仅预览），删除可通过操作历史撤销
+///
+/// 指定 `vanilla_path` 时与该 jar 或文件夹对比，否则按 `version_id` 使用（必要时下载）缓存的原版资源
+#[tauri::command]
+pub async fn strip_vanilla_identical(
+    version_id: Option<String>,
+    vanilla_path: Option<String>,
+    mirror: Option<crate::version_downloader::DownloadMirror>,
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::vanilla_diff::StripResult, String> {
+    let dry_run = resolve_dry_run(&state, None, dry_run)?;
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+    let source = match (vanilla_path.as_deref(), version_id.as_deref()) {
+        (Some(path), _) => resolve_vanilla_source(None, Some(path))?,
+        (None, Some(version_id)) => {
+            let mirror = mirror.unwrap_or_default();
+            cached_vanilla_assets(&state, version_id, &mirror).await?
+        }
+        (None, None) => return Err("Either version_id or vanilla_path is required".to_string()),
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let mut vanilla = crate::vanilla_diff::VanillaSource::open(&source)?;
+        crate::vanilla_diff::strip_vanilla_identical(&base_path, &mut vanilla, dry_run)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 检查材质包 GUI 贴图在两个版本间是否需要调整，需先下载两个版本的模板
+#[tauri::command]
+pub async fn check_gui_layout(
+    from_version: String,
+    to_version: String,
+    state: State<'_, AppState>,
+) -> Result<crate::vanilla_diff::GuiLayoutReport, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+    let from_source = resolve_vanilla_source(Some(&from_version), None)?;
+    let to_source = resolve_vanilla_source(Some(&to_version), None)?;
+
+    tokio::task::spawn_blocking(move || {
+        let mut from = crate::vanilla_diff::VanillaSource::open(&from_source)?;
+        let mut to = crate::vanilla_diff::VanillaSource::open(&to_source)?;
+        crate::vanilla_diff::check_gui_layout(&base_path, &mut from, &mut to)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
 #[tauri::command]
 pub async fn preload_folder_images(
     folder_path: String,
@@ -788,13 +3665,30 @@ pub async fn preload_folder_images(
     };
     state
         .preloader
-        .preload_folder(&full_path, &base_path, 512)
+        .preload_folder(&full_path, &base_path, constants::PRELOAD_THUMBNAIL_SIZE)
         .await
 }
 
+/// 清除磁盘和内存中的缩略图缓存
+#[tauri::command]
+pub async fn clear_thumbnail_cache(
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    tokio::task::spawn_blocking(move || {
+        crate::image_handler::clear_thumbnail_disk_cache(&base_path)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 预加载缓存的大小及预览命令的命中统计
 #[tauri::command]
-pub async fn get_preloader_stats(state: State<'_, AppState>) -> Result<(usize, usize), String> {
-    Ok(state.preloader.get_stats().await)
+pub async fn get_preloader_stats(
+    state: State<'_, AppState>,
+) -> Result<crate::preloader::PreloaderStats, String> {
+    Ok(state.preloader.stats())
 }
 
 #[tauri::command]
@@ -803,11 +3697,21 @@ pub async fn clear_preloader_cache(state: State<'_, AppState>) -> Result<(), Str
     Ok(())
 }
 
+/// 设置预加载缓存的字节上限，超出部分立即按最近最少使用淘汰
+#[tauri::command]
+pub async fn set_preloader_cache_limit(
+    max_bytes: usize,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state.preloader.set_max_cache_bytes(max_bytes);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn preload_folder_aggressive(
     folder_path: String,
     state: State<'_, AppState>,
-) -> Result<usize, String> {
+) -> Result<crate::preloader::PreloadResult, String> {
     let (base_path, full_path) = {
         let pack_path = state.current_pack_path.lock();
 
@@ -825,12 +3729,56 @@ pub async fn preload_folder_aggressive(
         (base_path, full_path)
     };
 
+    let pool = state.thread_pool.lock().clone();
     state
         .preloader
-        .preload_folder_aggressive(&full_path, &base_path)
+        .preload_folder_aggressive(&full_path, &base_path, &pool)
         .await
 }
 
+/// 只移除指定文件夹（相对材质包根目录）下的预加载缓存，返回移除的数量
+#[tauri::command]
+pub async fn evict_folder_cache(
+    folder_prefix: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    Ok(state.preloader.evict_folder(&folder_prefix))
+}
+
+/// 取消正在进行的激进预加载，`folder_path` 为空时取消全部，返回取消的预加载数
+#[tauri::command]
+pub async fn cancel_preload(
+    folder_path: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let folder = match folder_path {
+        Some(folder_path) => {
+            let base_path = get_pack_base_path(&state.current_pack_path)?;
+            Some(if folder_path.is_empty() {
+                base_path
+            } else {
+                base_path.join(&folder_path)
+            })
+        }
+        None => None,
+    };
+    Ok(state.preloader.cancel_preload(folder.as_deref()))
+}
+
+/// 设置并行任务使用的线程数，0 表示使用全部可用 CPU，返回实际线程数
+#[tauri::command]
+pub async fn set_parallelism(threads: usize, state: State<'_, AppState>) -> Result<usize, String> {
+    let threads = if threads == 0 {
+        crate::preloader::default_parallelism()
+    } else {
+        threads
+    };
+    let pool = crate::preloader::build_thread_pool(threads)?;
+    let effective = pool.current_num_threads();
+    *state.thread_pool.lock() = Arc::new(pool);
+    Ok(effective)
+}
+
 /// 打开指定文件夹
 #[tauri::command]
 pub async fn open_folder(folder_path: String) -> Result<(), String> {
@@ -870,38 +3818,35 @@ pub async fn open_folder(folder_path: String) -> Result<(), String> {
 /// 写入日志到文件
 #[allow(dead_code)]
 pub async fn write_log(level: &str, message: &str) {
-    let exe_path = match std::env::current_exe() {
-        Ok(path) => path,
-        Err(_) => return,
+    let level = match level.to_lowercase().as_str() {
+        "error" => log::Level::Error,
+        "warn" | "warning" => log::Level::Warn,
+        "debug" => log::Level::Debug,
+        "trace" => log::Level::Trace,
+        _ => log::Level::Info,
     };
+    log::log!(level, "{}", message);
+}
 
-    let exe_dir = match exe_path.parent() {
-        Some(dir) => dir,
-        None => return,
-    };
-
-    let logs_dir = exe_dir.join("logs");
-
-    // 确保logs目录存在
-    if let Err(_) = std::fs::create_dir_all(&logs_dir) {
-        return;
-    }
-
-    let log_file = logs_dir.join("latest.log");
+/// 设置日志级别（off / error / warn / info / debug / trace）
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<(), String> {
+    let filter = crate::logger::parse_level(&level)?;
+    log::set_max_level(filter);
+    log::info!("日志级别已设置为 {}", filter);
+    Ok(())
+}
 
-    // 格式化日志条目
-    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-    let log_entry = format!("[{}] [{}] {}\n", timestamp, level.to_uppercase(), message);
+/// 设置保留的历史日志数量，重启后仍然有效
+#[tauri::command]
+pub async fn set_log_retention(count: usize) -> Result<(), String> {
+    crate::logger::set_retention(count)
+}
 
-    // 追加到文件
-    use std::io::Write;
-    if let Ok(mut file) = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(&log_file)
-    {
-        let _ = file.write_all(log_entry.as_bytes());
-    }
+/// 获取历史日志列表（从新到旧）
+#[tauri::command]
+pub async fn get_recent_logs() -> Result<Vec<crate::logger::LogFileInfo>, String> {
+    Ok(crate::logger::list_rotated_logs())
 }
 
 /// 读取语言映射表
@@ -979,7 +3924,9 @@ pub struct SearchResult {
     pub file_path: String,
     pub match_type: String,
     pub line_number: Option<usize>,
+    /// 内容匹配的行预览，去掉首尾空白，过长时截断
     pub line_content: Option<String>,
+    /// 匹配的字节位置，内容匹配时相对于 line_content
     pub match_start: Option<usize>,
     pub match_end: Option<usize>,
     pub translation: Option<String>,
@@ -991,24 +3938,42 @@ pub struct SearchResponse {
     pub filename_matches: Vec<SearchResult>,
     pub content_matches: Vec<SearchResult>,
     pub total_count: usize,
+    /// 匹配数超过上限，只返回了前一部分
+    pub truncated: bool,
+}
+
+/// 内容搜索的文件范围
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FileFilter {
+    /// 只搜索这些扩展名（不含点），为空时搜索所有文本文件
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    /// 只搜索该文件夹（相对材质包根目录）下的文件
+    #[serde(default)]
+    pub folder: Option<String>,
 }
 
-/// 搜索文件
+
+/// 搜索文件名和文本文件（JSON、lang、mcmeta 等）的内容，`filter` 限定扩展名和文件夹
 #[tauri::command]
 pub async fn search_files(
     query: String,
     case_sensitive: bool,
     use_regex: bool,
+    filter: Option<FileFilter>,
+    pack_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<SearchResponse, String> {
-    let pack_path = state.current_pack_path.lock();
-    
-    let base_path = match pack_path.as_ref() {
-        Some(path) => path.clone(),
-        None => return Err("No pack loaded".to_string()),
+    if query.is_empty() {
+        return Err("Search query cannot be empty".to_string());
+    }
+    let filter = filter.unwrap_or_default();
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    let root = match &filter.folder {
+        Some(folder) => base_path.join(pack_relative_for(&state, pack_id.as_deref(), folder)?.1),
+        None => base_path.clone(),
     };
-    
-    drop(pack_path);
+    let extensions: Vec<String> = filter.extensions.iter().map(|e| e.to_lowercase()).collect();
     
     // 加载语言映射表用于中文搜索
     let language_map = load_language_map_sync(&base_path);
@@ -1019,70 +3984,110 @@ pub async fn search_files(
     } else {
         None
     };
+    // 内容搜索统一用正则匹配原始行，保证位置是原始字符串中的字节位置
+    let content_source = if use_regex { query.clone() } else { regex::escape(&query) };
+    let content_pattern = regex::RegexBuilder::new(&content_source)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid regex pattern: {}", e))?;
     
-    // 收集所有文件
-    let files = collect_searchable_files(&base_path)?;
-    
-    // 并行搜索
-    let (filename_matches, content_matches): (Vec<_>, Vec<_>) = files
-        .par_iter()
-        .filter_map(|file_path| {
-            search_in_file(
-                file_path,
-                &base_path,
-                &query,
-                case_sensitive,
-                use_regex,
-                regex_pattern.as_ref(),
-                &language_map,
-            ).ok()
-        })
-        .flatten()
-        .partition(|result| result.match_type == "filename");
-    
-    // 限制结果数量
-    let filename_matches: Vec<_> = filename_matches.into_iter().take(100).collect();
-    let content_matches: Vec<_> = content_matches.into_iter().take(200).collect();
-    
-    let total_count = filename_matches.len() + content_matches.len();
-    
-    Ok(SearchResponse {
-        filename_matches,
-        content_matches,
-        total_count,
+    tokio::task::spawn_blocking(move || {
+        // 收集所有文件
+        let files = collect_searchable_files(&base_path, &root, &extensions);
+        
+        // 并行搜索
+        let (mut filename_matches, mut content_matches): (Vec<_>, Vec<_>) = files
+            .par_iter()
+            .filter_map(|file_path| {
+                search_in_file(
+                    file_path,
+                    &base_path,
+                    &query,
+                    case_sensitive,
+                    regex_pattern.as_ref(),
+                    &content_pattern,
+                    &language_map,
+                ).ok()
+            })
+            .flatten()
+            .partition(|result| result.match_type == "filename");
+        content_matches.sort_by(|a, b| {
+            a.file_path.cmp(&b.file_path).then(a.line_number.cmp(&b.line_number))
+        });
+        
+        // 限制结果数量
+        let truncated = filename_matches.len() > constants::SEARCH_MAX_FILENAME_RESULTS
+            || content_matches.len() > constants::SEARCH_MAX_CONTENT_RESULTS;
+        filename_matches.truncate(constants::SEARCH_MAX_FILENAME_RESULTS);
+        content_matches.truncate(constants::SEARCH_MAX_CONTENT_RESULTS);
+        
+        let total_count = filename_matches.len() + content_matches.len();
+        
+        SearchResponse {
+            filename_matches,
+            content_matches,
+            total_count,
+            truncated,
+        }
     })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))
 }
 
-/// 收集可搜索的文件（并行优化版本）
-fn collect_searchable_files(base_path: &Path) -> Result<Vec<PathBuf>, String> {
+/// 收集 root 下可搜索的文件，`extensions` 为空时收集 PNG 和所有文本文件
+fn collect_searchable_files(base_path: &Path, root: &Path, extensions: &[String]) -> Vec<PathBuf> {
     use walkdir::WalkDir;
     
-    // 并行收集文件
-    let files: Vec<PathBuf> = WalkDir::new(base_path)
+    WalkDir::new(root)
         .follow_links(false)
         .into_iter()
         .filter_entry(|e| {
             // 排除 .history 和 .little100
-            if let Some(name) = e.file_name().to_str() {
-                !matches!(name, ".history" | ".little100")
-            } else {
-                true
-            }
+            e.path()
+                .strip_prefix(base_path)
+                .map(|rel| !crate::zip_handler::is_export_excluded(rel))
+                .unwrap_or(true)
         })
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
         .filter(|e| {
-            if let Some(ext) = e.path().extension() {
-                let ext_str = ext.to_string_lossy().to_lowercase();
-                matches!(ext_str.as_str(), "json" | "mcmeta" | "txt" | "png" | "lang")
+            let Some(ext) = e.path().extension() else {
+                return false;
+            };
+            let ext_str = ext.to_string_lossy().to_lowercase();
+            if extensions.is_empty() {
+                ext_str == "png" || constants::SEARCH_TEXT_EXTENSIONS.contains(&ext_str.as_str())
             } else {
-                false
+                extensions.contains(&ext_str)
             }
         })
         .map(|e| e.path().to_path_buf())
-        .collect();
-    
-    Ok(files)
+        .collect()
+}
+
+/// 生成去掉首尾空白、截断到 SEARCH_LINE_PREVIEW_CHARS 个字符的行预览，
+/// 匹配不在开头部分时从匹配处开始截取，返回预览和匹配在预览中的字节位置
+fn line_preview(line: &str, match_start: usize, match_end: usize) -> (String, usize, usize) {
+    let trimmed = line.trim();
+    let offset = line.len() - line.trim_start().len();
+    let start = match_start.saturating_sub(offset).min(trimmed.len());
+    let end = match_end.saturating_sub(offset).min(trimmed.len());
+
+    let head_end = trimmed
+        .char_indices()
+        .nth(constants::SEARCH_LINE_PREVIEW_CHARS)
+        .map_or(trimmed.len(), |(index, _)| index);
+    let window_start = if start < head_end { 0 } else { start };
+    let window = &trimmed[window_start..];
+    let window_end = window
+        .char_indices()
+        .nth(constants::SEARCH_LINE_PREVIEW_CHARS)
+        .map_or(window.len(), |(index, _)| index);
+    let preview = &window[..window_end];
+
+    let start = start - window_start;
+    let end = end.saturating_sub(window_start).clamp(start, preview.len());
+    (preview.to_string(), start, end)
 }
 
 /// 同步加载语言映射表
@@ -1227,8 +4232,8 @@ fn search_in_file(
     base_path: &Path,
     query: &str,
     case_sensitive: bool,
-    use_regex: bool,
     regex_pattern: Option<&Regex>,
+    content_pattern: &Regex,
     language_map: &std::collections::HashMap<String, String>,
 ) -> Result<Vec<SearchResult>, String> {
     let mut results = Vec::new();
@@ -1249,17 +4254,9 @@ fn search_in_file(
     let translation = get_file_translation(file_path, base_path, language_map);
     
     // 搜索文件名
-    let (filename_match, match_start, match_end) = if use_regex {
-        if let Some(regex) = regex_pattern {
-            if regex.is_match(&file_name) {
-                if let Some(mat) = regex.find(&file_name) {
-                    (true, Some(mat.start()), Some(mat.end()))
-                } else {
-                    (true, None, None)
-                }
-            } else {
-                (false, None, None)
-            }
+    let (filename_match, match_start, match_end) = if let Some(regex) = regex_pattern {
+        if let Some(mat) = regex.find(&file_name) {
+            (true, Some(mat.start()), Some(mat.end()))
         } else {
             (false, None, None)
         }
@@ -1302,7 +4299,7 @@ fn search_in_file(
     // 搜索文件内容
     if let Some(ext) = file_path.extension() {
         let ext_str = ext.to_string_lossy().to_lowercase();
-        if matches!(ext_str.as_str(), "json" | "mcmeta" | "txt" | "lang") {
+        if constants::SEARCH_TEXT_EXTENSIONS.contains(&ext_str.as_str()) {
             // 读取文件内容限制大小
             let metadata = std::fs::metadata(file_path).ok();
             if let Some(meta) = metadata {
@@ -1312,62 +4309,19 @@ fn search_in_file(
                 }
             }
             
-            // 预计算小写查询字符串，避免在循环中重复调用
-            let query_lower = if !case_sensitive { query.to_lowercase() } else { String::new() };
-            
+            // 非 UTF-8 文件视为二进制文件跳过
             if let Ok(content) = std::fs::read_to_string(file_path) {
                 for (line_num, line) in content.lines().enumerate() {
-                    let line_match = if use_regex {
-                        if let Some(regex) = regex_pattern {
-                            regex.is_match(line)
-                        } else {
-                            false
-                        }
-                    } else {
-                        if case_sensitive {
-                            line.contains(query)
-                        } else {
-                            line.to_lowercase().contains(&query_lower)
-                        }
-                    };
-                    
-                    if line_match {
-                        let (match_start, match_end) = if use_regex {
-                            if let Some(regex) = regex_pattern {
-                                if let Some(mat) = regex.find(line) {
-                                    (Some(mat.start()), Some(mat.end()))
-                                } else {
-                                    (None, None)
-                                }
-                            } else {
-                                (None, None)
-                            }
-                        } else {
-                            let search_line = if case_sensitive {
-                                line.to_string()
-                            } else {
-                                line.to_lowercase()
-                            };
-                            let search_query = if case_sensitive {
-                                query.to_string()
-                            } else {
-                                query.to_lowercase()
-                            };
-                            
-                            if let Some(pos) = search_line.find(&search_query) {
-                                (Some(pos), Some(pos + query.len()))
-                            } else {
-                                (None, None)
-                            }
-                        };
-                        
+                    if let Some(mat) = content_pattern.find(line) {
+                        let (preview, match_start, match_end) =
+                            line_preview(line, mat.start(), mat.end());
                         results.push(SearchResult {
                             file_path: relative_path.clone(),
                             match_type: "content".to_string(),
                             line_number: Some(line_num + 1),
-                            line_content: Some(line.to_string()),
-                            match_start,
-                            match_end,
+                            line_content: Some(preview),
+                            match_start: Some(match_start),
+                            match_end: Some(match_end),
                             translation: None, // 内容匹配不需要翻译
                         });
                     }
@@ -1401,7 +4355,8 @@ pub async fn download_minecraft_sounds(
         "Minecraft 声音资源".to_string(),
         "sounds".to_string(),
         output_dir.clone(),
-    ).await;
+    )
+    .await;
     
     let manager_clone = Arc::clone(&manager);
     let task_id_clone = task_id.clone();
@@ -1413,7 +4368,8 @@ pub async fn download_minecraft_sounds(
             task_id_clone.clone(),
             manager_clone.clone(),
             concurrent_downloads.unwrap_or(32),
-        ).await;
+        )
+        .await;
         
         // 更新最终状态
         match result {
@@ -1546,9 +4502,11 @@ pub async fn fetch_url(url: String) -> Result<serde_json::Value, String> {
     
     let is_trusted = constants::is_trusted_domain(host);
     
-    let response = reqwest::get(&url)
+    let response = crate::version_downloader::http_client()
+        .get(&url)
+        .send()
         .await
-        .map_err(|e| format!("Failed to fetch URL: {}", e))?;
+        .map_err(|e| crate::version_downloader::network_error("Failed to fetch URL", e))?;
     
     if !response.status().is_success() {
         return Err(format!("HTTP error! status: {}", response.status()));
@@ -1579,6 +4537,7 @@ pub async fn copy_sound_file(
     sound_name: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    ensure_pack_writable(&state, None)?;
     // 获取路径
     let base_path = {
         let pack_path = state.current_pack_path.lock();
@@ -1613,10 +4572,106 @@ pub async fn copy_sound_file(
     tokio::fs::copy(&source_path, &target_path)
         .await
         .map_err(|e| format!("Failed to copy audio file: {}", e))?;
+    crate::history_manager::notify_file_changed(&target_path);
     
     Ok(())
 }
 
+/// 校验 sounds.json 引用的音频文件
+#[tauri::command]
+pub async fn validate_sounds(
+    state: State<'_, AppState>,
+) -> Result<crate::sounds::SoundValidationReport, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+
+    tokio::task::spawn_blocking(move || crate::sounds::validate_sounds(&base_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 读取命名空间的 sounds.json，文件不存在时返回空映射
+#[tauri::command]
+pub async fn get_sounds_json(
+    namespace: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::sounds::SoundsDef, String> {
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+    crate::sounds::get_sounds_json(&base_path, &namespace)
+}
+
+/// 保存命名空间的 sounds.json，返回引用缺失 .ogg 文件的问题
+#[tauri::command]
+pub async fn update_sounds_json(
+    namespace: String,
+    sounds: crate::sounds::SoundsDef,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<crate::sounds::SoundsUpdateResult, String> {
+    ensure_pack_writable(&state, pack_id.as_deref())?;
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+
+    tokio::task::spawn_blocking(move || {
+        let result = crate::sounds::update_sounds_json(&base_path, &namespace, &sounds)?;
+        crate::history_manager::notify_file_changed(&base_path.join(&result.path));
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 列出命名空间 sounds 目录下的 .ogg 文件
+#[tauri::command]
+pub async fn list_sound_files(
+    namespace: String,
+    pack_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let base_path = pack_base_for(&state, pack_id.as_deref())?;
+
+    tokio::task::spawn_blocking(move || crate::sounds::list_sound_files(&base_path, &namespace))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 校验 OptiFine 发光贴图（_e）与基础贴图的配对
+#[tauri::command]
+pub async fn validate_emissive(
+    state: State<'_, AppState>,
+) -> Result<crate::optifine::EmissiveReport, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+
+    tokio::task::spawn_blocking(move || crate::optifine::validate_emissive(&base_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 检查贴图等文件名中的大写字母，以及大小写与磁盘不一致的引用
+#[tauri::command]
+pub async fn check_filename_casing(
+    state: State<'_, AppState>,
+) -> Result<crate::casing::CasingReport, String> {
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+
+    tokio::task::spawn_blocking(move || crate::casing::check_filename_casing(&base_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// 将文件名改为小写并更新所有引用（默认仅预览）
+#[tauri::command]
+pub async fn normalize_filenames(
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::casing::NormalizeResult, String> {
+    let dry_run = resolve_dry_run(&state, None, dry_run)?;
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+
+    tokio::task::spawn_blocking(move || crate::casing::normalize_filenames(&base_path, dry_run))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
 /// 检查临时文件夹中的音频文件
 #[tauri::command]
 pub async fn check_temp_audio_files(state: State<'_, AppState>) -> Result<Vec<String>, String> {
@@ -1783,6 +4838,35 @@ pub async fn execute_pack_merge(
     execute_merge_async(app_handle, sources, config).await
 }
 
+/// 将另一个材质包（zip 或文件夹）的文件合并到当前材质包，默认只预览合并结果
+#[tauri::command]
+pub async fn merge_pack(
+    other_pack_path: String,
+    conflict_policy: crate::pack_merger::ConflictPolicy,
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<crate::pack_merger::PackMergeReport, String> {
+    let dry_run = resolve_dry_run(&state, None, dry_run)?;
+    let base_path = get_pack_base_path(&state.current_pack_path)?;
+
+    let scan_path = base_path.clone();
+    let report = tokio::task::spawn_blocking(move || {
+        let other_pack_path = Path::new(&other_pack_path);
+        crate::pack_merger::merge_into_pack(&base_path, other_pack_path, conflict_policy, dry_run)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+    if dry_run {
+        return Ok(report);
+    }
+
+    // 重新扫描材质包
+    let pack_info = scan_pack_directory(&scan_path)?;
+    *state.current_pack_info.lock() = Some(pack_info);
+
+    Ok(report)
+}
+
 /// 获取pack.mcmeta
 #[tauri::command]
 pub async fn get_pack_meta_from_source(
@@ -1818,9 +4902,149 @@ pub async fn get_pack_meta_from_source(
         .map_err(|e| format!("无法解析 pack.mcmeta JSON: {}", e))
 }
 
+/// 调试信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DebugInfo {
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub pack_path: Option<String>,
+    pub pack_file_count: usize,
+    pub open_pack_count: usize,
+    pub preloader_cache_entries: usize,
+    pub preloader_cache_bytes: usize,
+    pub thumbnail_cache_entries: usize,
+    pub thumbnail_cache_bytes: usize,
+    pub history_size: u64,
+    pub active_downloads: usize,
+    pub web_server_running: bool,
+    pub rayon_threads: usize,
+    pub log_level: String,
+    pub recent_log: Vec<String>,
+}
+
+/// 获取用于问题反馈的调试信息
+#[tauri::command]
+pub async fn get_debug_info(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+    manager: State<'_, Arc<crate::download_manager::DownloadManager>>,
+) -> Result<DebugInfo, String> {
+    use crate::download_manager::DownloadStatus;
+    #[cfg(feature = "web-server")]
+    use tauri::Manager;
+
+    let pack_path = state.current_pack_path.lock().clone();
+
+    let (pack_file_count, history_size) = match pack_path.clone() {
+        Some(path) => tokio::task::spawn_blocking(move || {
+            let count = walkdir::WalkDir::new(&path)
+                .into_iter()
+                .filter_entry(|e| {
+                    !matches!(e.file_name().to_str(), Some(".history" | ".little100"))
+                })
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .count();
+            (count, crate::history_manager::history_dir_size(&path))
+        })
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?,
+        None => (0, 0),
+    };
+
+    let active_downloads = manager
+        .get_all_tasks()
+        .await
+        .iter()
+        .filter(|t| matches!(t.status, DownloadStatus::Pending | DownloadStatus::Downloading))
+        .count();
+
+    #[cfg(feature = "web-server")]
+    let web_server_running = match app_handle.try_state::<crate::web_server::WebServerState>() {
+        Some(server) => *server.running.lock().await,
+        None => false,
+    };
+    #[cfg(not(feature = "web-server"))]
+    let web_server_running = false;
+
+    let (preloader_cache_entries, _) = state.preloader.get_stats().await;
+    let (thumbnail_cache_entries, thumbnail_cache_bytes) =
+        crate::image_handler::thumbnail_cache_usage();
+    let rayon_threads = state.thread_pool.lock().current_num_threads();
+
+    Ok(DebugInfo {
+        app_version: app_handle.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        pack_path: pack_path.map(|p| p.to_string_lossy().to_string()),
+        pack_file_count,
+        open_pack_count: state.open_packs.lock().len(),
+        preloader_cache_entries,
+        preloader_cache_bytes: state.preloader.cache_bytes(),
+        thumbnail_cache_entries,
+        thumbnail_cache_bytes,
+        history_size,
+        active_downloads,
+        web_server_running,
+        rayon_threads,
+        log_level: log::max_level().to_string(),
+        recent_log: crate::logger::tail(constants::DEBUG_INFO_LOG_LINES),
+    })
+}
+
 /// 打开devtools
 #[tauri::command]
 pub async fn open_devtools(window: tauri::WebviewWindow) -> Result<(), String> {
     window.open_devtools();
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_pack(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("little100_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_line_preview_offsets_are_relative_to_preview() {
+        let line = "    \"texture\": \"block/stone\",";
+        let start = line.find("stone").unwrap();
+        let (preview, start, end) = line_preview(line, start, start + 5);
+        assert_eq!(preview, "\"texture\": \"block/stone\",");
+        assert_eq!(&preview[start..end], "stone");
+
+        let long = format!("{}needle", "a".repeat(constants::SEARCH_LINE_PREVIEW_CHARS + 10));
+        let start = long.find("needle").unwrap();
+        let (preview, start, end) = line_preview(&long, start, start + 6);
+        assert_eq!(&preview[start..end], "needle");
+    }
+
+    #[test]
+    fn test_tree_lists_dirs_first_then_by_name() {
+        let pack = temp_pack("tree_order");
+        for dir in ["b_dir", "a_dir/nested", ".little100"] {
+            std::fs::create_dir_all(pack.join(dir)).unwrap();
+        }
+        for file in ["a.txt", "c.txt", "pack.mcmeta"] {
+            std::fs::write(pack.join(file), b"").unwrap();
+        }
+
+        let nodes = read_directory_tree_lazy(&pack, &pack, 0, 0).unwrap();
+        let names: Vec<&str> = nodes.iter().map(|n| n.name.as_str()).collect();
+        let a_dir = nodes.iter().find(|n| n.name == "a_dir").unwrap();
+        let b_dir = nodes.iter().find(|n| n.name == "b_dir").unwrap();
+        let count = count_dir_entries(&pack, false).unwrap();
+        let _ = std::fs::remove_dir_all(&pack);
+
+        assert_eq!(names, ["a_dir", "b_dir", "a.txt", "c.txt", "pack.mcmeta"]);
+        assert!(a_dir.has_children && !a_dir.loaded && a_dir.children.is_none());
+        assert!(!b_dir.has_children);
+        assert_eq!(count, 5);
+    }
+}
@@ -1,21 +1,58 @@
 #![allow(dead_code)]
 
-pub const PRELOADER_CACHE_SIZE: usize = 200;
+pub const PRELOADER_CACHE_BYTES: usize = 256 * 1024 * 1024;
 pub const THUMBNAIL_CACHE_SIZE: usize = 1000;
 pub const IMAGE_INFO_CACHE_SIZE: usize = 2000;
 pub const IMAGE_SIZE_THUMBNAIL: u32 = 128;
 pub const IMAGE_SIZE_PREVIEW: u32 = 512;
 pub const IMAGE_SIZE_FULL: u32 = 2048;
 pub const IMAGE_SIZE_DEFAULT: u32 = 512;
+pub const PRELOAD_THUMBNAIL_SIZE: u32 = 512;
+pub const MAX_THUMBNAIL_BATCH_SIZE: usize = 1000;
 pub const PNG_MAX_SIZE: u32 = 8192;
+pub const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "tga"];
+
+pub fn is_supported_image_ext(ext: &str) -> bool {
+    let ext = ext.to_lowercase();
+    SUPPORTED_IMAGE_EXTENSIONS.contains(&ext.as_str())
+}
 
 pub const SEARCH_MAX_FILENAME_RESULTS: usize = 100;
 pub const SEARCH_MAX_CONTENT_RESULTS: usize = 200;
 pub const SEARCH_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+pub const SEARCH_LINE_PREVIEW_CHARS: usize = 200;
+pub const SEARCH_TEXT_EXTENSIONS: &[&str] = &[
+    "json", "mcmeta", "txt", "lang", "properties", "jem", "jpm", "fsh", "vsh", "glsl",
+];
 pub const LOG_MAX_LINES: usize = 50;
 
+pub const EXPORT_EXCLUDED_DIRS: &[&str] = &[".history", ".little100"];
+pub const EXPORT_PREFLIGHT_ERRORS_SHOWN: usize = 5;
+pub const LIVE_DEPLOY_DEBOUNCE_MS: u64 = 300;
+pub const DEBUG_INFO_LOG_LINES: usize = 100;
+pub const LOG_RETENTION_COUNT: usize = 5;
+pub const LOG_RETENTION_FILE: &str = "retention.txt";
+pub const DEFAULT_MAX_HISTORY_COUNT: u32 = 30;
+pub const MAX_OPERATION_LOG_SIZE: usize = 100;
+pub const SIZE_BREAKDOWN_TOP_FILES: usize = 20;
+pub const FILE_TREE_MAX_EAGER_DEPTH: usize = 3;
+pub const PACK_ICON_SIZE: u32 = 128;
+pub const ENGINE_TEXTURE_PREFIXES: &[&str] = &[
+    "assets/minecraft/textures/block/destroy_stage_",
+    "assets/minecraft/textures/block/water_",
+    "assets/minecraft/textures/block/lava_",
+    "assets/minecraft/textures/block/fire_",
+    "assets/minecraft/textures/block/soul_fire_",
+    "assets/minecraft/textures/item/empty_armor_slot_",
+    "assets/minecraft/textures/item/empty_slot_",
+];
+
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 15;
+pub const DEFAULT_READ_TIMEOUT_SECS: u64 = 60;
+pub const VERSION_DETAILS_CACHE_TTL_SECS: u64 = 24 * 60 * 60;
 pub const DEFAULT_CONCURRENT_DOWNLOADS: usize = 32;
 pub const MAX_CONCURRENT_DOWNLOADS: usize = 256;
+pub const DOWNLOAD_PROGRESS_INTERVAL_MS: u64 = 100;
 
 pub const CJK_UNIFIED_START: u32 = 0x4E00;
 pub const CJK_UNIFIED_END: u32 = 0x9FFF;
@@ -31,6 +68,10 @@ pub fn is_cjk_char(c: char) -> bool {
 }
 
 pub const WEB_SERVER_TOKEN_LENGTH: usize = 32;
+pub const WEB_SERVER_TOKEN_COOKIE: &str = "little100_token";
+pub const LIVE_RELOAD_PATH: &str = "/__live_reload";
+pub const WEB_SERVER_HEALTH_PATH: &str = "/__health";
+pub const LIVE_RELOAD_BACKLOG: usize = 64;
 
 pub const FETCH_BLOCKED_HOSTS: &[&str] = &[
     "localhost",
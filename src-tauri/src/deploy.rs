@@ -0,0 +1,321 @@
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use walkdir::WalkDir;
+
+use crate::error::io_error;
+use crate::zip_handler::is_export_excluded;
+
+/// 导出到文件夹的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FolderExportResult {
+    pub output_path: String,
+    pub files_copied: usize,
+    pub bytes_copied: u64,
+}
+
+/// 将材质包复制到目标文件夹下与材质包同名的子文件夹（如 resourcepacks/<材质包名>）
+///
+/// `overwrite` 为 true 时先清空该子文件夹，否则与已有内容合并；
+/// 只会清空包含 pack.mcmeta 的文件夹，避免误删其他内容
+pub fn export_to_folder(
+    pack_path: &Path,
+    dest: &Path,
+    overwrite: bool,
+) -> Result<FolderExportResult, String> {
+    let pack_canon = pack_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve pack path: {}", e))?;
+    let pack_name = pack_canon
+        .file_name()
+        .ok_or_else(|| "Failed to determine pack name".to_string())?;
+    let dest = &dest.join(pack_name);
+    let dest_canon = crate::path_security::canonical_path(dest);
+
+    if dest_canon == pack_canon {
+        return Err("Destination cannot be the pack itself".to_string());
+    }
+    if dest_canon.starts_with(&pack_canon) {
+        return Err("Destination cannot be inside the pack".to_string());
+    }
+    if pack_canon.starts_with(&dest_canon) {
+        return Err("Destination cannot contain the pack".to_string());
+    }
+
+    if overwrite && dest.exists() {
+        if !dest.is_dir() || !dest.join("pack.mcmeta").is_file() {
+            return Err(format!(
+                "Refusing to overwrite {}: it is not a resource pack folder",
+                dest.display()
+            ));
+        }
+        fs::remove_dir_all(dest)
+            .map_err(|e| format!("Failed to clear destination: {}", e))?;
+    }
+    fs::create_dir_all(dest)
+        .map_err(|e| io_error("Failed to create destination", e))?;
+
+    let mut files_copied = 0;
+    let mut bytes_copied = 0u64;
+
+    for entry in WalkDir::new(pack_path).into_iter().filter_map(|e| e.ok()) {
+        let rel = entry
+            .path()
+            .strip_prefix(pack_path)
+            .map_err(|e| format!("Failed to strip prefix: {}", e))?;
+
+        if rel.as_os_str().is_empty() || is_export_excluded(rel) {
+            continue;
+        }
+
+        let target = dest.join(rel);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)
+                .map_err(|e| io_error("Failed to create directory", e))?;
+        } else if entry.file_type().is_file() {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| io_error("Failed to create directory", e))?;
+            }
+            bytes_copied += fs::copy(entry.path(), &target)
+                .map_err(|e| io_error(&format!("Failed to copy {}", rel.display()), e))?;
+            files_copied += 1;
+        }
+    }
+
+    Ok(FolderExportResult {
+        output_path: dest.to_string_lossy().to_string(),
+        files_copied,
+        bytes_copied,
+    })
+}
+
+/// 检测到的 resourcepacks 目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcepacksDir {
+    pub path: String,
+    pub launcher: String,
+    pub instance: Option<String>,
+}
+
+/// 获取当前系统默认的 .minecraft 目录
+pub fn default_minecraft_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var_os("APPDATA").map(|appdata| PathBuf::from(appdata).join(".minecraft"))
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join("Library")
+                .join("Application Support")
+                .join("minecraft")
+        })
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".minecraft"))
+    }
+}
+
+/// 获取默认的 resourcepacks 目录（不检查是否存在）
+pub fn default_resourcepacks_dir() -> Option<PathBuf> {
+    default_minecraft_dir().map(|dir| dir.join("resourcepacks"))
+}
+
+/// 各启动器实例目录（启动器名, instances 目录）
+fn launcher_instance_roots() -> Vec<(&'static str, PathBuf)> {
+    let mut roots = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(appdata) = std::env::var_os("APPDATA") {
+            let appdata = PathBuf::from(appdata);
+            roots.push(("Prism Launcher", appdata.join("PrismLauncher").join("instances")));
+            roots.push(("PolyMC", appdata.join("PolyMC").join("instances")));
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(home) = std::env::var_os("HOME") {
+            let support = PathBuf::from(home).join("Library").join("Application Support");
+            roots.push(("Prism Launcher", support.join("PrismLauncher").join("instances")));
+            roots.push(("PolyMC", support.join("PolyMC").join("instances")));
+            roots.push(("MultiMC", support.join("MultiMC").join("instances")));
+        }
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        if let Some(home) = std::env::var_os("HOME") {
+            let home = PathBuf::from(home);
+            let share = home.join(".local").join("share");
+            roots.push(("Prism Launcher", share.join("PrismLauncher").join("instances")));
+            roots.push(("PolyMC", share.join("PolyMC").join("instances")));
+            roots.push(("MultiMC", share.join("multimc").join("instances")));
+            roots.push((
+                "Prism Launcher (Flatpak)",
+                home.join(".var")
+                    .join("app")
+                    .join("org.prismlauncher.PrismLauncher")
+                    .join("data")
+                    .join("PrismLauncher")
+                    .join("instances"),
+            ));
+        }
+    }
+
+    roots
+}
+
+/// 查找本机所有已存在的 resourcepacks 目录，找不到时返回空列表
+pub fn find_resourcepacks_dirs() -> Vec<ResourcepacksDir> {
+    let mut found = Vec::new();
+
+    if let Some(dir) = default_resourcepacks_dir() {
+        if dir.is_dir() {
+            found.push(ResourcepacksDir {
+                path: dir.to_string_lossy().to_string(),
+                launcher: "Minecraft Launcher".to_string(),
+                instance: None,
+            });
+        }
+    }
+
+    for (launcher, instances_dir) in launcher_instance_roots() {
+        let entries = match fs::read_dir(&instances_dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let instance_dir = entry.path();
+            if !instance_dir.is_dir() {
+                continue;
+            }
+
+            // 实例游戏目录可能是 .minecraft 或 minecraft
+            let dir = [".minecraft", "minecraft"]
+                .iter()
+                .map(|name| instance_dir.join(name).join("resourcepacks"))
+                .find(|p| p.is_dir());
+
+            if let Some(dir) = dir {
+                found.push(ResourcepacksDir {
+                    path: dir.to_string_lossy().to_string(),
+                    launcher: launcher.to_string(),
+                    instance: Some(entry.file_name().to_string_lossy().to_string()),
+                });
+            }
+        }
+    }
+
+    found
+}
+
+/// 实时部署同步事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveDeploySynced {
+    pub path: String,
+    pub action: String,
+    pub error: Option<String>,
+}
+
+/// 实时部署句柄，drop 后停止监听
+pub struct LiveDeploy {
+    _watcher: RecommendedWatcher,
+    pub pack_path: PathBuf,
+    pub dest: PathBuf,
+}
+
+/// 监听材质包变更并增量同步到目标文件夹
+pub fn start_live_deploy(
+    app_handle: AppHandle,
+    pack_path: PathBuf,
+    dest: PathBuf,
+) -> Result<LiveDeploy, String> {
+    let (tx, rx) = channel::<notify::Result<notify::Event>>();
+
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+    watcher
+        .watch(&pack_path, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch pack: {}", e))?;
+
+    let worker_pack = pack_path.clone();
+    let worker_dest = dest.clone();
+
+    std::thread::spawn(move || {
+        let debounce = Duration::from_millis(crate::constants::LIVE_DEPLOY_DEBOUNCE_MS);
+        let mut pending: HashSet<PathBuf> = HashSet::new();
+
+        loop {
+            match rx.recv_timeout(debounce) {
+                Ok(Ok(event)) => {
+                    pending.extend(event.paths);
+                }
+                Ok(Err(e)) => {
+                    log::warn!("[实时部署] 监听错误: {}", e);
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    for path in pending.drain() {
+                        if let Some(synced) = sync_changed_path(&worker_pack, &worker_dest, &path) {
+                            let _ = app_handle.emit("live-deploy-synced", &synced);
+                        }
+                    }
+                }
+                // watcher 已被 drop
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+
+    Ok(LiveDeploy {
+        _watcher: watcher,
+        pack_path,
+        dest,
+    })
+}
+
+/// 同步单个变更路径，忽略材质包外及排除目录中的路径
+fn sync_changed_path(pack_path: &Path, dest: &Path, changed: &Path) -> Option<LiveDeploySynced> {
+    let rel = changed.strip_prefix(pack_path).ok()?;
+    if rel.as_os_str().is_empty() || is_export_excluded(rel) {
+        return None;
+    }
+
+    let target = dest.join(rel);
+    let rel_str = rel.to_string_lossy().replace('\\', "/");
+
+    let (action, result) = if changed.is_file() {
+        let result = target
+            .parent()
+            .map(fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .and_then(|_| fs::copy(changed, &target).map(|_| ()));
+        ("copied", result)
+    } else if changed.is_dir() {
+        ("created", fs::create_dir_all(&target))
+    } else if target.is_dir() {
+        ("deleted", fs::remove_dir_all(&target))
+    } else if target.exists() {
+        ("deleted", fs::remove_file(&target))
+    } else {
+        return None;
+    };
+
+    Some(LiveDeploySynced {
+        path: rel_str,
+        action: action.to_string(),
+        error: result.err().map(|e| e.to_string()),
+    })
+}
@@ -31,6 +31,45 @@ pub struct DownloadProgress {
     pub error: Option<String>,
 }
 
+/// 模板下载的阶段
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplatePhase {
+    DownloadingJar,
+    ExtractingAssets,
+    DownloadingLanguageFiles,
+}
+
+impl TemplatePhase {
+    /// 阶段显示名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            TemplatePhase::DownloadingJar => "downloading jar",
+            TemplatePhase::ExtractingAssets => "extracting assets",
+            TemplatePhase::DownloadingLanguageFiles => "downloading language files",
+        }
+    }
+
+    /// 阶段在整体进度中的起点和占比（百分比）
+    fn span(&self) -> (f64, f64) {
+        match self {
+            TemplatePhase::DownloadingJar => (0.0, 60.0),
+            TemplatePhase::ExtractingAssets => (60.0, 30.0),
+            TemplatePhase::DownloadingLanguageFiles => (90.0, 10.0),
+        }
+    }
+}
+
+/// 模板下载的分阶段进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseProgress {
+    pub task_id: String,
+    pub phase: TemplatePhase,
+    pub label: String,
+    pub phase_percent: f64,
+    pub overall_percent: f64,
+}
+
 /// 下载任务
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadTask {
@@ -119,6 +158,25 @@ impl DownloadManager {
         }
     }
 
+    /// 发送模板下载的阶段进度事件
+    pub fn emit_phase_progress(&self, task_id: &str, phase: TemplatePhase, phase_percent: f64) {
+        let phase_percent = phase_percent.clamp(0.0, 100.0);
+        let (start, weight) = phase.span();
+        let progress = PhaseProgress {
+            task_id: task_id.to_string(),
+            phase,
+            label: phase.label().to_string(),
+            phase_percent,
+            overall_percent: start + weight * phase_percent / 100.0,
+        };
+        let _ = self.app_handle.emit("template-phase-progress", &progress);
+    }
+
+    /// 发送任务完成后的汇总事件
+    pub fn emit_summary<S: Serialize + Clone>(&self, summary: &S) {
+        let _ = self.app_handle.emit("template-summary", summary);
+    }
+
     /// 获取任务
     pub async fn get_task(&self, task_id: &str) -> Option<DownloadTask> {
         let tasks = self.tasks.read().await;
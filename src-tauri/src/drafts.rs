@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+/// 未保存编辑内容的草稿目录
+pub fn drafts_dir(pack_path: &Path) -> PathBuf {
+    pack_path.join(".little100").join("drafts")
+}
+
+/// 比磁盘上的文件更新、可以恢复的草稿
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftInfo {
+    pub path: String,
+    pub size: u64,
+    pub draft_modified: String,
+    /// 对应文件的修改时间，文件不存在时为 None
+    pub file_modified: Option<String>,
+}
+
+fn format_time(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Local>::from(time)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+/// 保存草稿，`relative` 为相对材质包根目录的文件路径
+pub fn save_draft(pack_path: &Path, relative: &Path, content: &str) -> Result<(), String> {
+    let draft_path = drafts_dir(pack_path).join(relative);
+    if let Some(parent) = draft_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create drafts directory: {}", e))?;
+    }
+    fs::write(&draft_path, content).map_err(|e| format!("Failed to write draft: {}", e))
+}
+
+/// 读取草稿，不存在时返回 None
+pub fn load_draft(pack_path: &Path, relative: &Path) -> Result<Option<String>, String> {
+    let draft_path = drafts_dir(pack_path).join(relative);
+    if !draft_path.is_file() {
+        return Ok(None);
+    }
+    fs::read_to_string(&draft_path)
+        .map(Some)
+        .map_err(|e| format!("Failed to read draft: {}", e))
+}
+
+/// 删除草稿及其留下的空目录
+pub fn discard_draft(pack_path: &Path, relative: &Path) -> Result<(), String> {
+    let root = drafts_dir(pack_path);
+    let draft_path = root.join(relative);
+    if !draft_path.is_file() {
+        return Ok(());
+    }
+    fs::remove_file(&draft_path).map_err(|e| format!("Failed to remove draft: {}", e))?;
+
+    let mut dir = draft_path.parent();
+    while let Some(parent) = dir {
+        if parent == root || !parent.starts_with(&root) || fs::remove_dir(parent).is_err() {
+            break;
+        }
+        dir = parent.parent();
+    }
+    Ok(())
+}
+
+/// 列出比对应文件更新（或对应文件已不存在）的草稿
+pub fn list_recoverable_drafts(pack_path: &Path) -> Vec<DraftInfo> {
+    let root = drafts_dir(pack_path);
+    let mut drafts: Vec<DraftInfo> = WalkDir::new(&root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let relative = e.path().strip_prefix(&root).ok()?;
+            let metadata = e.metadata().ok()?;
+            let draft_modified = metadata.modified().ok()?;
+            let file_modified = fs::metadata(pack_path.join(relative))
+                .and_then(|m| m.modified())
+                .ok();
+
+            if matches!(file_modified, Some(file_modified) if file_modified >= draft_modified) {
+                return None;
+            }
+
+            Some(DraftInfo {
+                path: relative.to_string_lossy().replace('\\', "/"),
+                size: metadata.len(),
+                draft_modified: format_time(draft_modified),
+                file_modified: file_modified.map(format_time),
+            })
+        })
+        .collect();
+    drafts.sort_by(|a, b| a.path.cmp(&b.path));
+    drafts
+}
@@ -0,0 +1,262 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// 命令返回给前端的错误类型
+///
+/// 序列化为 `{ kind, message, context }`，前端按 `kind` 判断错误类型而不是匹配错误文本
+#[derive(Debug, Clone)]
+pub enum AppError {
+    Io(String),
+    Parse(String),
+    NotFound(String),
+    Validation(String),
+    Network(String),
+    Timeout(String),
+    ReadOnly(String),
+    /// 没有写入目标位置的权限
+    PermissionDenied(String),
+    /// 磁盘空间不足
+    DiskFull(String),
+    /// 目标位置所在的文件系统只读
+    ReadOnlyFilesystem(String),
+    PathEscape(String),
+    NoPackLoaded,
+    Other(String),
+    /// 附带上下文（如正在处理的文件）的错误
+    Context {
+        context: String,
+        source: Box<AppError>,
+    },
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+const PERMISSION_DENIED_HINT: &str =
+    "Permission denied: the destination is not writable, check its permissions or choose another location";
+const DISK_FULL_HINT: &str =
+    "Not enough disk space: free up space on the destination drive and try again";
+const READ_ONLY_FILESYSTEM_HINT: &str = "The destination is read-only: choose a writable location";
+
+impl AppError {
+    /// 错误类型名称
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "Io",
+            AppError::Parse(_) => "Parse",
+            AppError::NotFound(_) => "NotFound",
+            AppError::Validation(_) => "Validation",
+            AppError::Network(_) => "Network",
+            AppError::Timeout(_) => "Timeout",
+            AppError::ReadOnly(_) => "ReadOnly",
+            AppError::PermissionDenied(_) => "PermissionDenied",
+            AppError::DiskFull(_) => "DiskFull",
+            AppError::ReadOnlyFilesystem(_) => "ReadOnlyFilesystem",
+            AppError::PathEscape(_) => "PathEscape",
+            AppError::NoPackLoaded => "NoPackLoaded",
+            AppError::Other(_) => "Other",
+            AppError::Context { source, .. } => source.kind(),
+        }
+    }
+
+    /// 不含上下文的错误信息
+    pub fn message(&self) -> String {
+        match self {
+            AppError::Io(msg)
+            | AppError::Parse(msg)
+            | AppError::NotFound(msg)
+            | AppError::Validation(msg)
+            | AppError::Network(msg)
+            | AppError::Timeout(msg)
+            | AppError::ReadOnly(msg)
+            | AppError::PermissionDenied(msg)
+            | AppError::DiskFull(msg)
+            | AppError::ReadOnlyFilesystem(msg)
+            | AppError::PathEscape(msg)
+            | AppError::Other(msg) => msg.clone(),
+            AppError::NoPackLoaded => "No pack loaded".to_string(),
+            AppError::Context { source, .. } => source.message(),
+        }
+    }
+
+    /// 错误上下文，多层上下文由外到内以 ": " 连接
+    pub fn context_text(&self) -> Option<String> {
+        match self {
+            AppError::Context { context, source } => Some(match source.context_text() {
+                Some(inner) => format!("{}: {}", context, inner),
+                None => context.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// 为错误附加上下文
+    pub fn context(self, context: impl Into<String>) -> Self {
+        AppError::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Context { context, source } => write!(f, "{}: {}", context, source),
+            _ => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.message())?;
+        state.serialize_field("context", &self.context_text())?;
+        state.end()
+    }
+}
+
+/// 旧代码返回的字符串错误，按已知文本归类
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        if message == "No pack loaded" {
+            AppError::NoPackLoaded
+        } else if message == "Pack is read-only" {
+            AppError::ReadOnly(message)
+        } else if message.starts_with("Request timed out") {
+            AppError::Timeout(message)
+        } else if message.starts_with("Access denied") {
+            AppError::PathEscape(message)
+        } else if message.contains(PERMISSION_DENIED_HINT) {
+            AppError::PermissionDenied(message)
+        } else if message.contains(DISK_FULL_HINT) {
+            AppError::DiskFull(message)
+        } else if message.contains(READ_ONLY_FILESYSTEM_HINT) {
+            AppError::ReadOnlyFilesystem(message)
+        } else {
+            AppError::Other(message)
+        }
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::from(message.to_string())
+    }
+}
+
+/// 权限、磁盘空间等用户可处理的 IO 错误附带处理建议
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+
+        match e.kind() {
+            ErrorKind::PermissionDenied => {
+                AppError::PermissionDenied(format!("{} ({})", PERMISSION_DENIED_HINT, e))
+            }
+            ErrorKind::StorageFull | ErrorKind::QuotaExceeded => {
+                AppError::DiskFull(format!("{} ({})", DISK_FULL_HINT, e))
+            }
+            ErrorKind::ReadOnlyFilesystem => {
+                AppError::ReadOnlyFilesystem(format!("{} ({})", READ_ONLY_FILESYSTEM_HINT, e))
+            }
+            _ => AppError::Io(e.to_string()),
+        }
+    }
+}
+
+/// 供仍返回 `Result<_, String>` 的函数转换 IO 错误，保留处理建议
+pub fn io_error(context: &str, e: std::io::Error) -> String {
+    AppError::from(e).context(context).to_string()
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::Parse(e.to_string())
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            AppError::Timeout(e.to_string())
+        } else {
+            AppError::Network(e.to_string())
+        }
+    }
+}
+
+/// 供仍返回 `Result<_, String>` 的函数使用 `?`
+impl From<AppError> for String {
+    fn from(e: AppError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    fn test_permission_denied_maps_to_variant() {
+        let err = AppError::from(Error::from(ErrorKind::PermissionDenied));
+        assert_eq!(err.kind(), "PermissionDenied");
+        assert!(err.message().contains("not writable"));
+    }
+
+    #[test]
+    fn test_storage_full_maps_to_disk_full() {
+        let err = AppError::from(Error::from(ErrorKind::StorageFull));
+        assert_eq!(err.kind(), "DiskFull");
+        assert!(err.message().contains("Not enough disk space"));
+    }
+
+    #[test]
+    fn test_read_only_filesystem_maps_to_variant() {
+        let err = AppError::from(Error::from(ErrorKind::ReadOnlyFilesystem));
+        assert_eq!(err.kind(), "ReadOnlyFilesystem");
+    }
+
+    #[test]
+    fn test_other_io_error_maps_to_io() {
+        let err = AppError::from(Error::from(ErrorKind::NotFound));
+        assert_eq!(err.kind(), "Io");
+    }
+
+    #[test]
+    fn test_io_error_string_keeps_kind() {
+        let message = io_error("Failed to write file", Error::from(ErrorKind::StorageFull));
+        assert!(message.starts_with("Failed to write file: "));
+        assert_eq!(AppError::from(message).kind(), "DiskFull");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_to_read_only_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir =
+            std::env::temp_dir().join(format!("little100_readonly_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        let result = std::fs::write(dir.join("texture.png"), b"data");
+
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // root 用户不受目录权限限制
+        let err = match result {
+            Ok(()) => return,
+            Err(e) => AppError::from(e).context("Failed to write file"),
+        };
+        assert_eq!(err.kind(), "PermissionDenied");
+        assert_eq!(err.context_text().as_deref(), Some("Failed to write file"));
+        assert!(err.to_string().contains("not writable"));
+    }
+}
@@ -0,0 +1,481 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use walkdir::WalkDir;
+
+/// 单个字体提供器的覆盖情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderCoverage {
+    pub provider_type: String,
+    /// 贴图、字体文件或引用的字体 ID
+    pub source: Option<String>,
+    /// 该提供器覆盖的所需字符数
+    pub covered: usize,
+    /// 无法在材质包中找到或解析时为 false（如引用原版字体）
+    pub resolved: bool,
+}
+
+/// 字体字符覆盖报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontCoverage {
+    pub required: usize,
+    pub covered: usize,
+    pub missing: Vec<String>,
+    pub providers: Vec<ProviderCoverage>,
+}
+
+/// 已解析的字体提供器
+enum Provider {
+    /// bitmap 和 space 提供器直接列出字符
+    Chars(HashSet<char>),
+    /// TTF 字体按字形表判断，`skip` 中的字符不提供
+    Ttf {
+        font: font_kit::font::Font,
+        skip: HashSet<char>,
+    },
+    /// 无法解析的提供器
+    Unresolved,
+}
+
+impl Provider {
+    fn covers(&self, c: char) -> bool {
+        match self {
+            Provider::Chars(chars) => chars.contains(&c),
+            Provider::Ttf { font, skip } => !skip.contains(&c) && font.glyph_for_char(c).is_some(),
+            Provider::Unresolved => false,
+        }
+    }
+}
+
+/// 将 "命名空间:路径" 解析为 assets/<命名空间>/<folder>/<路径>
+fn resource_path(pack_path: &Path, id: &str, folder: &str) -> std::path::PathBuf {
+    let (namespace, path) = id.split_once(':').unwrap_or(("minecraft", id));
+    pack_path
+        .join("assets")
+        .join(namespace)
+        .join(folder)
+        .join(path)
+}
+
+/// 字符串或字符串数组中的所有字符
+fn chars_of(value: Option<&Value>) -> HashSet<char> {
+    match value {
+        Some(Value::String(s)) => s.chars().collect(),
+        Some(Value::Array(rows)) => rows
+            .iter()
+            .filter_map(|row| row.as_str())
+            .flat_map(|row| row.chars())
+            .collect(),
+        _ => HashSet::new(),
+    }
+}
+
+/// 解析字体定义中的提供器，`reference` 提供器递归展开
+fn collect_providers(
+    pack_path: &Path,
+    font: &Value,
+    visited: &mut HashSet<String>,
+    providers: &mut Vec<(ProviderCoverage, Provider)>,
+) {
+    let list = match font.get("providers").and_then(|p| p.as_array()) {
+        Some(list) => list,
+        None => return,
+    };
+
+    for entry in list {
+        let provider_type = entry
+            .get("type")
+            .and_then(|t| t.as_str())
+            .unwrap_or("")
+            .to_string();
+        let mut info = ProviderCoverage {
+            provider_type: provider_type.clone(),
+            source: None,
+            covered: 0,
+            resolved: true,
+        };
+
+        let provider = match provider_type.as_str() {
+            "bitmap" => {
+                info.source = entry.get("file").and_then(|f| f.as_str()).map(String::from);
+                // \u0000 是网格中的空位
+                let mut chars = chars_of(entry.get("chars"));
+                chars.remove(&'\0');
+                Provider::Chars(chars)
+            }
+            "space" => Provider::Chars(
+                entry
+                    .get("advances")
+                    .and_then(|a| a.as_object())
+                    .map(|advances| advances.keys().flat_map(|k| k.chars()).collect())
+                    .unwrap_or_default(),
+            ),
+            "ttf" => {
+                let file = entry.get("file").and_then(|f| f.as_str()).unwrap_or("");
+                info.source = Some(file.to_string());
+                let font = fs::read(resource_path(pack_path, file, "font"))
+                    .ok()
+                    .and_then(|data| {
+                        font_kit::handle::Handle::from_memory(Arc::new(data), 0)
+                            .load()
+                            .ok()
+                    });
+                match font {
+                    Some(font) => Provider::Ttf {
+                        font,
+                        skip: chars_of(entry.get("skip")),
+                    },
+                    None => Provider::Unresolved,
+                }
+            }
+            "reference" => {
+                let id = entry.get("id").and_then(|i| i.as_str()).unwrap_or("");
+                info.source = Some(id.to_string());
+                let path = resource_path(pack_path, &format!("{}.json", id), "font");
+                let referenced = fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<Value>(&content).ok());
+                match referenced {
+                    Some(referenced) => {
+                        if visited.insert(id.to_string()) {
+                            collect_providers(pack_path, &referenced, visited, providers);
+                        }
+                        continue;
+                    }
+                    None => Provider::Unresolved,
+                }
+            }
+            // unihex、legacy_unicode 等依赖原版资源的提供器
+            _ => Provider::Unresolved,
+        };
+
+        info.resolved = !matches!(provider, Provider::Unresolved);
+        providers.push((info, provider));
+    }
+}
+
+/// 所需字符预设：ascii、latin1，或 lang（材质包语言文件中实际用到的字符）
+fn preset_chars(pack_path: &Path, preset: &str) -> Result<BTreeSet<char>, String> {
+    match preset {
+        "ascii" => Ok((0x20u8..=0x7E).map(char::from).collect()),
+        "latin1" => Ok((0x20u8..=0x7E).chain(0xA0..=0xFF).map(char::from).collect()),
+        "lang" => {
+            let mut chars = BTreeSet::new();
+            for entry in WalkDir::new(pack_path.join("assets"))
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+            {
+                let path = entry.path();
+                let in_lang = path
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .map(|name| name == "lang")
+                    .unwrap_or(false);
+                if !in_lang || path.extension().map(|e| e != "json").unwrap_or(true) {
+                    continue;
+                }
+                let json = fs::read_to_string(path)
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<Value>(&content).ok());
+                if let Some(Value::Object(entries)) = json {
+                    for value in entries.values().filter_map(|v| v.as_str()) {
+                        chars.extend(value.chars());
+                    }
+                }
+            }
+            Ok(chars)
+        }
+        _ => Err(format!("Unknown character preset: {}", preset)),
+    }
+}
+
+/// 检查字体定义对所需字符的覆盖情况
+pub fn font_coverage(
+    pack_path: &Path,
+    font_json: &Path,
+    required_chars: Option<&str>,
+    preset: Option<&str>,
+) -> Result<FontCoverage, String> {
+    let content = fs::read_to_string(font_json)
+        .map_err(|e| format!("Failed to read font definition: {}", e))?;
+    let font: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse font definition: {}", e))?;
+
+    let mut required: BTreeSet<char> = required_chars.unwrap_or("").chars().collect();
+    if let Some(preset) = preset {
+        required.extend(preset_chars(pack_path, preset)?);
+    }
+    required.retain(|c| !c.is_control());
+    if required.is_empty() {
+        return Err("No required characters specified".to_string());
+    }
+
+    let mut providers = Vec::new();
+    collect_providers(pack_path, &font, &mut HashSet::new(), &mut providers);
+
+    let mut missing = Vec::new();
+    for &c in &required {
+        match providers
+            .iter_mut()
+            .find(|(_, provider)| provider.covers(c))
+        {
+            Some((info, _)) => info.covered += 1,
+            None => missing.push(c.to_string()),
+        }
+    }
+
+    Ok(FontCoverage {
+        required: required.len(),
+        covered: required.len() - missing.len(),
+        missing,
+        providers: providers.into_iter().map(|(info, _)| info).collect(),
+    })
+}
+
+/// 字体定义中的一个提供器，按 type 区分
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FontProvider {
+    /// 位图字形表，`chars` 每行对应贴图中的一行字形
+    Bitmap {
+        /// 贴图 ID，如 minecraft:font/ascii.png
+        file: String,
+        /// 字形高度，省略时为 8
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        height: Option<i32>,
+        ascent: i32,
+        chars: Vec<String>,
+    },
+    Ttf {
+        /// 字体 ID，如 minecraft:custom.ttf
+        file: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        shift: Option<[f32; 2]>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        size: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        oversample: Option<f32>,
+        /// 不使用该字体的字符，字符串或字符串数组
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skip: Option<Value>,
+    },
+    Space {
+        /// 字符到宽度的映射
+        advances: std::collections::BTreeMap<String, f32>,
+    },
+    Unihex {
+        hex_file: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        size_overrides: Option<Vec<Value>>,
+    },
+    Reference {
+        id: String,
+    },
+}
+
+/// 提供器及其通用字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontProviderEntry {
+    #[serde(flatten)]
+    pub provider: FontProvider,
+    /// 按字体选项启用，如 {"uniform": false}
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+}
+
+/// assets/<命名空间>/font/<名称>.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontDef {
+    pub providers: Vec<FontProviderEntry>,
+}
+
+/// 保存字体定义的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FontDefResult {
+    pub path: String,
+    /// 材质包中找不到的贴图等（可能来自原版）
+    pub warnings: Vec<String>,
+}
+
+/// 检查 bitmap 提供器的字符网格与贴图尺寸是否一致，返回贴图缺失等警告
+fn validate_bitmap(
+    pack_path: &Path,
+    index: usize,
+    file: &str,
+    height: Option<i32>,
+    ascent: i32,
+    chars: &[String],
+) -> Result<Vec<String>, String> {
+    let height = height.unwrap_or(8);
+    if ascent > height {
+        return Err(format!(
+            "Provider {}: ascent {} is higher than height {}",
+            index, ascent, height
+        ));
+    }
+
+    let columns = chars.first().map(|row| row.chars().count()).unwrap_or(0);
+    if columns == 0 {
+        return Err(format!("Provider {}: chars cannot be empty", index));
+    }
+    if let Some(row) = chars.iter().position(|row| row.chars().count() != columns) {
+        return Err(format!(
+            "Provider {}: row {} of chars has {} characters, expected {}",
+            index,
+            row,
+            chars[row].chars().count(),
+            columns
+        ));
+    }
+
+    let texture = resource_path(pack_path, file, "textures");
+    if !texture.is_file() {
+        return Ok(vec![format!("Texture not found in pack: {}", file)]);
+    }
+    let (width, texture_height) = image::image_dimensions(&texture)
+        .map_err(|e| format!("Failed to read texture {}: {}", file, e))?;
+    let rows = chars.len() as u32;
+    if width % columns as u32 != 0 || texture_height % rows != 0 {
+        return Err(format!(
+            "Provider {}: texture size {}x{} cannot be divided into {} columns and {} rows",
+            index, width, texture_height, columns, rows
+        ));
+    }
+    Ok(Vec::new())
+}
+
+/// 读取字体定义
+pub fn get_font_definition(path: &Path) -> Result<FontDef, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read font definition: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse font definition: {}", e))
+}
+
+/// 校验并保存字体定义，文件不存在时创建
+pub fn update_font_definition(
+    pack_path: &Path,
+    font_path: &str,
+    def: &FontDef,
+) -> Result<FontDefResult, String> {
+    let mut warnings = Vec::new();
+    for (index, entry) in def.providers.iter().enumerate() {
+        match &entry.provider {
+            FontProvider::Bitmap {
+                file,
+                height,
+                ascent,
+                chars,
+            } => warnings.extend(validate_bitmap(
+                pack_path, index, file, *height, *ascent, chars,
+            )?),
+            FontProvider::Ttf { file, .. } => {
+                if !resource_path(pack_path, file, "font").is_file() {
+                    warnings.push(format!("Font file not found in pack: {}", file));
+                }
+            }
+            FontProvider::Reference { id } => {
+                if !resource_path(pack_path, &format!("{}.json", id), "font").is_file() {
+                    warnings.push(format!("Referenced font not found in pack: {}", id));
+                }
+            }
+            FontProvider::Space { .. } | FontProvider::Unihex { .. } => {}
+        }
+    }
+
+    let path = pack_path.join(font_path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create font directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(def)
+        .map_err(|e| format!("Failed to serialize font definition: {}", e))?;
+    fs::write(&path, content)
+        .map_err(|e| crate::error::io_error("Failed to write font definition", e))?;
+
+    Ok(FontDefResult {
+        path: font_path.to_string(),
+        warnings,
+    })
+}
+
+/// 按字形表生成 bitmap 提供器，字符从 `start_codepoint` 开始按行依次填入网格
+///
+/// `texture_path` 为相对材质包根目录的路径，必须位于 assets/<命名空间>/textures/ 下
+pub fn generate_bitmap_font(
+    pack_path: &Path,
+    texture_path: &str,
+    columns: u32,
+    rows: u32,
+    start_codepoint: u32,
+    ascent: i32,
+    height: Option<i32>,
+) -> Result<FontProviderEntry, String> {
+    if columns == 0 || rows == 0 {
+        return Err("Columns and rows must be greater than 0".to_string());
+    }
+    if ascent > height.unwrap_or(8) {
+        return Err(format!(
+            "Ascent {} is higher than height {}",
+            ascent,
+            height.unwrap_or(8)
+        ));
+    }
+
+    let normalized = texture_path.replace('\\', "/");
+    let file = normalized
+        .strip_prefix("assets/")
+        .and_then(|rest| rest.split_once("/textures/"))
+        .map(|(namespace, path)| format!("{}:{}", namespace, path))
+        .ok_or_else(|| {
+            format!(
+                "Texture must be under assets/<namespace>/textures/: {}",
+                texture_path
+            )
+        })?;
+
+    let (width, texture_height) = image::image_dimensions(pack_path.join(&normalized))
+        .map_err(|e| format!("Failed to read texture: {}", e))?;
+    if width % columns != 0 || texture_height % rows != 0 {
+        return Err(format!(
+            "Texture size {}x{} cannot be divided into {} columns and {} rows",
+            width, texture_height, columns, rows
+        ));
+    }
+
+    let count = columns
+        .checked_mul(rows)
+        .ok_or("Too many glyphs for the grid")?;
+    let mut codepoints = (0..count).map(|i| {
+        start_codepoint
+            .checked_add(i)
+            .and_then(char::from_u32)
+            .ok_or_else(|| {
+                format!(
+                    "{} glyphs starting at U+{:04X} run into invalid codepoints",
+                    count, start_codepoint
+                )
+            })
+    });
+    let mut chars = Vec::with_capacity(rows as usize);
+    for _ in 0..rows {
+        let row = codepoints
+            .by_ref()
+            .take(columns as usize)
+            .collect::<Result<String, String>>()?;
+        chars.push(row);
+    }
+
+    Ok(FontProviderEntry {
+        provider: FontProvider::Bitmap {
+            file,
+            height,
+            ascent,
+            chars,
+        },
+        filter: None,
+    })
+}
@@ -9,6 +9,25 @@ pub struct HistoryEntry {
     pub timestamp: String,
     pub content: String,
     pub file_type: String,
+    // 记录来源，旧的历史记录没有该字段，视为编辑器保存
+    #[serde(default)]
+    pub source: HistorySource,
+}
+
+// 历史记录的来源：编辑器保存的图片只包含绘制图层，不能直接写回文件
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HistorySource {
+    #[default]
+    Editor,
+    Snapshot,
+}
+
+impl HistoryEntry {
+    // 是否为完整的文件内容，可以写回文件
+    pub fn is_restorable(&self) -> bool {
+        self.file_type != "image" || self.source == HistorySource::Snapshot
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,6 +36,9 @@ pub struct HistoryMetadata {
     pub max_history_per_file: u32,
     pub files: HashMap<String, FileHistoryInfo>,
     pub total_size: u64,
+    // .history 目录实际占用的磁盘空间，只在 get_history_stats 中统计
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_size: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -24,6 +46,89 @@ pub struct FileHistoryInfo {
     pub history_count: u32,
     pub last_modified: String,
     pub size: u64,
+    // 撤销后所在的历史版本序号，None 表示位于最新版本
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<u32>,
+}
+
+// 文本差异中一行的操作
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub line: String,
+}
+
+// 图片历史版本的信息，无法解码时宽高为 None
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImageVersionInfo {
+    pub index: u32,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub size: u64,
+    pub sha1: String,
+}
+
+// 两个历史版本的差异：文本按行比较，图片比较尺寸、大小和 SHA1
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum HistoryDiff {
+    Text { lines: Vec<DiffLine> },
+    Image { a: ImageVersionInfo, b: ImageVersionInfo },
+}
+
+// 历史版本时间线中的一项
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryVersion {
+    pub index: u32,
+    pub timestamp: String,
+    pub file_type: String,
+    pub size: u64,
+}
+
+// 文件的历史版本时间线及撤销位置
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryVersions {
+    pub versions: Vec<HistoryVersion>,
+    pub position: Option<u32>,
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
+// 前端设置中的历史记录选项，用于后端自动保存的快照
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct HistorySettings {
+    pub enabled: bool,
+    pub max_count: u32,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_count: crate::constants::DEFAULT_MAX_HISTORY_COUNT,
+        }
+    }
+}
+
+// 同步前端的历史记录设置（是否启用、每个文件保留的数量）
+#[command]
+pub async fn set_history_settings(
+    enabled: bool,
+    max_count: u32,
+    state: tauri::State<'_, crate::commands::AppState>,
+) -> Result<(), String> {
+    *state.history_settings.lock() = HistorySettings {
+        enabled,
+        max_count: max_count.max(1),
+    };
+    Ok(())
 }
 
 // 获取.history文件夹路径
@@ -45,22 +150,86 @@ fn get_file_history_dir(pack_dir: &Path, file_path: &str) -> Result<PathBuf, Str
     Ok(file_history_path)
 }
 
-// 保存文件历史记录
+// 保存文件历史记录，未指定 max_count 时按历史记录设置中的数量保留
 #[command]
 pub async fn save_file_history(
     pack_dir: String,
     file_path: String,
     content: String,
     file_type: String,
-    max_count: u32,
+    max_count: Option<u32>,
+    state: tauri::State<'_, crate::commands::AppState>,
 ) -> Result<String, String> {
-    let pack_path = Path::new(&pack_dir);
-    let file_history_dir = get_file_history_dir(pack_path, &file_path)?;
+    let max_count = max_count.unwrap_or(state.history_settings.lock().max_count).max(1);
+    record_history(
+        Path::new(&pack_dir),
+        &file_path,
+        content,
+        file_type,
+        HistorySource::Editor,
+        max_count,
+    )?;
+    Ok("历史记录保存成功".to_string())
+}
+
+// 读取图片并编码为 data URL，格式与前端保存的历史记录一致
+fn image_data_url(full_path: &Path) -> Result<String, String> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let data = fs::read(full_path)
+        .map_err(|e| format!("读取图片失败: {}", e))?;
+    let mime = match image::ImageFormat::from_path(full_path) {
+        Ok(format) => format.to_mime_type(),
+        Err(_) => "image/png",
+    };
+    Ok(format!("data:{};base64,{}", mime, general_purpose::STANDARD.encode(&data)))
+}
+
+// 将图片当前内容保存为历史记录，格式与前端保存的 data URL 一致；历史记录关闭时不保存
+pub fn snapshot_image(pack_path: &Path, file_path: &str, settings: HistorySettings) -> Result<(), String> {
+    if !settings.enabled {
+        return Ok(());
+    }
+    let content = image_data_url(&pack_path.join(file_path))?;
+
+    record_history(
+        pack_path,
+        file_path,
+        content,
+        "image".to_string(),
+        HistorySource::Snapshot,
+        settings.max_count,
+    )
+}
+
+// 写入一条历史记录，超出 max_count 时删除最旧的记录
+pub fn record_history(
+    pack_path: &Path,
+    file_path: &str,
+    content: String,
+    file_type: String,
+    source: HistorySource,
+    max_count: u32,
+) -> Result<(), String> {
+    let max_count = max_count.max(1);
+    let file_history_dir = get_file_history_dir(pack_path, file_path)?;
     
     // 创建历史记录目录
     fs::create_dir_all(&file_history_dir)
         .map_err(|e| format!("创建历史记录目录失败: {}", e))?;
     
+    // 撤销后再次编辑时丢弃可重做的版本
+    if let Some(position) = load_metadata(pack_path)?
+        .files
+        .get(file_path)
+        .and_then(|info| info.position)
+    {
+        for path in version_files(&file_history_dir)?.iter().skip(position as usize + 1) {
+            fs::remove_file(path)
+                .map_err(|e| format!("删除可重做的历史记录失败: {}", e))?;
+        }
+    }
+    
     let mut files: Vec<_> = fs::read_dir(&file_history_dir)
         .map_err(|e| format!("读取历史记录目录失败: {}", e))?
         .filter_map(|e| e.ok())
@@ -68,11 +237,11 @@ pub async fn save_file_history(
     
     files.sort_by_key(|f| f.file_name());
     
-    if files.len() as u32 >= max_count {
-        if let Some(oldest) = files.first() {
-            fs::remove_file(oldest.path())
-                .map_err(|e| format!("删除旧历史记录失败: {}", e))?;
-        }
+    // 为新版本留出位置，上限调低后一次删除所有多出的旧记录
+    let excess = (files.len() + 1).saturating_sub(max_count as usize);
+    for oldest in files.drain(..excess) {
+        fs::remove_file(oldest.path())
+            .map_err(|e| format!("删除旧历史记录失败: {}", e))?;
     }
     
     let timestamp = chrono::Utc::now();
@@ -82,6 +251,7 @@ pub async fn save_file_history(
         timestamp: timestamp_str.clone(),
         content,
         file_type,
+        source,
     };
     
     let history_file = file_history_dir.join(format!("{}.json", file_name));
@@ -93,9 +263,9 @@ pub async fn save_file_history(
     
     // 更新元数据
     let count = files.len() as u32;
-    update_metadata(pack_path, &file_path, count + 1, &timestamp_str)?;
+    update_metadata(pack_path, file_path, count + 1, &timestamp_str, max_count)?;
     
-    Ok("历史记录保存成功".to_string())
+    Ok(())
 }
 
 // 加载文件历史记录
@@ -134,26 +304,59 @@ pub async fn load_file_history(
     Ok(entries)
 }
 
+// 撤销文件到上一个历史版本
+#[command]
+pub async fn undo_file(
+    pack_dir: String,
+    file_path: String,
+    max_count: Option<u32>,
+    state: tauri::State<'_, crate::commands::AppState>,
+) -> Result<HistoryEntry, String> {
+    crate::commands::ensure_path_writable(&state, Path::new(&pack_dir))?;
+    let max_count = max_count.unwrap_or(state.history_settings.lock().max_count);
+    tokio::task::spawn_blocking(move || undo_file_blocking(Path::new(&pack_dir), &file_path, max_count))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+// 重做文件到下一个历史版本
+#[command]
+pub async fn redo_file(
+    pack_dir: String,
+    file_path: String,
+    state: tauri::State<'_, crate::commands::AppState>,
+) -> Result<HistoryEntry, String> {
+    crate::commands::ensure_path_writable(&state, Path::new(&pack_dir))?;
+    tokio::task::spawn_blocking(move || redo_file_blocking(Path::new(&pack_dir), &file_path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+// 获取文件的历史版本时间线
+#[command]
+pub async fn get_history_versions(pack_dir: String, file_path: String) -> Result<HistoryVersions, String> {
+    history_versions(Path::new(&pack_dir), &file_path)
+}
+
+// 比较文件的两个历史版本（序号来自 get_history_versions）
+#[command]
+pub async fn diff_file_history(
+    pack_dir: String,
+    file_path: String,
+    version_a: u32,
+    version_b: u32,
+) -> Result<HistoryDiff, String> {
+    tokio::task::spawn_blocking(move || diff_versions(Path::new(&pack_dir), &file_path, version_a, version_b))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
 // 获取历史记录统计信息
 #[command]
 pub async fn get_history_stats(pack_dir: String) -> Result<HistoryMetadata, String> {
     let pack_path = Path::new(&pack_dir);
-    let meta_file = get_history_dir(pack_path).join("history_meta.json");
-    
-    if !meta_file.exists() {
-        return Ok(HistoryMetadata {
-            version: "1.0".to_string(),
-            max_history_per_file: 30,
-            files: HashMap::new(),
-            total_size: 0,
-        });
-    }
-    
-    let content = fs::read_to_string(&meta_file)
-        .map_err(|e| format!("读取元数据失败: {}", e))?;
-    let metadata: HistoryMetadata = serde_json::from_str(&content)
-        .map_err(|e| format!("解析元数据失败: {}", e))?;
-    
+    let mut metadata = load_metadata(pack_path)?;
+    metadata.disk_size = Some(history_dir_size(pack_path));
     Ok(metadata)
 }
 
@@ -171,18 +374,210 @@ pub async fn clear_file_history(pack_dir: String, file_path: String) -> Result<S
     Ok("历史记录已清理".to_string())
 }
 
-// 清理所有历史记录
+// 清理历史记录的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryClearResult {
+    pub dry_run: bool,
+    pub files: Vec<String>,
+    pub files_removed: usize,
+    pub bytes_removed: u64,
+}
+
+// 清理所有历史记录，与其他批量命令一样默认只预览（dry_run 默认为 true），只返回将被删除的文件
 #[command]
-pub async fn clear_all_history(pack_dir: String) -> Result<String, String> {
+pub async fn clear_all_history(
+    pack_dir: String,
+    dry_run: Option<bool>,
+    state: tauri::State<'_, crate::commands::AppState>,
+) -> Result<HistoryClearResult, String> {
+    let dry_run = dry_run.unwrap_or(true);
     let pack_path = Path::new(&pack_dir);
+    if !dry_run {
+        crate::commands::ensure_path_writable(&state, pack_path)?;
+    }
     let history_dir = get_history_dir(pack_path);
     
-    if history_dir.exists() {
+    let mut files = Vec::new();
+    let mut bytes_removed = 0u64;
+    for entry in walkdir::WalkDir::new(&history_dir).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        bytes_removed += entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if let Ok(relative) = entry.path().strip_prefix(&history_dir) {
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    files.sort();
+    
+    if !dry_run && history_dir.exists() {
         fs::remove_dir_all(&history_dir)
             .map_err(|e| format!("删除所有历史记录失败: {}", e))?;
     }
     
-    Ok("所有历史记录已清理".to_string())
+    Ok(HistoryClearResult {
+        dry_run,
+        files_removed: files.len(),
+        bytes_removed,
+        files,
+    })
+}
+
+// 历史记录版本文件的时间，优先使用文件名中的时间戳
+fn version_time(path: &Path) -> Option<chrono::DateTime<chrono::Utc>> {
+    let stem = path.file_stem()?.to_str()?;
+    chrono::NaiveDateTime::parse_from_str(stem, "%Y%m%d_%H%M%S_%3f")
+        .map(|t| t.and_utc())
+        .ok()
+        .or_else(|| {
+            let modified = path.metadata().and_then(|m| m.modified()).ok()?;
+            Some(chrono::DateTime::<chrono::Utc>::from(modified))
+        })
+}
+
+// 按保留策略删除旧的历史记录，先按文件版本数、再按时间、最后按总大小删除最旧的版本
+fn prune_history_blocking(
+    pack_path: &Path,
+    max_versions_per_file: Option<u32>,
+    max_age_days: Option<u32>,
+    max_total_bytes: Option<u64>,
+    dry_run: bool,
+) -> Result<HistoryClearResult, String> {
+    let history_dir = get_history_dir(pack_path);
+    
+    // (文件路径, 版本文件, 大小, 时间)
+    let mut versions: Vec<(String, PathBuf, u64, chrono::DateTime<chrono::Utc>)> = Vec::new();
+    for entry in walkdir::WalkDir::new(&history_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+    {
+        let file_path = entry
+            .path()
+            .strip_prefix(&history_dir)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        for path in version_files(entry.path())? {
+            let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+            let time = version_time(&path).unwrap_or_default();
+            versions.push((file_path.clone(), path, size, time));
+        }
+    }
+    versions.sort_by(|a, b| a.3.cmp(&b.3).then_with(|| a.1.cmp(&b.1)));
+    
+    let mut removed = vec![false; versions.len()];
+    if let Some(max_versions) = max_versions_per_file {
+        let mut remaining: HashMap<&str, usize> = HashMap::new();
+        for (file_path, ..) in &versions {
+            *remaining.entry(file_path.as_str()).or_default() += 1;
+        }
+        for (i, (file_path, ..)) in versions.iter().enumerate() {
+            let count = remaining.get_mut(file_path.as_str()).unwrap();
+            if *count > max_versions as usize {
+                removed[i] = true;
+                *count -= 1;
+            }
+        }
+    }
+    if let Some(days) = max_age_days {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(days as i64);
+        for (i, version) in versions.iter().enumerate() {
+            if version.3 < cutoff {
+                removed[i] = true;
+            }
+        }
+    }
+    if let Some(max_bytes) = max_total_bytes {
+        let mut total: u64 = versions
+            .iter()
+            .zip(&removed)
+            .filter(|(_, removed)| !**removed)
+            .map(|(version, _)| version.2)
+            .sum();
+        for (i, version) in versions.iter().enumerate() {
+            if total <= max_bytes {
+                break;
+            }
+            if !removed[i] {
+                removed[i] = true;
+                total -= version.2;
+            }
+        }
+    }
+    
+    let mut files = Vec::new();
+    let mut bytes_removed = 0u64;
+    let mut touched: Vec<&str> = Vec::new();
+    for (version, _) in versions.iter().zip(&removed).filter(|(_, removed)| **removed) {
+        let (file_path, path, size, _) = version;
+        bytes_removed += size;
+        if let Ok(relative) = path.strip_prefix(&history_dir) {
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+        }
+        if !touched.contains(&file_path.as_str()) {
+            touched.push(file_path);
+        }
+        if !dry_run {
+            fs::remove_file(path)
+                .map_err(|e| format!("删除旧历史记录失败: {}", e))?;
+        }
+    }
+    files.sort();
+    
+    if !dry_run && !touched.is_empty() {
+        // 删除了旧版本的文件撤销位置已失效，回到最新版本
+        let mut metadata = load_metadata(pack_path)?;
+        for file_path in touched {
+            let file_history_dir = get_file_history_dir(pack_path, file_path)?;
+            let remaining = version_files(&file_history_dir)?;
+            if remaining.is_empty() {
+                let _ = fs::remove_dir(&file_history_dir);
+                metadata.files.remove(file_path);
+            } else if let Some(info) = metadata.files.get_mut(file_path) {
+                info.history_count = remaining.len() as u32;
+                info.size = calculate_dir_size(&file_history_dir, false)?;
+                info.position = None;
+            }
+        }
+        metadata.total_size = metadata.files.values().map(|f| f.size).sum();
+        write_metadata(pack_path, &metadata)?;
+    }
+    
+    Ok(HistoryClearResult {
+        dry_run,
+        files_removed: files.len(),
+        bytes_removed,
+        files,
+    })
+}
+
+// 按保留策略清理历史记录，返回删除的文件和释放的空间，默认只预览（dry_run 默认为 true）
+#[command]
+pub async fn prune_history(
+    pack_dir: String,
+    max_versions_per_file: Option<u32>,
+    max_age_days: Option<u32>,
+    max_total_bytes: Option<u64>,
+    dry_run: Option<bool>,
+    state: tauri::State<'_, crate::commands::AppState>,
+) -> Result<HistoryClearResult, String> {
+    let dry_run = dry_run.unwrap_or(true);
+    if !dry_run {
+        crate::commands::ensure_path_writable(&state, Path::new(&pack_dir))?;
+    }
+    tokio::task::spawn_blocking(move || {
+        prune_history_blocking(
+            Path::new(&pack_dir),
+            max_versions_per_file,
+            max_age_days,
+            max_total_bytes,
+            dry_run,
+        )
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
 }
 
 // 获取材质包大小
@@ -198,22 +593,10 @@ fn update_metadata(
     file_path: &str,
     count: u32,
     timestamp: &str,
+    max_count: u32,
 ) -> Result<(), String> {
-    let meta_file = get_history_dir(pack_path).join("history_meta.json");
-    
-    let mut metadata = if meta_file.exists() {
-        let content = fs::read_to_string(&meta_file)
-            .map_err(|e| format!("读取元数据失败: {}", e))?;
-        serde_json::from_str(&content)
-            .map_err(|e| format!("解析元数据失败: {}", e))?
-    } else {
-        HistoryMetadata {
-            version: "1.0".to_string(),
-            max_history_per_file: 30,
-            files: HashMap::new(),
-            total_size: 0,
-        }
-    };
+    let mut metadata = load_metadata(pack_path)?;
+    metadata.max_history_per_file = max_count;
     
     let file_history_dir = get_file_history_dir(pack_path, file_path)?;
     let size = calculate_dir_size(&file_history_dir, false)?;
@@ -224,21 +607,667 @@ fn update_metadata(
             history_count: count,
             last_modified: timestamp.to_string(),
             size,
+            position: None,
         },
     );
     
     // 重新计算总大小
     metadata.total_size = metadata.files.values().map(|f| f.size).sum();
     
-    let json = serde_json::to_string_pretty(&metadata)
+    write_metadata(pack_path, &metadata)
+}
+
+// 读取元数据，不存在时返回空元数据
+fn load_metadata(pack_path: &Path) -> Result<HistoryMetadata, String> {
+    let meta_file = get_history_dir(pack_path).join("history_meta.json");
+    
+    if !meta_file.exists() {
+        return Ok(HistoryMetadata {
+            version: "1.0".to_string(),
+            max_history_per_file: crate::constants::DEFAULT_MAX_HISTORY_COUNT,
+            files: HashMap::new(),
+            total_size: 0,
+            disk_size: None,
+        });
+    }
+    
+    let content = fs::read_to_string(&meta_file)
+        .map_err(|e| format!("读取元数据失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析元数据失败: {}", e))
+}
+
+fn write_metadata(pack_path: &Path, metadata: &HistoryMetadata) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(metadata)
         .map_err(|e| format!("序列化元数据失败: {}", e))?;
     
-    fs::write(&meta_file, json)
-        .map_err(|e| format!("写入元数据失败: {}", e))?;
+    fs::write(get_history_dir(pack_path).join("history_meta.json"), json)
+        .map_err(|e| format!("写入元数据失败: {}", e))
+}
+
+// 按时间顺序排列的历史记录文件
+fn version_files(file_history_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    if !file_history_dir.exists() {
+        return Ok(Vec::new());
+    }
     
+    let mut files: Vec<PathBuf> = fs::read_dir(file_history_dir)
+        .map_err(|e| format!("读取历史记录目录失败: {}", e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().and_then(|s| s.to_str()) == Some("json"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn read_version(path: &Path) -> Result<HistoryEntry, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("读取历史记录文件失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析历史记录失败: {}", e))
+}
+
+// 文件的撤销位置，超出现有版本数（如历史记录被清理后）时视为位于最新版本
+fn file_position(pack_path: &Path, file_path: &str, count: usize) -> Result<Option<u32>, String> {
+    Ok(load_metadata(pack_path)?
+        .files
+        .get(file_path)
+        .and_then(|info| info.position)
+        .filter(|&p| (p as usize) < count))
+}
+
+// 读取文件的历史版本时间线
+fn history_versions(pack_path: &Path, file_path: &str) -> Result<HistoryVersions, String> {
+    let files = version_files(&get_file_history_dir(pack_path, file_path)?)?;
+    let position = file_position(pack_path, file_path, files.len())?;
+    
+    let mut versions = Vec::new();
+    for (index, path) in files.iter().enumerate() {
+        let entry = read_version(path)?;
+        versions.push(HistoryVersion {
+            index: index as u32,
+            timestamp: entry.timestamp,
+            file_type: entry.file_type,
+            size: path.metadata().map(|m| m.len()).unwrap_or(0),
+        });
+    }
+    
+    let current = position.map(|p| p as usize).unwrap_or(files.len().saturating_sub(1));
+    Ok(HistoryVersions {
+        can_undo: current > 0,
+        can_redo: position.is_some() && current + 1 < files.len(),
+        versions,
+        position,
+    })
+}
+
+// 解码图片历史记录并计算尺寸、大小和 SHA1
+fn image_version_info(index: u32, entry: &HistoryEntry) -> Result<ImageVersionInfo, String> {
+    use base64::{engine::general_purpose, Engine as _};
+    use sha1::{Digest, Sha1};
+
+    let data = entry
+        .content
+        .split_once(',')
+        .map(|(_, data)| data)
+        .unwrap_or(&entry.content);
+    let bytes = general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| format!("解码图片历史记录失败: {}", e))?;
+    let dimensions = image::load_from_memory(&bytes)
+        .ok()
+        .map(|img| (img.width(), img.height()));
+
+    Ok(ImageVersionInfo {
+        index,
+        width: dimensions.map(|(w, _)| w),
+        height: dimensions.map(|(_, h)| h),
+        size: bytes.len() as u64,
+        sha1: hex::encode(Sha1::digest(&bytes)),
+    })
+}
+
+fn diff_versions(pack_path: &Path, file_path: &str, version_a: u32, version_b: u32) -> Result<HistoryDiff, String> {
+    let files = version_files(&get_file_history_dir(pack_path, file_path)?)?;
+    let read = |index: u32| {
+        files
+            .get(index as usize)
+            .ok_or_else(|| format!("历史版本不存在: {}", index))
+            .and_then(|path| read_version(path))
+    };
+    let a = read(version_a)?;
+    let b = read(version_b)?;
+
+    if a.file_type == "image" || b.file_type == "image" {
+        return Ok(HistoryDiff::Image {
+            a: image_version_info(version_a, &a)?,
+            b: image_version_info(version_b, &b)?,
+        });
+    }
+
+    let lines = similar::TextDiff::from_lines(&a.content, &b.content)
+        .iter_all_changes()
+        .map(|change| DiffLine {
+            op: match change.tag() {
+                similar::ChangeTag::Equal => DiffOp::Equal,
+                similar::ChangeTag::Insert => DiffOp::Insert,
+                similar::ChangeTag::Delete => DiffOp::Delete,
+            },
+            line: change.value().trim_end_matches(['\r', '\n']).to_string(),
+        })
+        .collect();
+    Ok(HistoryDiff::Text { lines })
+}
+
+// 读取文件当前内容，编码方式与历史记录一致
+fn current_content(full_path: &Path, file_type: &str) -> Result<String, String> {
+    if file_type == "image" {
+        image_data_url(full_path)
+    } else {
+        fs::read_to_string(full_path)
+            .map_err(|e| format!("读取文件失败: {}", e))
+    }
+}
+
+// 将历史记录内容写回文件，图片内容为 data URL，按文件扩展名保持原格式
+fn restore_content(full_path: &Path, entry: &HistoryEntry) -> Result<(), String> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    if !entry.is_restorable() {
+        return Err("该历史记录只包含绘制图层，无法恢复到文件".to_string());
+    }
+    if entry.file_type == "image" {
+        let data = entry
+            .content
+            .split_once(',')
+            .map(|(_, data)| data)
+            .unwrap_or(&entry.content);
+        let bytes = general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| format!("解码图片历史记录失败: {}", e))?;
+        crate::image_handler::save_image_bytes(full_path, &bytes)
+            .map_err(|e| format!("恢复文件失败: {}", e))?;
+    } else {
+        fs::write(full_path, &entry.content)
+            .map_err(|e| format!("恢复文件失败: {}", e))?;
+    }
+    notify_file_changed(full_path);
     Ok(())
 }
 
+fn set_position(pack_path: &Path, file_path: &str, position: Option<u32>) -> Result<(), String> {
+    let mut metadata = load_metadata(pack_path)?;
+    if let Some(info) = metadata.files.get_mut(file_path) {
+        info.position = position;
+    }
+    write_metadata(pack_path, &metadata)
+}
+
+// 撤销到上一个历史版本并写回文件，返回该版本
+//
+// 位于最新版本且文件内容与最近一个完整版本不同时（如图片编辑前只保存了修改前的快照），
+// 先把当前内容记录为新快照，以便之后可以重做回来。只包含绘制图层的图片记录会被跳过
+fn undo_file_blocking(pack_path: &Path, file_path: &str, max_count: u32) -> Result<HistoryEntry, String> {
+    let full_path = crate::path_security::resolve_safe_path(file_path, Some(&pack_path.to_path_buf()))?;
+    let file_history_dir = get_file_history_dir(pack_path, file_path)?;
+    let mut files = version_files(&file_history_dir)?;
+    let position = file_position(pack_path, file_path, files.len())?;
+    let current = match position {
+        Some(position) => position as usize,
+        None => {
+            let (index, latest) = last_restorable(&files, files.len())?.ok_or("没有可撤销的历史记录")?;
+            let current = current_content(&full_path, &latest.file_type)?;
+            if current == latest.content {
+                index
+            } else {
+                record_history(
+                    pack_path,
+                    file_path,
+                    current,
+                    latest.file_type,
+                    HistorySource::Snapshot,
+                    max_count,
+                )?;
+                files = version_files(&file_history_dir)?;
+                files.len() - 1
+            }
+        }
+    };
+    
+    let (target, entry) = last_restorable(&files, current)?.ok_or("没有更早的历史版本")?;
+    restore_content(&full_path, &entry)?;
+    set_position(pack_path, file_path, Some(target as u32))?;
+    Ok(entry)
+}
+
+// 在 files[..end] 中查找最近一个可以写回文件的历史记录
+fn last_restorable(files: &[PathBuf], end: usize) -> Result<Option<(usize, HistoryEntry)>, String> {
+    for index in (0..end.min(files.len())).rev() {
+        let entry = read_version(&files[index])?;
+        if entry.is_restorable() {
+            return Ok(Some((index, entry)));
+        }
+    }
+    Ok(None)
+}
+
+// 重做到下一个历史版本并写回文件，返回该版本
+fn redo_file_blocking(pack_path: &Path, file_path: &str) -> Result<HistoryEntry, String> {
+    let full_path = crate::path_security::resolve_safe_path(file_path, Some(&pack_path.to_path_buf()))?;
+    let files = version_files(&get_file_history_dir(pack_path, file_path)?)?;
+    let position = file_position(pack_path, file_path, files.len())?
+        .ok_or("没有可重做的历史版本")?;
+    
+    let mut next = None;
+    for index in position as usize + 1..files.len() {
+        let entry = read_version(&files[index])?;
+        if entry.is_restorable() {
+            next = Some((index, entry));
+            break;
+        }
+    }
+    let (target, entry) = next.ok_or("没有可重做的历史版本")?;
+    restore_content(&full_path, &entry)?;
+    // 回到最新版本后再编辑不需要截断
+    let position = (target + 1 < files.len()).then_some(target as u32);
+    set_position(pack_path, file_path, position)?;
+    Ok(entry)
+}
+
+// 按 .history 目录中的历史记录文件重新生成元数据
+pub fn rebuild_metadata(pack_path: &Path, max_count: u32) -> Result<(), String> {
+    let history_dir = get_history_dir(pack_path);
+    let mut files = HashMap::new();
+    
+    for entry in walkdir::WalkDir::new(&history_dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+    {
+        let entries: Vec<_> = fs::read_dir(entry.path())
+            .map_err(|e| format!("读取历史记录目录失败: {}", e))?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_file() && e.path().extension().and_then(|s| s.to_str()) == Some("json"))
+            .collect();
+        if entries.is_empty() {
+            continue;
+        }
+        
+        let last_modified = entries
+            .iter()
+            .filter_map(|e| e.metadata().and_then(|m| m.modified()).ok())
+            .max()
+            .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+            .unwrap_or_default();
+        let file_path = entry
+            .path()
+            .strip_prefix(&history_dir)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        
+        files.insert(
+            file_path,
+            FileHistoryInfo {
+                history_count: entries.len() as u32,
+                last_modified,
+                size: calculate_dir_size(entry.path(), false)?,
+                position: None,
+            },
+        );
+    }
+    
+    let metadata = HistoryMetadata {
+        version: "1.0".to_string(),
+        max_history_per_file: max_count,
+        total_size: files.values().map(|f| f.size).sum(),
+        files,
+        disk_size: None,
+    };
+    
+    let json = serde_json::to_string_pretty(&metadata)
+        .map_err(|e| format!("序列化元数据失败: {}", e))?;
+    fs::write(history_dir.join("history_meta.json"), json)
+        .map_err(|e| format!("写入元数据失败: {}", e))
+}
+
+// 操作日志中单个路径的变化，before/after 为 .little100/operations 下的备份路径，None 表示不存在
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OperationChange {
+    File {
+        path: String,
+        before: Option<String>,
+        after: Option<String>,
+        // 修改前后路径内容的哈希，撤销或重做前用于确认文件没有在操作之外被修改
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        before_hash: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        after_hash: Option<String>,
+    },
+    Rename {
+        from: String,
+        to: String,
+    },
+}
+
+// 一次修改材质包的操作
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Operation {
+    pub id: String,
+    pub timestamp: String,
+    pub description: String,
+    pub changes: Vec<OperationChange>,
+}
+
+// 整个材质包的操作日志，position 之后的操作可以重做
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OperationLog {
+    pub entries: Vec<Operation>,
+    pub position: usize,
+}
+
+// 已备份修改前状态、等待提交的操作
+pub struct PendingOperation {
+    id: String,
+    description: String,
+    befores: Vec<(String, Option<String>, String)>,
+}
+
+// 同一时间只允许一个操作读写日志
+static OPERATION_LOCK: parking_lot::Mutex<()> = parking_lot::Mutex::new(());
+
+fn operations_dir(pack_path: &Path) -> PathBuf {
+    pack_path.join(".little100").join("operations")
+}
+
+fn operation_log_file(pack_path: &Path) -> PathBuf {
+    pack_path.join(".little100").join("operations.json")
+}
+
+impl OperationLog {
+    pub fn load(pack_path: &Path) -> Result<Self, String> {
+        let file = operation_log_file(pack_path);
+        if !file.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&file)
+            .map_err(|e| format!("读取操作日志失败: {}", e))?;
+        let mut log: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("解析操作日志失败: {}", e))?;
+        log.position = log.position.min(log.entries.len());
+        Ok(log)
+    }
+
+    fn save(&self, pack_path: &Path) -> Result<(), String> {
+        let file = operation_log_file(pack_path);
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("创建操作日志目录失败: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("序列化操作日志失败: {}", e))?;
+        fs::write(&file, json)
+            .map_err(|e| format!("写入操作日志失败: {}", e))
+    }
+
+    // 追加操作，丢弃可重做的操作并限制日志长度，删除不再被引用的备份
+    fn push(&mut self, pack_path: &Path, operation: Operation) -> Result<(), String> {
+        self.entries.truncate(self.position);
+        self.entries.push(operation);
+        let overflow = self.entries.len().saturating_sub(crate::constants::MAX_OPERATION_LOG_SIZE);
+        self.entries.drain(..overflow);
+        self.position = self.entries.len();
+        self.save(pack_path)?;
+
+        // 也会清理操作失败时遗留的备份
+        if let Ok(dirs) = fs::read_dir(operations_dir(pack_path)) {
+            for dir in dirs.filter_map(|e| e.ok()) {
+                let name = dir.file_name().to_string_lossy().to_string();
+                if !self.entries.iter().any(|op| op.id == name) {
+                    let _ = fs::remove_dir_all(dir.path());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// 日志中的路径不记录编辑器自己的目录
+fn is_editor_path(relative: &str) -> bool {
+    crate::zip_handler::is_export_excluded(Path::new(relative))
+}
+
+fn remove_path(path: &Path) -> Result<(), String> {
+    if path.is_dir() {
+        fs::remove_dir_all(path).map_err(|e| format!("删除目录失败: {}", e))
+    } else if path.exists() {
+        fs::remove_file(path).map_err(|e| format!("删除文件失败: {}", e))
+    } else {
+        Ok(())
+    }
+}
+
+// 复制文件或整个目录
+fn copy_path(source: &Path, target: &Path) -> Result<(), String> {
+    for entry in walkdir::WalkDir::new(source).into_iter().filter_map(|e| e.ok()) {
+        let relative = entry.path().strip_prefix(source).unwrap_or(entry.path());
+        let dest = if relative.as_os_str().is_empty() {
+            target.to_path_buf()
+        } else {
+            target.join(relative)
+        };
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest)
+                .map_err(|e| format!("创建目录失败: {}", e))?;
+        } else {
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("创建目录失败: {}", e))?;
+            }
+            fs::copy(entry.path(), &dest)
+                .map_err(|e| format!("复制文件失败: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+// 将路径当前的状态备份到操作目录，路径不存在时返回 None
+fn backup_state(pack_path: &Path, id: &str, stage: &str, relative: &str) -> Result<Option<String>, String> {
+    let source = pack_path.join(relative);
+    if !source.exists() {
+        return Ok(None);
+    }
+    let backup = format!("{}/{}/{}", id, stage, relative);
+    copy_path(&source, &operations_dir(pack_path).join(&backup))?;
+    Ok(Some(backup))
+}
+
+// 路径当前内容的哈希，目录按相对路径顺序计算所有文件，路径不存在时为空字符串
+fn state_hash(path: &Path) -> Result<String, String> {
+    use sha1::{Digest, Sha1};
+
+    if !path.exists() {
+        return Ok(String::new());
+    }
+    let mut hasher = Sha1::new();
+    for entry in walkdir::WalkDir::new(path)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let relative = entry.path().strip_prefix(path).unwrap_or(entry.path());
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        hasher.update(fs::read(entry.path()).map_err(|e| format!("读取文件失败: {}", e))?);
+        hasher.update([0]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+// 确认操作涉及的路径仍是 expected 状态，否则拒绝撤销或重做，避免覆盖之后的修改
+fn ensure_unchanged(pack_path: &Path, operation: &Operation, undo: bool) -> Result<(), String> {
+    let action = if undo { "撤销" } else { "重做" };
+    for change in &operation.changes {
+        match change {
+            OperationChange::File { path, before_hash, after_hash, .. } => {
+                let expected = if undo { after_hash } else { before_hash };
+                if let Some(expected) = expected {
+                    if state_hash(&pack_path.join(path))? != *expected {
+                        return Err(format!("{} 在该操作之后已被修改，无法{}", path, action));
+                    }
+                }
+            }
+            OperationChange::Rename { from, to } => {
+                let (source, target) = if undo { (to, from) } else { (from, to) };
+                if !pack_path.join(source).exists() || pack_path.join(target).exists() {
+                    return Err(format!("{} 在该操作之后已被修改，无法{}", source, action));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// 通知网页预览文件已被修改
+pub fn notify_file_changed(path: &Path) {
+    #[cfg(feature = "web-server")]
+    crate::web_server::notify_file_changed(path);
+    #[cfg(not(feature = "web-server"))]
+    let _ = path;
+}
+
+// 将路径恢复为备份中的状态
+fn apply_state(pack_path: &Path, relative: &str, backup: Option<&str>) -> Result<(), String> {
+    let target = pack_path.join(relative);
+    remove_path(&target)?;
+    if let Some(backup) = backup {
+        copy_path(&operations_dir(pack_path).join(backup), &target)?;
+    }
+    crate::image_handler::invalidate_image_caches(&target);
+    notify_file_changed(&target);
+    Ok(())
+}
+
+// 在修改前备份 paths（相对材质包根目录）的当前状态
+pub fn begin_operation(pack_path: &Path, description: String, paths: &[String]) -> Result<PendingOperation, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let mut befores = Vec::new();
+    for path in paths.iter().filter(|p| !is_editor_path(p)) {
+        let hash = state_hash(&pack_path.join(path))?;
+        befores.push((path.clone(), backup_state(pack_path, &id, "before", path)?, hash));
+    }
+    Ok(PendingOperation { id, description, befores })
+}
+
+// 修改完成后备份新状态并写入操作日志
+pub fn commit_operation(pack_path: &Path, pending: PendingOperation) -> Result<(), String> {
+    if pending.befores.is_empty() {
+        return Ok(());
+    }
+    let mut changes = Vec::new();
+    for (path, before, before_hash) in pending.befores {
+        notify_file_changed(&pack_path.join(&path));
+        let after = backup_state(pack_path, &pending.id, "after", &path)?;
+        let after_hash = state_hash(&pack_path.join(&path))?;
+        changes.push(OperationChange::File {
+            path,
+            before,
+            after,
+            before_hash: Some(before_hash),
+            after_hash: Some(after_hash),
+        });
+    }
+
+    let _guard = OPERATION_LOCK.lock();
+    let mut log = OperationLog::load(pack_path)?;
+    log.push(pack_path, Operation {
+        id: pending.id,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        description: pending.description,
+        changes,
+    })
+}
+
+// 记录重命名，撤销时改回原名，不需要备份文件内容
+pub fn record_rename(pack_path: &Path, description: String, from: &str, to: &str) -> Result<(), String> {
+    if is_editor_path(from) || is_editor_path(to) {
+        return Ok(());
+    }
+    notify_file_changed(&pack_path.join(from));
+    notify_file_changed(&pack_path.join(to));
+    let _guard = OPERATION_LOCK.lock();
+    let mut log = OperationLog::load(pack_path)?;
+    log.push(pack_path, Operation {
+        id: uuid::Uuid::new_v4().to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        description,
+        changes: vec![OperationChange::Rename {
+            from: from.to_string(),
+            to: to.to_string(),
+        }],
+    })
+}
+
+// 撤销最近一次操作，返回其描述
+pub fn undo_last_operation(pack_path: &Path) -> Result<String, String> {
+    let _guard = OPERATION_LOCK.lock();
+    let mut log = OperationLog::load(pack_path)?;
+    if log.position == 0 {
+        return Err("没有可撤销的操作".to_string());
+    }
+
+    let operation = log.entries[log.position - 1].clone();
+    ensure_unchanged(pack_path, &operation, true)?;
+    for change in operation.changes.iter().rev() {
+        match change {
+            OperationChange::File { path, before, .. } => {
+                apply_state(pack_path, path, before.as_deref())?
+            }
+            OperationChange::Rename { from, to } => fs::rename(pack_path.join(to), pack_path.join(from))
+                .map_err(|e| format!("撤销重命名失败: {}", e))?,
+        }
+    }
+
+    log.position -= 1;
+    log.save(pack_path)?;
+    Ok(operation.description)
+}
+
+// 重做最近一次撤销的操作，返回其描述
+pub fn redo_last_operation(pack_path: &Path) -> Result<String, String> {
+    let _guard = OPERATION_LOCK.lock();
+    let mut log = OperationLog::load(pack_path)?;
+    let operation = log
+        .entries
+        .get(log.position)
+        .cloned()
+        .ok_or("没有可重做的操作")?;
+
+    ensure_unchanged(pack_path, &operation, false)?;
+    for change in &operation.changes {
+        match change {
+            OperationChange::File { path, after, .. } => {
+                apply_state(pack_path, path, after.as_deref())?
+            }
+            OperationChange::Rename { from, to } => fs::rename(pack_path.join(from), pack_path.join(to))
+                .map_err(|e| format!("重做重命名失败: {}", e))?,
+        }
+    }
+
+    log.position += 1;
+    log.save(pack_path)?;
+    Ok(operation.description)
+}
+
+// 获取历史记录总大小
+pub fn history_dir_size(pack_dir: &Path) -> u64 {
+    calculate_dir_size(&get_history_dir(pack_dir), false).unwrap_or(0)
+}
+
 // 计算目录大小
 fn calculate_dir_size(path: &Path, exclude_history: bool) -> Result<u64, String> {
     let mut total_size = 0u64;
@@ -281,4 +1310,94 @@ fn calculate_dir_size(path: &Path, exclude_history: bool) -> Result<u64, String>
     }
     
     Ok(total_size)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_pack(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("little100_oplog_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // 记录一次写入 relative 的操作
+    fn write_with_log(pack: &Path, relative: &str, content: &str) {
+        let pending = begin_operation(pack, format!("Write {}", content), &[relative.to_string()]).unwrap();
+        fs::write(pack.join(relative), content).unwrap();
+        commit_operation(pack, pending).unwrap();
+    }
+
+    #[test]
+    fn test_undo_and_redo_restore_file_states() {
+        let pack = temp_pack("undo_redo");
+        fs::write(pack.join("a.json"), "old").unwrap();
+        write_with_log(&pack, "a.json", "new");
+
+        assert_eq!(undo_last_operation(&pack).unwrap(), "Write new");
+        let undone = fs::read_to_string(pack.join("a.json")).unwrap();
+        assert_eq!(redo_last_operation(&pack).unwrap(), "Write new");
+        let redone = fs::read_to_string(pack.join("a.json")).unwrap();
+        let nothing_to_redo = redo_last_operation(&pack);
+        let _ = fs::remove_dir_all(&pack);
+
+        assert_eq!(undone, "old");
+        assert_eq!(redone, "new");
+        assert!(nothing_to_redo.is_err());
+    }
+
+    #[test]
+    fn test_undo_refuses_files_changed_outside_the_log() {
+        let pack = temp_pack("changed");
+        fs::write(pack.join("a.json"), "old").unwrap();
+        write_with_log(&pack, "a.json", "new");
+        fs::write(pack.join("a.json"), "edited elsewhere").unwrap();
+
+        let result = undo_last_operation(&pack);
+        let content = fs::read_to_string(pack.join("a.json")).unwrap();
+        let position = OperationLog::load(&pack).unwrap().position;
+        let _ = fs::remove_dir_all(&pack);
+
+        assert!(result.is_err());
+        assert_eq!(content, "edited elsewhere");
+        assert_eq!(position, 1);
+    }
+
+    #[test]
+    fn test_undo_rename_moves_file_back() {
+        let pack = temp_pack("rename");
+        fs::write(pack.join("a.png"), "image").unwrap();
+        fs::rename(pack.join("a.png"), pack.join("b.png")).unwrap();
+        record_rename(&pack, "Rename a.png".to_string(), "a.png", "b.png").unwrap();
+
+        undo_last_operation(&pack).unwrap();
+        let undone = (pack.join("a.png").exists(), pack.join("b.png").exists());
+        redo_last_operation(&pack).unwrap();
+        let redone = (pack.join("a.png").exists(), pack.join("b.png").exists());
+        let _ = fs::remove_dir_all(&pack);
+
+        assert_eq!(undone, (true, false));
+        assert_eq!(redone, (false, true));
+    }
+
+    #[test]
+    fn test_new_operation_discards_redo_entries() {
+        let pack = temp_pack("truncate");
+        write_with_log(&pack, "a.json", "first");
+        write_with_log(&pack, "a.json", "second");
+        undo_last_operation(&pack).unwrap();
+        write_with_log(&pack, "a.json", "third");
+
+        let log = OperationLog::load(&pack).unwrap();
+        let redo = redo_last_operation(&pack);
+        let backups = fs::read_dir(operations_dir(&pack)).unwrap().count();
+        let _ = fs::remove_dir_all(&pack);
+
+        let descriptions: Vec<&str> = log.entries.iter().map(|op| op.description.as_str()).collect();
+        assert_eq!(descriptions, vec!["Write first", "Write third"]);
+        assert_eq!(log.position, 2);
+        assert!(redo.is_err());
+        assert_eq!(backups, 2);
+    }
+}
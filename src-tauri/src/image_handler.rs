@@ -9,6 +9,8 @@ use lru::LruCache;
 use std::num::NonZeroUsize;
 use std::sync::LazyLock;
 
+use crate::error::io_error;
+
 static THUMBNAIL_CACHE: LazyLock<Arc<RwLock<LruCache<String, String>>>> = LazyLock::new(|| {
     Arc::new(RwLock::new(LruCache::new(NonZeroUsize::new(crate::constants::THUMBNAIL_CACHE_SIZE).unwrap())))
 });
@@ -83,13 +85,29 @@ pub fn validate_texture_size(width: u32, height: u32) -> bool {
     (is_multiple_of_16(width) && is_multiple_of_16(height))
 }
 
-/// 创建缩略图（优化版本，带缓存）
+/// 文件修改时间（毫秒），无法读取时为 0
+fn mtime_millis(path: &Path) -> u128 {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis())
+        .unwrap_or(0)
+}
+
+/// 内存缩略图缓存的键：路径、尺寸和修改时间，文件在外部被修改后不会命中旧缓存
+///
+/// 以 `<路径>_` 开头，invalidate_image_caches 按前缀移除
+fn thumbnail_cache_key(path: &Path, max_size: u32) -> String {
+    format!("{}_{}_{}", path.to_string_lossy(), max_size, mtime_millis(path))
+}
+
+/// 创建缩略图（带缓存），缩小时使用最近邻采样以保持像素画清晰
 pub fn create_thumbnail(
     path: &Path,
     max_size: u32,
 ) -> Result<String, String> {
-    let path_str = path.to_string_lossy().to_string();
-    let cache_key = format!("{}_{}", path_str, max_size);
+    let cache_key = thumbnail_cache_key(path, max_size);
     
     // 检查缓存
     {
@@ -99,15 +117,20 @@ pub fn create_thumbnail(
         }
     }
     
-    let file = File::open(path)
-        .map_err(|e| format!("Failed to open image: {}", e))?;
-    let reader = BufReader::with_capacity(8192, file);
-    
-    let img = image::load(reader, image::ImageFormat::from_path(path)
-        .map_err(|e| format!("Failed to detect image format: {}", e))?)
-        .map_err(|e| format!("Failed to decode image: {}", e))?;
+    // 按内容识别格式（TGA 等无魔数的格式按扩展名），GIF 和 WebP 动图只解码第一帧
+    let img = image::ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .decode()
+        .map_err(|e| match e {
+            image::ImageError::Unsupported(e) => format!("Unsupported image: {}", e),
+            e => format!("Failed to decode image: {}", e),
+        })?;
     
     let (width, height) = (img.width(), img.height());
+    if width == 0 || height == 0 {
+        return Err("Image has no decodable frames".to_string());
+    }
     
     if width <= max_size && height <= max_size {
         let mut buffer = Vec::with_capacity((width * height * 4) as usize);
@@ -125,13 +148,7 @@ pub fn create_thumbnail(
     let new_width = (width as f32 * scale) as u32;
     let new_height = (height as f32 * scale) as u32;
     
-    let filter = if scale < 0.5 {
-        FilterType::Lanczos3
-    } else {
-        FilterType::Triangle
-    };
-    
-    let thumbnail = img.resize(new_width, new_height, filter);
+    let thumbnail = img.resize(new_width, new_height, FilterType::Nearest);
     
     // 预分配缓冲区
     let mut buffer = Vec::with_capacity((new_width * new_height * 4) as usize);
@@ -146,6 +163,88 @@ pub fn create_thumbnail(
     Ok(result)
 }
 
+/// 磁盘缩略图缓存目录
+pub fn thumbnail_cache_dir(pack_path: &Path) -> PathBuf {
+    pack_path.join(".little100").join("thumbs")
+}
+
+/// FNV-1a 哈希，用于生成跨会话稳定的缓存文件名
+fn fnv1a64(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in data {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// 创建缩略图，优先读取 .little100/thumbs 下的磁盘缓存
+///
+/// 缓存按相对路径哈希分目录，文件名包含尺寸和源文件修改时间，源文件变更后旧缓存会被替换；
+/// `write_cache` 为 false 时（如只读材质包）只读取已有缓存，不写入磁盘
+pub fn create_thumbnail_disk_cached(
+    pack_path: &Path,
+    path: &Path,
+    max_size: u32,
+    write_cache: bool,
+) -> Result<String, String> {
+    let cache_key = thumbnail_cache_key(path, max_size);
+    if let Some(cached) = THUMBNAIL_CACHE.read().peek(&cache_key) {
+        return Ok(cached.clone());
+    }
+
+    let relative = path
+        .strip_prefix(pack_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    let mtime = mtime_millis(path);
+
+    let cache_dir =
+        thumbnail_cache_dir(pack_path).join(format!("{:016x}", fnv1a64(relative.as_bytes())));
+    let prefix = format!("{}_", max_size);
+    let cache_file = cache_dir.join(format!("{}{}.png", prefix, mtime));
+
+    if let Ok(bytes) = std::fs::read(&cache_file) {
+        let result = general_purpose::STANDARD.encode(&bytes);
+        THUMBNAIL_CACHE.write().put(cache_key, result.clone());
+        return Ok(result);
+    }
+
+    let result = create_thumbnail(path, max_size)?;
+    if !write_cache {
+        THUMBNAIL_CACHE.write().put(cache_key, result.clone());
+        return Ok(result);
+    }
+
+    // 写入失败不影响返回结果
+    if std::fs::create_dir_all(&cache_dir).is_ok() {
+        if let Ok(entries) = std::fs::read_dir(&cache_dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                    let _ = std::fs::remove_file(entry.path());
+                }
+            }
+        }
+        if let Ok(bytes) = general_purpose::STANDARD.decode(&result) {
+            let _ = std::fs::write(&cache_file, bytes);
+        }
+    }
+
+    Ok(result)
+}
+
+/// 清除材质包的磁盘缩略图缓存
+pub fn clear_thumbnail_disk_cache(pack_path: &Path) -> Result<(), String> {
+    let cache_dir = thumbnail_cache_dir(pack_path);
+    if cache_dir.exists() {
+        std::fs::remove_dir_all(&cache_dir)
+            .map_err(|e| format!("Failed to clear thumbnail cache: {}", e))?;
+    }
+    THUMBNAIL_CACHE.write().clear();
+    Ok(())
+}
+
 /// 图片信息
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ImageInfo {
@@ -154,6 +253,106 @@ pub struct ImageInfo {
     pub format: String,
     pub size_bytes: u64,
     pub is_valid_texture: bool,
+    /// 按文件内容识别的格式
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detected_format: Option<String>,
+    /// 扩展名与实际内容不一致时的警告
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format_warning: Option<String>,
+    /// GIF 和 WebP 的帧数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_count: Option<u32>,
+}
+
+/// 扩展名与文件内容不一致的图片
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FormatMismatch {
+    pub path: String,
+    pub extension_format: String,
+    /// 无法识别（如文件损坏或被截断）时为 None
+    pub detected_format: Option<String>,
+    pub message: String,
+}
+
+/// 图片格式名称
+fn format_name(format: ImageFormat) -> String {
+    format!("{:?}", format).to_uppercase()
+}
+
+/// 读取文件头，按魔数识别图片格式
+pub fn sniff_image_format(path: &Path) -> Result<Option<ImageFormat>, String> {
+    use std::io::Read;
+
+    let mut file = File::open(path).map_err(|e| format!("Failed to open image: {}", e))?;
+    let mut header = [0u8; 32];
+    let read = file
+        .read(&mut header)
+        .map_err(|e| format!("Failed to read image: {}", e))?;
+    Ok(image::guess_format(&header[..read]).ok())
+}
+
+/// 检查扩展名与文件内容是否一致，不一致时返回 (扩展名格式, 实际格式)
+fn detect_format_mismatch(path: &Path) -> Option<(ImageFormat, Option<ImageFormat>)> {
+    let expected = ImageFormat::from_path(path).ok()?;
+    // TGA 没有文件头魔数，无法按内容识别
+    if expected == ImageFormat::Tga {
+        return None;
+    }
+    let detected = sniff_image_format(path).ok()?;
+    if detected == Some(expected) {
+        None
+    } else {
+        Some((expected, detected))
+    }
+}
+
+fn format_mismatch_message(expected: ImageFormat, detected: Option<ImageFormat>) -> String {
+    match detected {
+        Some(detected) => format!(
+            "File extension says {} but the content is {}",
+            format_name(expected),
+            format_name(detected)
+        ),
+        None => format!(
+            "File extension says {} but the content is not a recognizable image (corrupt or truncated)",
+            format_name(expected)
+        ),
+    }
+}
+
+/// 检查单个图片的格式是否与扩展名一致
+pub fn check_image_format(path: &Path) -> Option<FormatMismatch> {
+    let (expected, detected) = detect_format_mismatch(path)?;
+    Some(FormatMismatch {
+        path: path.to_string_lossy().to_string(),
+        extension_format: format_name(expected),
+        detected_format: detected.map(format_name),
+        message: format_mismatch_message(expected, detected),
+    })
+}
+
+/// 查找材质包中扩展名与内容不一致的图片，路径相对材质包根目录
+pub fn find_format_mismatches(pack_path: &Path) -> Vec<FormatMismatch> {
+    use rayon::prelude::*;
+
+    let mut mismatches: Vec<FormatMismatch> = crate::preloader::collect_images_recursive(pack_path)
+        .par_iter()
+        .filter_map(|path| {
+            let mut mismatch = check_image_format(path)?;
+            if let Ok(relative) = path.strip_prefix(pack_path) {
+                mismatch.path = relative.to_string_lossy().replace('\\', "/");
+            }
+            Some(mismatch)
+        })
+        .collect();
+    mismatches.sort_by(|a, b| a.path.cmp(&b.path));
+    mismatches
+}
+
+/// 将图片按扩展名对应的格式重新编码，修复扩展名与内容不一致的问题
+pub fn fix_image_format(path: &Path) -> Result<(), String> {
+    let data = std::fs::read(path).map_err(|e| format!("Failed to read image: {}", e))?;
+    save_image_bytes(path, &data)
 }
 
 /// 获取图片完整信息
@@ -168,7 +367,11 @@ pub fn get_image_info(path: &Path) -> Result<ImageInfo, String> {
         }
     }
     
-    let img = image::open(path)
+    // 按内容识别格式解码，扩展名错误的图片也能读取
+    let img = image::ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .decode()
         .map_err(|e| format!("Failed to open image: {}", e))?;
     
     let (width, height) = (img.width(), img.height());
@@ -184,12 +387,20 @@ pub fn get_image_info(path: &Path) -> Result<ImageInfo, String> {
     
     let is_valid_texture = validate_texture_size(width, height);
     
+    let mismatch = detect_format_mismatch(path);
+    let detected_format = sniff_image_format(path).ok().flatten().map(format_name);
+    let format_warning = mismatch.map(|(expected, detected)| format_mismatch_message(expected, detected));
+    let frame_count = count_animation_frames(path).ok().flatten();
+    
     let info = ImageInfo {
         width,
         height,
         format,
         size_bytes,
         is_valid_texture,
+        detected_format,
+        format_warning,
+        frame_count,
     };
     
     // 缓存结果
@@ -199,6 +410,146 @@ pub fn get_image_info(path: &Path) -> Result<ImageInfo, String> {
     Ok(info)
 }
 
+/// 图片的颜色格式和动画信息
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ImageMetadata {
+    pub width: u32,
+    pub height: u32,
+    /// 按文件内容识别的格式
+    pub format: Option<String>,
+    /// PNG 为 Grayscale、GrayscaleAlpha、RGB、RGBA 或 Indexed
+    pub color_type: String,
+    /// 每个通道（调色板 PNG 为每个索引）的位数
+    pub bit_depth: u8,
+    /// 有透明通道，或调色板 PNG 带有 tRNS 透明块
+    pub has_alpha: bool,
+    /// 灰度图在游戏中可能会被染色（如草、树叶）
+    pub is_grayscale: bool,
+    /// 调色板 PNG 的颜色数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub palette_size: Option<usize>,
+    /// 是否有带 animation 的 .mcmeta，或为多帧 GIF/WebP
+    pub is_animated: bool,
+    pub frame_count: u32,
+}
+
+/// 读取 PNG 文件头中的颜色信息，不解码像素数据
+fn read_png_metadata(path: &Path) -> Result<ImageMetadata, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open image: {}", e))?;
+    let reader = png::Decoder::new(BufReader::new(file))
+        .read_info()
+        .map_err(|e| format!("Failed to read PNG header: {}", e))?;
+    let info = reader.info();
+
+    let has_trns = info.trns.is_some();
+    let (color_type, has_alpha, is_grayscale) = match info.color_type {
+        png::ColorType::Grayscale => ("Grayscale", has_trns, true),
+        png::ColorType::GrayscaleAlpha => ("GrayscaleAlpha", true, true),
+        png::ColorType::Rgb => ("RGB", has_trns, false),
+        png::ColorType::Rgba => ("RGBA", true, false),
+        png::ColorType::Indexed => ("Indexed", has_trns, false),
+    };
+    let palette_size = match info.color_type {
+        png::ColorType::Indexed => info.palette.as_ref().map(|palette| palette.len() / 3),
+        _ => None,
+    };
+
+    Ok(ImageMetadata {
+        width: info.width,
+        height: info.height,
+        format: Some(format_name(ImageFormat::Png)),
+        color_type: color_type.to_string(),
+        bit_depth: info.bit_depth as u8,
+        has_alpha,
+        is_grayscale,
+        palette_size,
+        is_animated: false,
+        frame_count: 1,
+    })
+}
+
+/// 读取解码器提供的颜色信息，不解码像素数据
+fn read_decoder_metadata(path: &Path, detected: Option<ImageFormat>) -> Result<ImageMetadata, String> {
+    use image::ImageDecoder;
+
+    let decoder = image::ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .into_decoder()
+        .map_err(|e| format!("Failed to read image: {}", e))?;
+    let (width, height) = decoder.dimensions();
+    let color = decoder.color_type();
+
+    Ok(ImageMetadata {
+        width,
+        height,
+        format: detected.map(format_name),
+        color_type: format!("{:?}", color),
+        bit_depth: (color.bits_per_pixel() / color.channel_count().max(1) as u16) as u8,
+        has_alpha: color.has_alpha(),
+        is_grayscale: !color.has_color(),
+        palette_size: None,
+        is_animated: false,
+        frame_count: 1,
+    })
+}
+
+/// GIF 和 WebP 的帧数（逐帧解码），其他格式返回 None
+pub fn count_animation_frames(path: &Path) -> Result<Option<u32>, String> {
+    use image::AnimationDecoder;
+
+    let format = sniff_image_format(path)?;
+    if !matches!(format, Some(ImageFormat::Gif | ImageFormat::WebP)) {
+        return Ok(None);
+    }
+    let reader = BufReader::new(File::open(path).map_err(|e| format!("Failed to open image: {}", e))?);
+    let frames = match format {
+        Some(ImageFormat::Gif) => image::codecs::gif::GifDecoder::new(reader)
+            .map_err(|e| format!("Failed to read GIF: {}", e))?
+            .into_frames()
+            .take_while(|frame| frame.is_ok())
+            .count(),
+        _ => {
+            let decoder = image::codecs::webp::WebPDecoder::new(reader)
+                .map_err(|e| format!("Failed to read WebP: {}", e))?;
+            if decoder.has_animation() {
+                decoder.into_frames().take_while(|frame| frame.is_ok()).count()
+            } else {
+                1
+            }
+        }
+    };
+    Ok(Some(frames as u32))
+}
+
+/// 获取图片的颜色类型、位深、透明度和动画帧数
+///
+/// PNG 只读取文件头，其他格式只读取解码器信息
+pub fn get_image_metadata(path: &Path) -> Result<ImageMetadata, String> {
+    let detected = sniff_image_format(path)?;
+    let mut metadata = if detected == Some(ImageFormat::Png) {
+        read_png_metadata(path)?
+    } else {
+        read_decoder_metadata(path, detected)?
+    };
+
+    if let Some(frames) = count_animation_frames(path)? {
+        metadata.frame_count = frames;
+        metadata.is_animated = frames > 1;
+    }
+
+    if let Some(config) = crate::animation::read_animation(path)? {
+        metadata.is_animated = true;
+        if let Ok((_, _, count)) =
+            crate::animation::frame_layout(metadata.width, metadata.height, &config)
+        {
+            metadata.frame_count = count;
+        }
+    }
+
+    Ok(metadata)
+}
+
 /// 创建透明PNG图片
 pub fn create_transparent_png(
     path: &Path,
@@ -232,6 +583,694 @@ pub fn create_transparent_png(
     Ok(())
 }
 
+/// 按目标文件扩展名保存图片数据，格式不一致时重新编码（如 PNG 数据写入 .tga）
+pub fn save_image_bytes(path: &Path, data: &[u8]) -> Result<(), String> {
+    let target_format = ImageFormat::from_path(path).unwrap_or(ImageFormat::Png);
+
+    let source_format = match image::guess_format(data) {
+        Ok(format) => format,
+        // TGA 没有文件头魔数，无法识别时按 TGA 解码
+        Err(_) if target_format == ImageFormat::Tga => ImageFormat::Tga,
+        Err(e) => return Err(format!("Failed to decode image: {}", e)),
+    };
+
+    // TGA 无法通过魔数确认内容，总是解码后重新编码
+    if source_format == target_format && target_format != ImageFormat::Tga {
+        std::fs::write(path, data)
+            .map_err(|e| io_error("Failed to save image", e))?;
+    } else {
+        let img = image::load_from_memory_with_format(data, source_format)
+            .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+        // JPEG 不支持透明通道
+        let img = if target_format == ImageFormat::Jpeg {
+            DynamicImage::ImageRgb8(img.to_rgb8())
+        } else {
+            img
+        };
+
+        img.save_with_format(path, target_format).map_err(|e| match e {
+            image::ImageError::IoError(e) => io_error("Failed to save image", e),
+            e => format!("Failed to save image: {}", e),
+        })?;
+    }
+
+    invalidate_image_caches(path);
+    Ok(())
+}
+
+/// 移除指定图片的缓存
+pub fn invalidate_image_caches(path: &Path) {
+    let path_str = path.to_string_lossy().to_string();
+    let prefix = format!("{}_", path_str);
+
+    {
+        let mut cache = THUMBNAIL_CACHE.write();
+        let keys: Vec<String> = cache
+            .iter()
+            .filter(|(k, _)| k.starts_with(&prefix))
+            .map(|(k, _)| k.clone())
+            .collect();
+        for key in keys {
+            cache.pop(&key);
+        }
+    }
+
+    IMAGE_INFO_CACHE.write().pop(&path_str);
+}
+
+/// 图片各通道的 256 级直方图
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ImageHistogram {
+    pub width: u32,
+    pub height: u32,
+    pub red: Vec<u32>,
+    pub green: Vec<u32>,
+    pub blue: Vec<u32>,
+    /// 透明度直方图始终统计所有像素
+    pub alpha: Vec<u32>,
+    pub luminance: Vec<u32>,
+    /// 计入 RGB 和亮度直方图的像素数
+    pub counted_pixels: u64,
+}
+
+/// 统计直方图，`include_transparent` 为 false 时 RGB 和亮度忽略完全透明的像素
+pub fn compute_histogram(img: &RgbaImage, include_transparent: bool) -> ImageHistogram {
+    let mut red = [0u32; 256];
+    let mut green = [0u32; 256];
+    let mut blue = [0u32; 256];
+    let mut alpha = [0u32; 256];
+    let mut luminance = [0u32; 256];
+    let mut counted_pixels = 0u64;
+
+    for pixel in img.pixels() {
+        let [r, g, b, a] = pixel.0;
+        alpha[a as usize] += 1;
+        if a == 0 && !include_transparent {
+            continue;
+        }
+
+        red[r as usize] += 1;
+        green[g as usize] += 1;
+        blue[b as usize] += 1;
+        // Rec. 709 亮度
+        let luma = (2126 * r as u32 + 7152 * g as u32 + 722 * b as u32 + 5000) / 10000;
+        luminance[luma.min(255) as usize] += 1;
+        counted_pixels += 1;
+    }
+
+    ImageHistogram {
+        width: img.width(),
+        height: img.height(),
+        red: red.to_vec(),
+        green: green.to_vec(),
+        blue: blue.to_vec(),
+        alpha: alpha.to_vec(),
+        luminance: luminance.to_vec(),
+        counted_pixels,
+    }
+}
+
+/// 读取图片并计算直方图
+pub fn get_image_histogram(path: &Path, include_transparent: bool) -> Result<ImageHistogram, String> {
+    let img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .to_rgba8();
+    Ok(compute_histogram(&img, include_transparent))
+}
+
+/// 生成亮度、对比度、伽马调整的查找表
+///
+/// `brightness` 和 `contrast` 取值 -1.0 到 1.0（0 为不变），`gamma` 大于 0（1.0 为不变）
+pub(crate) fn levels_lut(brightness: f32, contrast: f32, gamma: f32) -> Result<[u8; 256], String> {
+    if !(-1.0..=1.0).contains(&brightness) {
+        return Err(format!("Brightness must be between -1 and 1: {}", brightness));
+    }
+    if !(-1.0..=1.0).contains(&contrast) {
+        return Err(format!("Contrast must be between -1 and 1: {}", contrast));
+    }
+    if !gamma.is_finite() || gamma <= 0.0 {
+        return Err(format!("Gamma must be greater than 0: {}", gamma));
+    }
+
+    let contrast_factor = 1.0 + contrast;
+    let mut lut = [0u8; 256];
+    for (i, value) in lut.iter_mut().enumerate() {
+        let v = (i as f32 / 255.0).powf(1.0 / gamma);
+        let v = (v - 0.5) * contrast_factor + 0.5 + brightness;
+        *value = (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+    Ok(lut)
+}
+
+/// 对 RGB 通道应用色阶调整，保留透明度
+pub fn apply_levels(img: &mut RgbaImage, brightness: f32, contrast: f32, gamma: f32) -> Result<(), String> {
+    let lut = levels_lut(brightness, contrast, gamma)?;
+    for pixel in img.pixels_mut() {
+        for channel in &mut pixel.0[..3] {
+            *channel = lut[*channel as usize];
+        }
+    }
+    Ok(())
+}
+
+/// 调整图片的亮度、对比度和伽马并写回原文件
+pub fn adjust_levels(path: &Path, brightness: f32, contrast: f32, gamma: f32) -> Result<(), String> {
+    let mut img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .to_rgba8();
+    apply_levels(&mut img, brightness, contrast, gamma)?;
+
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    img.write_to(&mut buffer, ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    save_image_bytes(path, buffer.get_ref())
+}
+
+/// 将编辑后的图片按目标文件扩展名对应的格式写入
+fn write_edited_image(path: &Path, img: &DynamicImage) -> Result<(), String> {
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    img.write_to(&mut buffer, ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+    save_image_bytes(path, buffer.get_ref())
+}
+
+/// 将图片居中裁剪为正方形并缩放为 PACK_ICON_SIZE 大小（最近邻），写为 PNG
+pub fn create_pack_icon(src: &Path, dst: &Path) -> Result<(u32, u32), String> {
+    let img = image::open(src)
+        .map_err(|e| format!("Failed to open image: {}", e))?;
+    if img.width() == 0 || img.height() == 0 {
+        return Err("Source image is empty".to_string());
+    }
+
+    let side = img.width().min(img.height());
+    let square = img.crop_imm((img.width() - side) / 2, (img.height() - side) / 2, side, side);
+    let size = crate::constants::PACK_ICON_SIZE;
+    let icon = square.resize_exact(size, size, FilterType::Nearest);
+    write_edited_image(dst, &icon)?;
+    Ok((icon.width(), icon.height()))
+}
+
+/// 翻转方向
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlipDirection {
+    Horizontal,
+    Vertical,
+}
+
+/// 水平或竖直翻转图片并写回原文件
+pub fn flip_image(path: &Path, direction: FlipDirection) -> Result<(), String> {
+    let img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?;
+    let flipped = match direction {
+        FlipDirection::Horizontal => img.fliph(),
+        FlipDirection::Vertical => img.flipv(),
+    };
+    write_edited_image(path, &flipped)
+}
+
+/// 顺时针旋转 90、180 或 270 度并写回原文件（逐像素移动，不插值）
+pub fn rotate_image(path: &Path, degrees: u32) -> Result<(), String> {
+    let img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?;
+    let rotated = match degrees {
+        90 => img.rotate90(),
+        180 => img.rotate180(),
+        270 => img.rotate270(),
+        _ => return Err(format!("Rotation must be 90, 180 or 270 degrees: {}", degrees)),
+    };
+    write_edited_image(path, &rotated)
+}
+
+/// 裁剪图片并写回原文件，裁剪区域必须位于图片范围内
+pub fn crop_image(path: &Path, x: u32, y: u32, width: u32, height: u32) -> Result<(), String> {
+    let img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?;
+
+    if width == 0 || height == 0 {
+        return Err("Crop width and height must be greater than 0".to_string());
+    }
+    let in_bounds = x.checked_add(width).is_some_and(|right| right <= img.width())
+        && y.checked_add(height).is_some_and(|bottom| bottom <= img.height());
+    if !in_bounds {
+        return Err(format!(
+            "Crop rectangle {}x{} at ({}, {}) is outside the {}x{} image",
+            width, height, x, y, img.width(), img.height()
+        ));
+    }
+
+    write_edited_image(path, &img.crop_imm(x, y, width, height))
+}
+
+/// 去色时计算灰度的方式
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DesaturateMethod {
+    /// Rec. 709 亮度，最接近人眼感受
+    #[default]
+    Luminance,
+    /// RGB 平均值
+    Average,
+    /// 最大和最小通道的平均值
+    Lightness,
+}
+
+impl DesaturateMethod {
+    fn gray(&self, r: u8, g: u8, b: u8) -> u8 {
+        let (r, g, b) = (r as u32, g as u32, b as u32);
+        let gray = match self {
+            DesaturateMethod::Luminance => (2126 * r + 7152 * g + 722 * b + 5000) / 10000,
+            DesaturateMethod::Average => (r + g + b + 1) / 3,
+            DesaturateMethod::Lightness => (r.max(g).max(b) + r.min(g).min(b) + 1) / 2,
+        };
+        gray.min(255) as u8
+    }
+}
+
+/// 将图片转为灰度，透明度保持不变
+///
+/// `normalize` 为 true 时将非透明像素的灰度拉伸到 0-255
+pub fn desaturate(img: &mut RgbaImage, method: DesaturateMethod, normalize: bool) {
+    for pixel in img.pixels_mut() {
+        let [r, g, b, _] = pixel.0;
+        let gray = method.gray(r, g, b);
+        pixel.0[..3].fill(gray);
+    }
+
+    if !normalize {
+        return;
+    }
+    let visible = img.pixels().filter(|p| p.0[3] > 0).map(|p| p.0[0]);
+    let (min, max) = visible.fold((u8::MAX, u8::MIN), |(min, max), v| (min.min(v), max.max(v)));
+    if max <= min {
+        return;
+    }
+    let range = (max - min) as u32;
+    for pixel in img.pixels_mut() {
+        let v = pixel.0[0].clamp(min, max);
+        let stretched = (((v - min) as u32 * 255 + range / 2) / range) as u8;
+        pixel.0[..3].fill(stretched);
+    }
+}
+
+/// 生成贴图的灰度版本并保存到 `dst_path`，作为重新着色的模板
+pub fn desaturate_image(
+    src_path: &Path,
+    dst_path: &Path,
+    method: DesaturateMethod,
+    normalize: bool,
+) -> Result<(), String> {
+    let mut img = image::open(src_path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .to_rgba8();
+    desaturate(&mut img, method, normalize);
+
+    if let Some(parent) = dst_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    write_edited_image(dst_path, &DynamicImage::ImageRgba8(img))
+}
+
+/// 遮罩模式下，RGB 通道最大差值不超过该值的像素视为灰色
+const TINT_GRAY_TOLERANCE: u8 = 24;
+
+/// 解析 #RRGGBB 格式的颜色，`#` 可省略
+pub fn parse_hex_color(hex: &str) -> Result<[u8; 3], String> {
+    let digits = hex.trim().trim_start_matches('#');
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid hex color: {}", hex));
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).unwrap_or(0);
+    Ok([channel(0), channel(2), channel(4)])
+}
+
+/// 将 RGB 与颜色相乘后按 `strength`（0-1）混合，透明度保持不变
+///
+/// `mask` 为 true 时只处理非透明的灰色像素，与游戏中草、树叶的染色方式一致
+pub fn tint(img: &mut RgbaImage, color: [u8; 3], strength: f32, mask: bool) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&strength) {
+        return Err(format!("Strength must be between 0 and 1: {}", strength));
+    }
+
+    for pixel in img.pixels_mut() {
+        let [r, g, b, a] = pixel.0;
+        if mask {
+            let spread = r.max(g).max(b) - r.min(g).min(b);
+            if a == 0 || spread > TINT_GRAY_TOLERANCE {
+                continue;
+            }
+        }
+        for (channel, tint) in pixel.0[..3].iter_mut().zip(color) {
+            let value = *channel as f32;
+            let multiplied = value * tint as f32 / 255.0;
+            *channel = (value + (multiplied - value) * strength).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+    Ok(())
+}
+
+/// 用颜色给贴图染色并保存到 `dst_path`，用于预览生物群系染色效果
+pub fn tint_image(
+    src_path: &Path,
+    dst_path: &Path,
+    hex_color: &str,
+    strength: f32,
+    mask: bool,
+) -> Result<(), String> {
+    let color = parse_hex_color(hex_color)?;
+    let mut img = image::open(src_path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .to_rgba8();
+    tint(&mut img, color, strength, mask)?;
+
+    if let Some(parent) = dst_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    write_edited_image(dst_path, &DynamicImage::ImageRgba8(img))
+}
+
+/// 缩放方式
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResizeMode {
+    /// 按比例缩放，如 0.5 将 64x 材质转为 32x
+    Scale(f32),
+    /// 缩放到指定的宽高
+    Exact(u32, u32),
+    /// 长边超过该值时等比缩小，否则保持不变
+    MaxDimension(u32),
+}
+
+/// 缩放插值方式，默认使用最近邻以保持像素风格
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResizeFilter {
+    #[default]
+    Nearest,
+    Triangle,
+    Lanczos3,
+}
+
+impl From<ResizeFilter> for FilterType {
+    fn from(filter: ResizeFilter) -> Self {
+        match filter {
+            ResizeFilter::Nearest => FilterType::Nearest,
+            ResizeFilter::Triangle => FilterType::Triangle,
+            ResizeFilter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+/// 批量缩放参数
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct ResizeSpec {
+    pub mode: ResizeMode,
+    #[serde(default)]
+    pub filter: ResizeFilter,
+}
+
+impl ResizeMode {
+    /// 检查缩放参数是否有效
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            ResizeMode::Scale(scale) if !scale.is_finite() || scale <= 0.0 => {
+                Err(format!("Scale must be greater than 0: {}", scale))
+            }
+            ResizeMode::Exact(width, height) if width == 0 || height == 0 => {
+                Err("Width and height must be greater than 0".to_string())
+            }
+            ResizeMode::MaxDimension(0) => Err("Max dimension must be greater than 0".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// 计算 width x height 缩放后的尺寸
+    fn apply(&self, width: u32, height: u32) -> (u32, u32) {
+        let scaled = |scale: f32| {
+            (
+                ((width as f32 * scale).round() as u32).max(1),
+                ((height as f32 * scale).round() as u32).max(1),
+            )
+        };
+        match *self {
+            ResizeMode::Scale(scale) => scaled(scale),
+            ResizeMode::Exact(width, height) => (width, height),
+            ResizeMode::MaxDimension(max) if width.max(height) > max => {
+                scaled(max as f32 / width.max(height) as f32)
+            }
+            ResizeMode::MaxDimension(_) => (width, height),
+        }
+    }
+}
+
+/// 缩放单张图片并写回原文件，返回新的尺寸；`dry_run` 为 true 时只计算尺寸而不写入
+///
+/// 动画贴图按单帧计算目标尺寸后缩放整条贴图，保持帧的排列；
+/// .mcmeta 中显式指定的帧宽高会同步更新
+pub fn resize_texture(path: &Path, spec: &ResizeSpec, dry_run: bool) -> Result<(u32, u32), String> {
+    spec.mode.validate()?;
+
+    let (width, height) = image::image_dimensions(path)
+        .map_err(|e| format!("Failed to read image dimensions: {}", e))?;
+
+    let animation = crate::animation::read_animation(path)?;
+    let layout = animation
+        .as_ref()
+        .and_then(|config| crate::animation::frame_layout(width, height, config).ok());
+    let (new_width, new_height, new_frame) = match layout {
+        Some((frame_width, frame_height, _)) => {
+            let (columns, rows) = (width / frame_width, height / frame_height);
+            let (fw, fh) = spec.mode.apply(frame_width, frame_height);
+            (fw * columns, fh * rows, Some((fw, fh)))
+        }
+        None => {
+            let (w, h) = spec.mode.apply(width, height);
+            (w, h, None)
+        }
+    };
+
+    if (new_width, new_height) == (width, height) {
+        return Ok((width, height));
+    }
+    if new_width > 16384 || new_height > 16384 {
+        return Err(format!("Resized image is too large: {}x{}", new_width, new_height));
+    }
+    if dry_run {
+        return Ok((new_width, new_height));
+    }
+
+    let img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?;
+    let resized = img.resize_exact(new_width, new_height, spec.filter.into());
+    write_edited_image(path, &resized)?;
+
+    if let (Some(mut config), Some((fw, fh))) = (animation, new_frame) {
+        if config.width.is_some() || config.height.is_some() {
+            config.width = config.width.map(|_| fw);
+            config.height = config.height.map(|_| fh);
+            crate::animation::save_animation(path, &config)?;
+        }
+    }
+
+    Ok((new_width, new_height))
+}
+
+/// 图片转换的目标格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvertFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl From<ConvertFormat> for ImageFormat {
+    fn from(format: ConvertFormat) -> Self {
+        match format {
+            ConvertFormat::Png => ImageFormat::Png,
+            ConvertFormat::Jpeg => ImageFormat::Jpeg,
+            ConvertFormat::Webp => ImageFormat::WebP,
+        }
+    }
+}
+
+/// 图片转换结果
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConvertResult {
+    pub width: u32,
+    pub height: u32,
+    pub size_bytes: u64,
+    /// 不影响转换的提示，如透明度丢失
+    pub warnings: Vec<String>,
+}
+
+/// 将图片转换为 PNG、JPEG 或 WebP
+///
+/// 源图片可以是任意支持的格式（包括旧材质包中的 TGA），转为 PNG 时保留透明度；
+/// `quality`（1-100，默认 90）只对 JPEG 有效；WebP 始终以无损方式编码
+pub fn convert_image(
+    src_path: &Path,
+    dst_path: &Path,
+    format: ConvertFormat,
+    quality: Option<u8>,
+) -> Result<ConvertResult, String> {
+    let quality = quality.unwrap_or(90);
+    if !(1..=100).contains(&quality) {
+        return Err(format!("Quality must be between 1 and 100: {}", quality));
+    }
+
+    // 按内容识别格式解码，扩展名错误的图片也能读取
+    let img = image::ImageReader::open(src_path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .decode()
+        .map_err(|e| format!("Failed to decode image: {}", e))?;
+
+    let mut warnings = Vec::new();
+    if ImageFormat::from_path(dst_path).ok() != Some(format.into()) {
+        warnings.push(format!(
+            "The file extension does not match the {} format",
+            format_name(format.into())
+        ));
+    }
+
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    match format {
+        ConvertFormat::Png => img.write_to(&mut buffer, ImageFormat::Png),
+        ConvertFormat::Jpeg => {
+            if img.color().has_alpha() && img.to_rgba8().pixels().any(|p| p.0[3] < 255) {
+                warnings.push(
+                    "JPEG does not support transparency, transparent pixels will be lost".to_string(),
+                );
+            }
+            let encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buffer, quality);
+            DynamicImage::ImageRgb8(img.to_rgb8()).write_with_encoder(encoder)
+        }
+        ConvertFormat::Webp => {
+            warnings.push("Minecraft cannot load WebP textures".to_string());
+            DynamicImage::ImageRgba8(img.to_rgba8()).write_to(&mut buffer, ImageFormat::WebP)
+        }
+    }
+    .map_err(|e| format!("Failed to encode image: {}", e))?;
+
+    if let Some(parent) = dst_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    std::fs::write(dst_path, buffer.get_ref())
+        .map_err(|e| io_error("Failed to save image", e))?;
+    invalidate_image_caches(dst_path);
+
+    Ok(ConvertResult {
+        width: img.width(),
+        height: img.height(),
+        size_bytes: buffer.get_ref().len() as u64,
+        warnings,
+    })
+}
+
+/// GIF 导入结果
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GifAnimationResult {
+    pub frame_count: usize,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub frame_times: Vec<u32>,
+}
+
+/// 将 GIF 帧延迟（毫秒）换算为游戏刻，至少为 1
+fn delay_ms_to_ticks(ms: u32) -> u32 {
+    ((ms + 25) / 50).max(1)
+}
+
+/// 将动态 GIF 拆分为竖向排列的 PNG 动画条，并写入对应的 .mcmeta
+pub fn import_gif_as_animation(
+    gif_path: &Path,
+    output_path: &Path,
+    square_frames: bool,
+) -> Result<GifAnimationResult, String> {
+    use image::codecs::gif::GifDecoder;
+    use image::AnimationDecoder;
+
+    let file = File::open(gif_path)
+        .map_err(|e| format!("Failed to open GIF: {}", e))?;
+    let decoder = GifDecoder::new(BufReader::new(file))
+        .map_err(|e| format!("Failed to decode GIF: {}", e))?;
+    let frames = decoder
+        .into_frames()
+        .collect_frames()
+        .map_err(|e| format!("Failed to decode GIF frames: {}", e))?;
+
+    let first = frames.first().ok_or("GIF contains no frames")?;
+    let (width, height) = first.buffer().dimensions();
+
+    if square_frames && width != height {
+        return Err(format!("Frames must be square, got {}x{}", width, height));
+    }
+
+    for (i, frame) in frames.iter().enumerate() {
+        if frame.buffer().dimensions() != (width, height) {
+            let (w, h) = frame.buffer().dimensions();
+            return Err(format!(
+                "Frame {} is {}x{}, expected {}x{}",
+                i, w, h, width, height
+            ));
+        }
+    }
+
+    let strip_height = height
+        .checked_mul(frames.len() as u32)
+        .ok_or("Animation strip is too tall")?;
+    let mut strip = RgbaImage::new(width, strip_height);
+    let mut frame_times = Vec::with_capacity(frames.len());
+
+    for (i, frame) in frames.iter().enumerate() {
+        image::imageops::replace(&mut strip, frame.buffer(), 0, (i as u32 * height) as i64);
+        let (numer, denom) = frame.delay().numer_denom_ms();
+        frame_times.push(delay_ms_to_ticks(numer / denom.max(1)));
+    }
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+    strip
+        .save_with_format(output_path, ImageFormat::Png)
+        .map_err(|e| format!("Failed to save PNG: {}", e))?;
+
+    // 所有帧时长相同时使用 frametime，否则逐帧写入
+    let animation = if frame_times.iter().all(|t| *t == frame_times[0]) {
+        serde_json::json!({ "frametime": frame_times[0] })
+    } else {
+        let frames: Vec<_> = frame_times
+            .iter()
+            .enumerate()
+            .map(|(index, time)| serde_json::json!({ "index": index, "time": time }))
+            .collect();
+        serde_json::json!({ "frames": frames })
+    };
+    let mcmeta = serde_json::json!({ "animation": animation });
+
+    let mut mcmeta_path = output_path.as_os_str().to_owned();
+    mcmeta_path.push(".mcmeta");
+    let content = serde_json::to_string_pretty(&mcmeta)
+        .map_err(|e| format!("Failed to serialize mcmeta: {}", e))?;
+    std::fs::write(PathBuf::from(mcmeta_path), content)
+        .map_err(|e| format!("Failed to write mcmeta: {}", e))?;
+
+    invalidate_image_caches(output_path);
+
+    Ok(GifAnimationResult {
+        frame_count: frames.len(),
+        frame_width: width,
+        frame_height: height,
+        frame_times,
+    })
+}
+
 /// 异步创建缩略图
 pub async fn create_thumbnail_async(
     path: PathBuf,
@@ -248,6 +1287,24 @@ pub async fn create_thumbnail_async(
         .map_err(|e| format!("Channel error: {}", e))?
 }
 
+/// 异步创建缩略图（使用磁盘缓存）
+pub async fn create_thumbnail_disk_cached_async(
+    pack_path: PathBuf,
+    path: PathBuf,
+    max_size: u32,
+    write_cache: bool,
+) -> Result<String, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    rayon::spawn(move || {
+        let result = create_thumbnail_disk_cached(&pack_path, &path, max_size, write_cache);
+        let _ = tx.send(result);
+    });
+
+    rx.await
+        .map_err(|e| format!("Channel error: {}", e))?
+}
+
 /// 批量创建缩略图
 #[allow(dead_code)]
 pub async fn create_thumbnails_batch(
@@ -277,10 +1334,68 @@ pub fn clear_caches() {
     IMAGE_INFO_CACHE.write().clear();
 }
 
+/// 内存缩略图缓存的条目数和占用字节数
+pub fn thumbnail_cache_usage() -> (usize, usize) {
+    let cache = THUMBNAIL_CACHE.read();
+    let bytes = cache.iter().map(|(key, data)| key.len() + data.len()).sum();
+    (cache.len(), bytes)
+}
+
 /// 获取缓存统计信息
 #[allow(dead_code)]
 pub fn get_cache_stats() -> (usize, usize) {
     let thumb_cache = THUMBNAIL_CACHE.read();
     let info_cache = IMAGE_INFO_CACHE.read();
     (thumb_cache.len(), info_cache.len())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+
+    #[test]
+    fn test_save_image_bytes_accepts_tga_data() {
+        let dir = std::env::temp_dir().join(format!("little100_tga_bytes_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("sun.tga");
+
+        let mut img = RgbaImage::new(2, 2);
+        img.put_pixel(1, 1, Rgba([10, 20, 30, 40]));
+        let mut data = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut data, ImageFormat::Tga).unwrap();
+
+        let result = save_image_bytes(&target, data.get_ref());
+        let saved = image::open(&target).map(|img| img.to_rgba8());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(result.is_ok(), "{:?}", result);
+        assert_eq!(saved.unwrap().get_pixel(1, 1), &Rgba([10, 20, 30, 40]));
+    }
+
+    #[test]
+    fn test_tga_converts_to_png_with_alpha() {
+        let dir = std::env::temp_dir().join(format!("little100_tga_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let src = dir.join("icons.tga");
+        let dst = dir.join("icons.png");
+
+        let mut img = RgbaImage::new(4, 2);
+        img.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        img.put_pixel(1, 0, Rgba([0, 255, 0, 128]));
+        img.put_pixel(3, 1, Rgba([0, 0, 255, 0]));
+        img.save_with_format(&src, ImageFormat::Tga).unwrap();
+
+        let info = get_image_info(&src);
+        let result = convert_image(&src, &dst, ConvertFormat::Png, None);
+        let converted = image::open(&dst).map(|img| img.to_rgba8());
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let info = info.unwrap();
+        assert_eq!((info.width, info.height), (4, 2));
+        assert!(result.unwrap().warnings.is_empty());
+        let converted = converted.unwrap();
+        assert_eq!(converted.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(converted.get_pixel(1, 0), &Rgba([0, 255, 0, 128]));
+        assert_eq!(converted.get_pixel(3, 1).0[3], 0);
+    }
+}
@@ -0,0 +1,271 @@
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::io_error;
+
+/// 调色板 PNG 保存结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedSaveResult {
+    /// 调色板颜色数
+    pub colors: usize,
+    /// 原图颜色超过上限时为 true，表示已量化为近似颜色
+    pub quantized: bool,
+    pub original_size: u64,
+    pub size: u64,
+}
+
+/// 完全透明的像素统一为同一颜色，避免占用多个调色板项
+fn normalize(pixel: [u8; 4]) -> [u8; 4] {
+    if pixel[3] == 0 {
+        [0, 0, 0, 0]
+    } else {
+        pixel
+    }
+}
+
+/// 统计每种颜色的像素数
+fn count_colors(img: &RgbaImage) -> HashMap<[u8; 4], u32> {
+    let mut counts = HashMap::new();
+    for pixel in img.pixels() {
+        *counts.entry(normalize(pixel.0)).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// 颜色盒中跨度最大的通道及其跨度
+fn widest_channel(colors: &[([u8; 4], u32)]) -> (usize, u8) {
+    (0..4)
+        .map(|channel| {
+            let min = colors.iter().map(|(c, _)| c[channel]).min().unwrap_or(0);
+            let max = colors.iter().map(|(c, _)| c[channel]).max().unwrap_or(0);
+            (channel, max - min)
+        })
+        .max_by_key(|&(_, range)| range)
+        .unwrap_or((0, 0))
+}
+
+/// 按像素数加权的中位切分，生成不超过 `max_colors` 个颜色的调色板
+fn median_cut(colors: Vec<([u8; 4], u32)>, max_colors: usize) -> Vec<[u8; 4]> {
+    let mut boxes = vec![colors];
+
+    while boxes.len() < max_colors {
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, colors)| colors.len() > 1)
+            .map(|(index, colors)| (index, widest_channel(colors)))
+            .max_by_key(|&(_, (_, range))| range);
+        let (index, channel) = match widest {
+            Some((index, (channel, range))) if range > 0 => (index, channel),
+            _ => break,
+        };
+
+        let mut colors = boxes.swap_remove(index);
+        colors.sort_unstable_by_key(|(c, _)| c[channel]);
+
+        let total: u64 = colors.iter().map(|(_, count)| *count as u64).sum();
+        let mut accumulated = 0u64;
+        let mut split = colors.len() / 2;
+        for (i, (_, count)) in colors.iter().enumerate() {
+            accumulated += *count as u64;
+            if accumulated * 2 >= total {
+                split = (i + 1).clamp(1, colors.len() - 1);
+                break;
+            }
+        }
+
+        let rest = colors.split_off(split);
+        boxes.push(colors);
+        boxes.push(rest);
+    }
+
+    boxes
+        .iter()
+        .map(|colors| {
+            let total: u64 = colors
+                .iter()
+                .map(|(_, count)| *count as u64)
+                .sum::<u64>()
+                .max(1);
+            let mut sum = [0u64; 4];
+            for (color, count) in colors {
+                for (acc, value) in sum.iter_mut().zip(color) {
+                    *acc += *value as u64 * *count as u64;
+                }
+            }
+            sum.map(|s| ((s + total / 2) / total) as u8)
+        })
+        .collect()
+}
+
+/// 调色板中与 `color` 距离最近的颜色索引
+fn nearest(palette: &[[u8; 4]], color: [u8; 4]) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, p)| {
+            (0..4)
+                .map(|channel| {
+                    let diff = p[channel] as i32 - color[channel] as i32;
+                    (diff * diff) as u32
+                })
+                .sum::<u32>()
+        })
+        .map(|(index, _)| index)
+        .unwrap_or(0)
+}
+
+/// 生成调色板，颜色超过 `max_colors` 且不允许量化时返回错误
+fn build_palette(
+    counts: &HashMap<[u8; 4], u32>,
+    max_colors: usize,
+    quantize: bool,
+) -> Result<(Vec<[u8; 4]>, bool), String> {
+    if counts.len() <= max_colors {
+        let mut palette: Vec<[u8; 4]> = counts.keys().copied().collect();
+        palette.sort_unstable();
+        return Ok((palette, false));
+    }
+    if !quantize {
+        return Err(format!(
+            "Image has {} colors, more than the limit of {}",
+            counts.len(),
+            max_colors
+        ));
+    }
+
+    // 完全透明单独保留一项，保证透明区域不被量化成半透明
+    let transparent = counts.contains_key(&[0, 0, 0, 0]);
+    let colors: Vec<([u8; 4], u32)> = counts
+        .iter()
+        .filter(|(color, _)| color[3] != 0)
+        .map(|(color, count)| (*color, *count))
+        .collect();
+    if transparent && max_colors < 2 {
+        return Err("max_colors must be at least 2 for images with transparency".to_string());
+    }
+    let budget = if transparent {
+        max_colors - 1
+    } else {
+        max_colors
+    };
+
+    let mut palette = median_cut(colors, budget);
+    if transparent {
+        palette.push([0, 0, 0, 0]);
+    }
+    palette.sort_unstable();
+    palette.dedup();
+    Ok((palette, true))
+}
+
+/// 将图片编码为调色板 PNG，按调色板大小选择 1/2/4/8 位深度
+pub fn encode_indexed_png(
+    img: &RgbaImage,
+    max_colors: usize,
+    quantize: bool,
+) -> Result<(Vec<u8>, usize, bool), String> {
+    if !(1..=256).contains(&max_colors) {
+        return Err(format!(
+            "max_colors must be between 1 and 256: {}",
+            max_colors
+        ));
+    }
+
+    let counts = count_colors(img);
+    let (mut palette, quantized) = build_palette(&counts, max_colors, quantize)?;
+
+    // 半透明项排在前面，tRNS 只需写到最后一个非不透明项
+    palette.sort_by_key(|color| color[3] == 255);
+    let indices: HashMap<[u8; 4], u8> = counts
+        .keys()
+        .map(|&color| {
+            let index = match palette.iter().position(|&p| p == color) {
+                Some(index) => index,
+                None => nearest(&palette, color),
+            };
+            (color, index as u8)
+        })
+        .collect();
+
+    let (bits, depth) = match palette.len() {
+        0..=2 => (1, png::BitDepth::One),
+        3..=4 => (2, png::BitDepth::Two),
+        5..=16 => (4, png::BitDepth::Four),
+        _ => (8, png::BitDepth::Eight),
+    };
+    let width = img.width() as usize;
+    let per_byte = 8 / bits;
+    let row_bytes = (width * bits).div_ceil(8);
+    let mut data = vec![0u8; row_bytes * img.height() as usize];
+    for (x, y, pixel) in img.enumerate_pixels() {
+        let (x, y) = (x as usize, y as usize);
+        let index = indices[&normalize(pixel.0)];
+        let shift = 8 - bits * (x % per_byte + 1);
+        data[y * row_bytes + x / per_byte] |= index << shift;
+    }
+
+    let rgb: Vec<u8> = palette.iter().flat_map(|c| [c[0], c[1], c[2]]).collect();
+    let trns: Vec<u8> = palette
+        .iter()
+        .take_while(|c| c[3] != 255)
+        .map(|c| c[3])
+        .collect();
+
+    let mut output = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut output, img.width(), img.height());
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(depth);
+        encoder.set_palette(rgb);
+        if !trns.is_empty() {
+            encoder.set_trns(trns);
+        }
+        encoder.set_compression(png::Compression::Best);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("Failed to write PNG header: {}", e))?;
+        writer
+            .write_image_data(&data)
+            .map_err(|e| format!("Failed to write PNG data: {}", e))?;
+    }
+
+    Ok((output, palette.len(), quantized))
+}
+
+/// 将 PNG 重新保存为调色板 PNG
+///
+/// 颜色数超过 `max_colors` 时，`quantize` 为 true 则量化到最近的调色板颜色，否则返回错误
+pub fn save_image_indexed(
+    path: &Path,
+    max_colors: usize,
+    quantize: bool,
+) -> Result<IndexedSaveResult, String> {
+    let is_png = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("png"))
+        .unwrap_or(false);
+    if !is_png {
+        return Err("Indexed saving is only supported for .png files".to_string());
+    }
+
+    let original_size = std::fs::metadata(path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read image: {}", e))?;
+    let img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .to_rgba8();
+
+    let (data, colors, quantized) = encode_indexed_png(&img, max_colors, quantize)?;
+    std::fs::write(path, &data).map_err(|e| io_error("Failed to save image", e))?;
+    crate::image_handler::invalidate_image_caches(path);
+
+    Ok(IndexedSaveResult {
+        colors,
+        quantized,
+        original_size,
+        size: data.len() as u64,
+    })
+}
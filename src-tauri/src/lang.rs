@@ -0,0 +1,375 @@
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 按文件中的顺序保存的语言条目
+struct OrderedEntries(Vec<(String, String)>);
+
+impl<'de> Deserialize<'de> for OrderedEntries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = OrderedEntries;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON object mapping translation keys to strings")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries: Vec<(String, String)> = Vec::new();
+                while let Some((key, value)) = map.next_entry::<String, String>()? {
+                    // 重复的键以最后一次出现为准，与游戏行为一致
+                    match entries.iter_mut().find(|(k, _)| *k == key) {
+                        Some(entry) => entry.1 = value,
+                        None => entries.push((key, value)),
+                    }
+                }
+                Ok(OrderedEntries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+impl Serialize for OrderedEntries {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+
+/// 语言文件与参照语言的差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MissingKeysReport {
+    /// 参照语言中有、当前语言中没有的键及其参照文本
+    pub missing: Vec<(String, String)>,
+    /// 当前语言中有、参照语言中没有的键
+    pub extra: Vec<String>,
+    pub reference_count: usize,
+    pub translated_count: usize,
+}
+
+/// 语言文件格式转换结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LangConversion {
+    /// 生成的文件路径
+    pub path: PathBuf,
+    pub entries: usize,
+    /// 无法表示或无法解析而跳过的内容
+    pub warnings: Vec<String>,
+}
+
+/// pack_format 4（1.13）之前的语言文件使用 .lang 格式
+pub fn uses_legacy_lang(pack_format: i32) -> bool {
+    pack_format < 4
+}
+
+/// 列出 assets/*/lang 下指定扩展名的语言文件（相对材质包根目录）
+pub fn list_lang_files(pack_path: &Path, extension: &str) -> Vec<String> {
+    let Ok(namespaces) = fs::read_dir(pack_path.join("assets")) else {
+        return Vec::new();
+    };
+    let mut files = Vec::new();
+    for namespace in namespaces.flatten() {
+        let Ok(entries) = fs::read_dir(namespace.path().join("lang")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let matches = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.eq_ignore_ascii_case(extension))
+                .unwrap_or(false);
+            if matches && path.is_file() {
+                if let Ok(relative) = path.strip_prefix(pack_path) {
+                    files.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn check_json_lang(path: &Path) -> Result<(), String> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("json") => Ok(()),
+        _ => Err(format!(
+            "Only JSON language files are supported: {}",
+            path.display()
+        )),
+    }
+}
+
+fn check_key(key: &str) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("Translation key cannot be empty".to_string());
+    }
+    Ok(())
+}
+
+/// 按文件中的顺序读取语言条目
+pub fn read_lang_entries(path: &Path) -> Result<Vec<(String, String)>, String> {
+    check_json_lang(path)?;
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read language file: {}", e))?;
+    // 部分语言文件带有 BOM
+    let content = content.trim_start_matches('\u{feff}');
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let entries: OrderedEntries = serde_json::from_str(content)
+        .map_err(|e| format!("Failed to parse language file: {}", e))?;
+    Ok(entries.0)
+}
+
+fn write_lang_entries(path: &Path, entries: Vec<(String, String)>) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(&OrderedEntries(entries))
+        .map_err(|e| format!("Failed to serialize language file: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write language file: {}", e))
+}
+
+/// 修改已有条目的文本，保持键的顺序
+pub fn update_lang_entry(path: &Path, key: &str, value: &str) -> Result<(), String> {
+    let mut entries = read_lang_entries(path)?;
+    let entry = entries
+        .iter_mut()
+        .find(|(k, _)| k == key)
+        .ok_or_else(|| format!("Translation key not found: {}", key))?;
+    entry.1 = value.to_string();
+    write_lang_entries(path, entries)
+}
+
+/// 在末尾添加条目，语言文件不存在时创建
+pub fn add_lang_entry(path: &Path, key: &str, value: &str) -> Result<(), String> {
+    check_key(key)?;
+    let mut entries = if path.exists() {
+        read_lang_entries(path)?
+    } else {
+        check_json_lang(path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create lang directory: {}", e))?;
+        }
+        Vec::new()
+    };
+    if entries.iter().any(|(k, _)| k == key) {
+        return Err(format!("Translation key already exists: {}", key));
+    }
+    entries.push((key.to_string(), value.to_string()));
+    write_lang_entries(path, entries)
+}
+
+/// 删除条目
+pub fn delete_lang_entry(path: &Path, key: &str) -> Result<(), String> {
+    let mut entries = read_lang_entries(path)?;
+    let before = entries.len();
+    entries.retain(|(k, _)| k != key);
+    if entries.len() == before {
+        return Err(format!("Translation key not found: {}", key));
+    }
+    write_lang_entries(path, entries)
+}
+
+/// 对比参照语言（如 en_us），找出未翻译和多余的键
+pub fn find_missing_keys(
+    lang_path: &Path,
+    reference_path: &Path,
+) -> Result<MissingKeysReport, String> {
+    let entries = read_lang_entries(lang_path)?;
+    let reference = read_lang_entries(reference_path)?;
+
+    let keys: HashSet<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+    let reference_keys: HashSet<&str> = reference.iter().map(|(k, _)| k.as_str()).collect();
+
+    let missing: Vec<(String, String)> = reference
+        .iter()
+        .filter(|(k, _)| !keys.contains(k.as_str()))
+        .cloned()
+        .collect();
+    let extra = entries
+        .iter()
+        .filter(|(k, _)| !reference_keys.contains(k.as_str()))
+        .map(|(k, _)| k.clone())
+        .collect();
+
+    Ok(MissingKeysReport {
+        translated_count: reference.len() - missing.len(),
+        reference_count: reference.len(),
+        missing,
+        extra,
+    })
+}
+
+/// .lang 中没有转义语法，只把换行写成 `\n`
+fn escape_legacy_value(value: &str) -> String {
+    value.replace("\r\n", "\\n").replace('\n', "\\n")
+}
+
+fn unescape_legacy_value(value: &str) -> String {
+    value.replace("\\n", "\n")
+}
+
+/// 解析 .lang 内容：每行 key=value，# 开头为注释
+fn parse_legacy_lang(content: &str) -> (Vec<(String, String)>, Vec<String>) {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    let mut warnings = Vec::new();
+    for (index, line) in content.trim_start_matches('\u{feff}').lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            warnings.push(format!("Line {}: missing '=', skipped", index + 1));
+            continue;
+        };
+        let value = unescape_legacy_value(value);
+        match entries.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((key.to_string(), value)),
+        }
+    }
+    (entries, warnings)
+}
+
+fn format_legacy_lang(entries: &[(String, String)]) -> (String, Vec<String>) {
+    let mut content = String::new();
+    let mut warnings = Vec::new();
+    for (key, value) in entries {
+        if key.contains('=') || key.contains('\n') || key.starts_with('#') {
+            warnings.push(format!("Key cannot be written to .lang, skipped: {}", key));
+            continue;
+        }
+        content.push_str(key);
+        content.push('=');
+        content.push_str(&escape_legacy_value(value));
+        content.push('\n');
+    }
+    (content, warnings)
+}
+
+/// 将 JSON 语言文件转换为同名的 .lang 文件（1.13 之前的格式）
+pub fn convert_lang_json_to_properties(path: &Path) -> Result<LangConversion, String> {
+    let entries = read_lang_entries(path)?;
+    let (content, warnings) = format_legacy_lang(&entries);
+    let output = path.with_extension("lang");
+    fs::write(&output, content).map_err(|e| format!("Failed to write language file: {}", e))?;
+
+    Ok(LangConversion {
+        path: output,
+        entries: entries.len() - warnings.len(),
+        warnings,
+    })
+}
+
+/// 将 .lang 文件转换为同名的 JSON 语言文件，注释会被丢弃
+pub fn convert_lang_properties_to_json(path: &Path) -> Result<LangConversion, String> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("lang") => {}
+        _ => return Err(format!("Not a .lang file: {}", path.display())),
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read language file: {}", e))?;
+    let (entries, warnings) = parse_legacy_lang(&content);
+    let count = entries.len();
+    let output = path.with_extension("json");
+    write_lang_entries(&output, entries)?;
+
+    Ok(LangConversion {
+        path: output,
+        entries: count,
+        warnings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("little100_lang_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_edits_preserve_order_and_escape() {
+        let dir = temp_dir("edit");
+        let path = dir.join("en_us.json");
+        fs::write(
+            &path,
+            "{\"z.key\": \"Z\", \"a.key\": \"A\", \"m.key\": \"M\"}",
+        )
+        .unwrap();
+
+        update_lang_entry(&path, "a.key", "Say \"hi\"\n\\o/").unwrap();
+        add_lang_entry(&path, "b.key", "B").unwrap();
+        delete_lang_entry(&path, "m.key").unwrap();
+        let entries = read_lang_entries(&path);
+        let raw = fs::read_to_string(&path).unwrap_or_default();
+        let _ = fs::remove_dir_all(&dir);
+
+        let entries = entries.unwrap();
+        let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, ["z.key", "a.key", "b.key"]);
+        assert_eq!(entries[1].1, "Say \"hi\"\n\\o/");
+        assert!(raw.contains(r#""a.key": "Say \"hi\"\n\\o/""#));
+    }
+
+    #[test]
+    fn test_find_missing_keys() {
+        let dir = temp_dir("missing");
+        let reference = dir.join("en_us.json");
+        let lang = dir.join("zh_cn.json");
+        fs::write(&reference, r#"{"a": "A", "b": "B", "c": "C"}"#).unwrap();
+        fs::write(&lang, r#"{"a": "甲", "d": "丁"}"#).unwrap();
+
+        let report = find_missing_keys(&lang, &reference);
+        let _ = fs::remove_dir_all(&dir);
+
+        let report = report.unwrap();
+        assert_eq!(
+            report.missing,
+            vec![
+                ("b".to_string(), "B".to_string()),
+                ("c".to_string(), "C".to_string())
+            ]
+        );
+        assert_eq!(report.extra, vec!["d".to_string()]);
+        assert_eq!((report.translated_count, report.reference_count), (1, 3));
+    }
+
+    #[test]
+    fn test_legacy_lang_round_trip() {
+        let dir = temp_dir("legacy");
+        let path = dir.join("en_us.lang");
+        fs::write(
+            &path,
+            "# comment\nitem.apple.name=Apple\n\nbroken line\ntile.sign.text=a=b\\nc\n",
+        )
+        .unwrap();
+
+        let to_json = convert_lang_properties_to_json(&path);
+        let entries = read_lang_entries(&dir.join("en_us.json"));
+        fs::remove_file(&path).unwrap();
+        let to_lang = convert_lang_json_to_properties(&dir.join("en_us.json"));
+        let lang = fs::read_to_string(&path).unwrap_or_default();
+        let _ = fs::remove_dir_all(&dir);
+
+        let to_json = to_json.unwrap();
+        assert_eq!(to_json.entries, 2);
+        assert_eq!(to_json.warnings.len(), 1);
+        let entries = entries.unwrap();
+        assert_eq!(
+            entries[1],
+            ("tile.sign.text".to_string(), "a=b\nc".to_string())
+        );
+        assert!(to_lang.unwrap().warnings.is_empty());
+        assert_eq!(lang, "item.apple.name=Apple\ntile.sign.text=a=b\\nc\n");
+    }
+}
@@ -11,6 +11,22 @@ mod preloader;
 mod download_manager;
 mod version_converter;
 mod pack_merger;
+mod deploy;
+mod sounds;
+mod optifine;
+mod vanilla_diff;
+mod logger;
+mod analysis;
+mod casing;
+mod drafts;
+mod indexed_png;
+mod error;
+mod font;
+mod metadata_repair;
+mod animation;
+mod blockstates;
+mod models;
+mod lang;
 pub use pack_merger::{
     MergeSource, MergePreview, MergeConflictSummary, MergeProgress, MergeResult,
     FileConflict, SourceStats, PackSourceType,
@@ -68,33 +84,6 @@ use std::sync::Arc;
 #[cfg(feature = "web-server")]
 use web_server::{WebServerState, start_server, stop_server, get_server_status};
 
-/// 初始化日志
-fn init_logging() {
-    // 获取exe目录
-    if let Ok(exe_path) = std::env::current_exe() {
-        if let Some(exe_dir) = exe_path.parent() {
-            let logs_dir = exe_dir.join("logs");
-            let log_file = logs_dir.join("latest.log");
-            
-            // 创建logs目录
-            let _ = std::fs::create_dir_all(&logs_dir);
-            
-            // 如果latest.log存在，删除它
-            if log_file.exists() {
-                let _ = std::fs::remove_file(&log_file);
-            }
-            
-            // 写入启动日志
-            use std::io::Write;
-            if let Ok(mut file) = std::fs::File::create(&log_file) {
-                let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
-                let _ = writeln!(file, "[{}] [INFO] 应用程序启动", timestamp);
-                let _ = writeln!(file, "[{}] [INFO] 日志系统初始化完成", timestamp);
-            }
-        }
-    }
-}
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let mut builder = tauri::Builder::default()
@@ -107,7 +96,7 @@ pub fn run() {
         .manage(AppState::default())
         .setup(|app| {
             // 初始化日志系统
-            init_logging();
+            logger::init();
             
             // 初始化下载管理器
             let download_manager = DownloadManager::new(app.handle().clone());
@@ -146,41 +135,142 @@ pub fn run() {
         check_pack_mcmeta,
         get_current_pack_info,
         get_current_pack_path,
+        set_pack_readonly,
+        is_pack_readonly,
+        open_pack,
+        close_pack,
+        list_open_packs,
+        switch_active_pack,
+        copy_between_packs,
         get_image_thumbnail,
+        get_thumbnails_batch,
         get_image_preview,
         get_image_details,
+        get_image_full_metadata,
+        check_image_formats,
+        fix_image_format,
+        get_image_histogram,
+        adjust_levels,
+        flip_image,
+        rotate_image,
+        crop_image,
+        adjust_levels_batch,
+        batch_resize_images,
+        convert_image,
+        desaturate_image,
+        tint_image,
+        save_image_indexed,
+        font_coverage,
+        get_font_definition,
+        update_font_definition,
+        generate_bitmap_font,
         export_pack,
+        export_selected_files,
+        get_modified_files_since,
+        export_variants,
+        export_to_folder,
+        get_default_resourcepacks_dir,
+        find_minecraft_resourcepacks_dirs,
+        start_live_deploy,
+        stop_live_deploy,
         cleanup_temp,
         read_file_content,
         read_file_binary,
         write_file_content,
+        autosave_draft,
+        load_draft,
+        discard_draft,
         create_new_file,
         create_new_folder,
         delete_file,
         rename_file,
+        undo_last_operation,
+        redo_last_operation,
         get_pack_mcmeta,
         update_pack_mcmeta,
+        validate_pack_format,
+        validate_json,
+        validate_all_json,
+        preflight_pack,
+        get_pack_formats,
+        update_pack_formats,
+        set_pack_icon,
+        get_pack_icon,
+        clear_pack_icon,
+        add_pack_overlay,
+        wrap_mod_assets_as_pack,
         create_new_pack,
+        list_pack_templates,
+        create_pack_from_template,
         create_item_model,
         create_block_model,
         create_multiple_item_models,
         create_multiple_block_models,
+        create_blockstate,
+        get_blockstate,
+        update_blockstate,
+        get_lang_entries,
+        update_lang_entry,
+        add_lang_entry,
+        delete_lang_entry,
+        find_missing_keys,
+        convert_lang_json_to_properties,
+        convert_lang_properties_to_json,
+        get_model_elements,
+        update_model_elements,
+        list_item_overrides,
+        add_item_override,
+        compact_custom_model_data,
         get_system_fonts,
         get_file_tree,
         load_folder_children,
+        count_children,
+        get_asset_tree,
+        get_tree_weights,
+        get_pack_size_breakdown,
+        validate_pack,
+        find_unused_textures,
+        find_duplicate_files,
+        analyze_pack,
+        cancel_analysis,
+        repair_editor_metadata,
+        get_namespaces,
+        get_namespace_tree,
         create_transparent_png,
         save_image,
+        import_gif_as_animation,
+        get_animation_mcmeta,
+        save_animation_mcmeta,
+        get_animation_preview,
         get_minecraft_versions,
+        set_network_timeouts,
         download_minecraft_version,
+        get_available_languages,
         download_latest_minecraft_version,
         extract_assets_from_jar,
+        read_asset_from_jar,
+        list_jar_assets,
+        summarize_jar_assets,
         download_and_extract_template,
         clear_template_cache,
+        generate_change_manifest,
+        diff_against_vanilla,
+        strip_vanilla_identical,
+        check_gui_layout,
         preload_folder_images,
         get_preloader_stats,
         clear_preloader_cache,
+        evict_folder_cache,
+        set_preloader_cache_limit,
+        clear_thumbnail_cache,
         preload_folder_aggressive,
+        cancel_preload,
+        set_parallelism,
         open_devtools,
+        get_debug_info,
+        set_log_level,
+        set_log_retention,
+        get_recent_logs,
         open_folder,
         load_language_map,
         get_sound_subtitles,
@@ -198,17 +288,31 @@ pub fn run() {
         check_file_exists,
         check_temp_audio_files,
         copy_sound_file,
+        validate_sounds,
+        get_sounds_json,
+        update_sounds_json,
+        list_sound_files,
+        validate_emissive,
+        check_filename_casing,
+        normalize_filenames,
         read_file_as_base64,
         open_in_explorer,
         read_merge_source_file_base64,
         preview_pack_merge,
         execute_pack_merge,
+        merge_pack,
         get_pack_meta_from_source,
         history_manager::save_file_history,
+        history_manager::set_history_settings,
         history_manager::load_file_history,
+        history_manager::undo_file,
+        history_manager::redo_file,
+        history_manager::get_history_versions,
+        history_manager::diff_file_history,
         history_manager::get_history_stats,
         history_manager::clear_file_history,
         history_manager::clear_all_history,
+        history_manager::prune_history,
         history_manager::get_pack_size,
         #[cfg(feature = "web-server")]
         start_server,
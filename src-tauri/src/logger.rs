@@ -0,0 +1,218 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// 同时写入控制台和 logs/latest.log 的日志器
+struct FileLogger {
+    file: Mutex<Option<File>>,
+}
+
+static LOGGER: FileLogger = FileLogger {
+    file: Mutex::new(None),
+};
+
+/// 保留的历史日志数量
+static RETENTION: AtomicUsize = AtomicUsize::new(crate::constants::LOG_RETENTION_COUNT);
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f");
+        let line = format!(
+            "[{}] [{}] [{}] {}",
+            timestamp,
+            record.level(),
+            record.target(),
+            record.args()
+        );
+
+        if record.level() <= Level::Warn {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
+
+        if let Ok(mut guard) = self.file.lock() {
+            if let Some(file) = guard.as_mut() {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut guard) = self.file.lock() {
+            if let Some(file) = guard.as_mut() {
+                let _ = file.flush();
+            }
+        }
+    }
+}
+
+/// 日志目录（exe 所在目录下的 logs）
+pub fn logs_dir() -> Option<PathBuf> {
+    let exe_path = std::env::current_exe().ok()?;
+    Some(exe_path.parent()?.join("logs"))
+}
+
+/// 将上次会话的 latest.log 重命名为按其最后修改时间命名的文件
+fn rotate_latest_log(logs_dir: &std::path::Path) {
+    let latest = logs_dir.join("latest.log");
+    let modified = match fs::metadata(&latest).and_then(|m| m.modified()) {
+        Ok(modified) => modified,
+        Err(_) => return,
+    };
+
+    let timestamp = chrono::DateTime::<chrono::Local>::from(modified)
+        .format("%Y-%m-%d_%H-%M-%S")
+        .to_string();
+    let mut target = logs_dir.join(format!("{}.log", timestamp));
+    let mut suffix = 1;
+    while target.exists() {
+        target = logs_dir.join(format!("{}_{}.log", timestamp, suffix));
+        suffix += 1;
+    }
+
+    let _ = fs::rename(&latest, &target);
+}
+
+/// 历史日志文件信息
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LogFileInfo {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub modified: String,
+}
+
+/// 列出已轮转的历史日志（不含 latest.log），按时间从新到旧排序
+pub fn list_rotated_logs() -> Vec<LogFileInfo> {
+    let dir = match logs_dir() {
+        Some(dir) => dir,
+        None => return Vec::new(),
+    };
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut logs: Vec<(std::time::SystemTime, LogFileInfo)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            if name == "latest.log" || !name.ends_with(".log") {
+                return None;
+            }
+            let metadata = e.metadata().ok()?;
+            let modified = metadata.modified().ok()?;
+            Some((
+                modified,
+                LogFileInfo {
+                    name,
+                    path: e.path().to_string_lossy().to_string(),
+                    size: metadata.len(),
+                    modified: chrono::DateTime::<chrono::Local>::from(modified)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string(),
+                },
+            ))
+        })
+        .collect();
+
+    logs.sort_by(|a, b| b.0.cmp(&a.0));
+    logs.into_iter().map(|(_, info)| info).collect()
+}
+
+/// 删除超出保留数量的旧日志
+fn prune_rotated_logs() {
+    let keep = RETENTION.load(Ordering::Relaxed);
+    for log in list_rotated_logs().into_iter().skip(keep) {
+        let _ = fs::remove_file(&log.path);
+    }
+}
+
+/// 读取上次设置的保留数量，文件不存在或无效时使用默认值
+fn load_retention(logs_dir: &std::path::Path) {
+    let saved = fs::read_to_string(logs_dir.join(crate::constants::LOG_RETENTION_FILE))
+        .ok()
+        .and_then(|content| content.trim().parse::<usize>().ok());
+    if let Some(count) = saved {
+        RETENTION.store(count, Ordering::Relaxed);
+    }
+}
+
+/// 设置保留的历史日志数量并保存到日志目录，立即清理多余的旧日志
+pub fn set_retention(count: usize) -> Result<(), String> {
+    RETENTION.store(count, Ordering::Relaxed);
+    if let Some(dir) = logs_dir() {
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create logs directory: {}", e))?;
+        fs::write(
+            dir.join(crate::constants::LOG_RETENTION_FILE),
+            count.to_string(),
+        )
+        .map_err(|e| format!("Failed to save log retention: {}", e))?;
+    }
+    prune_rotated_logs();
+    Ok(())
+}
+
+/// 初始化日志系统
+pub fn init() {
+    if let Some(dir) = logs_dir() {
+        if fs::create_dir_all(&dir).is_ok() {
+            load_retention(&dir);
+            rotate_latest_log(&dir);
+            prune_rotated_logs();
+            if let Ok(mut guard) = LOGGER.file.lock() {
+                *guard = File::create(dir.join("latest.log")).ok();
+            }
+        }
+    }
+
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(LevelFilter::Info);
+    }
+
+    log::info!("应用程序启动");
+    log::info!("日志系统初始化完成");
+}
+
+/// 解析日志级别名称
+pub fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    match level.to_lowercase().as_str() {
+        "off" => Ok(LevelFilter::Off),
+        "error" => Ok(LevelFilter::Error),
+        "warn" | "warning" => Ok(LevelFilter::Warn),
+        "info" => Ok(LevelFilter::Info),
+        "debug" => Ok(LevelFilter::Debug),
+        "trace" => Ok(LevelFilter::Trace),
+        _ => Err(format!("Unknown log level: {}", level)),
+    }
+}
+
+/// 读取 latest.log 的最后若干行
+pub fn tail(lines: usize) -> Vec<String> {
+    let latest = match logs_dir() {
+        Some(dir) => dir.join("latest.log"),
+        None => return Vec::new(),
+    };
+    let content = match fs::read_to_string(latest) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+
+    let all: Vec<&str> = content.lines().collect();
+    all[all.len().saturating_sub(lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
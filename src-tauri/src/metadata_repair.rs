@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+use crate::history_manager::{HistoryEntry, HistoryMetadata};
+
+/// 一项元数据修复
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataFix {
+    /// 相对材质包根目录的路径
+    pub path: String,
+    /// removed、regenerated 或 reported（无法自动修复，仅报告）
+    pub action: String,
+    pub reason: String,
+}
+
+/// 编辑器元数据自检结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataRepairReport {
+    pub dry_run: bool,
+    pub checked: usize,
+    pub fixes: Vec<MetadataFix>,
+}
+
+/// 文件头是否以 `magic` 开头
+fn has_magic(path: &Path, magic: &[u8]) -> bool {
+    use std::io::Read;
+
+    let mut header = vec![0u8; magic.len()];
+    fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map(|_| header == magic)
+        .unwrap_or(false)
+}
+
+fn parse_error<T: serde::de::DeserializeOwned>(path: &Path) -> Option<String> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<T>(&content)
+            .err()
+            .map(|e| format!("Invalid JSON: {}", e)),
+        Err(e) => Some(format!("Failed to read file: {}", e)),
+    }
+}
+
+/// 检查 .little100 下的单个文件，返回问题描述和是否可以删除
+///
+/// 这些文件都是缓存或可重新生成的数据，删除后编辑器会按需重建
+fn check_editor_file(rel: &str, path: &Path) -> Option<(String, bool)> {
+    let parts: Vec<&str> = rel.split('/').collect();
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match parts.as_slice() {
+        // 回收站中是用户删除的文件，不做检查
+        [".little100", "trash", ..] => None,
+        [".little100", "map.json"] => {
+            parse_error::<HashMap<String, String>>(path).map(|e| (e, true))
+        }
+        [".little100", "sounds.json"] => parse_error::<Value>(path).map(|e| (e, true)),
+        [".little100", "sounds", ..] => {
+            let valid = match ext.as_str() {
+                "ogg" => has_magic(path, b"OggS"),
+                "wav" => has_magic(path, b"RIFF"),
+                _ => true,
+            };
+            (!valid).then(|| ("Corrupted audio file".to_string(), true))
+        }
+        [".little100", "thumbs", ..] => image::image_dimensions(path)
+            .err()
+            .map(|e| (format!("Unreadable thumbnail: {}", e), true)),
+        [".little100", "drafts", ..] => fs::read(path)
+            .ok()
+            .and_then(|data| String::from_utf8(data).err())
+            .map(|_| ("Draft is not valid UTF-8 text".to_string(), true)),
+        _ if ext == "json" => parse_error::<Value>(path).map(|e| (e, false)),
+        _ => None,
+    }
+}
+
+/// 检查 .little100 和 .history 下编辑器自己的元数据，删除或重新生成无法解析的文件
+///
+/// 只处理这两个目录，不会修改材质包中的资源文件
+pub fn repair_editor_metadata(
+    pack_path: &Path,
+    dry_run: bool,
+    max_history_count: u32,
+) -> Result<MetadataRepairReport, String> {
+    let mut checked = 0;
+    let mut fixes = Vec::new();
+
+    let remove = |rel: String, path: &Path, reason: String, fixes: &mut Vec<MetadataFix>| {
+        let action = if dry_run {
+            "removed"
+        } else {
+            match fs::remove_file(path) {
+                Ok(()) => "removed",
+                Err(_) => "reported",
+            }
+        };
+        fixes.push(MetadataFix {
+            path: rel,
+            action: action.to_string(),
+            reason,
+        });
+    };
+
+    let little100 = pack_path.join(".little100");
+    for entry in WalkDir::new(&little100)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let rel = entry
+            .path()
+            .strip_prefix(pack_path)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        checked += 1;
+        match check_editor_file(&rel, entry.path()) {
+            Some((reason, true)) => remove(rel, entry.path(), reason, &mut fixes),
+            Some((reason, false)) => fixes.push(MetadataFix {
+                path: rel,
+                action: "reported".to_string(),
+                reason,
+            }),
+            None => {}
+        }
+    }
+
+    // 历史记录文件无法解析时整个文件的历史都无法加载，只删除损坏的那一条
+    let history = pack_path.join(".history");
+    let meta_file = history.join("history_meta.json");
+    for entry in WalkDir::new(&history)
+        .min_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .map(|ext| ext == "json")
+                .unwrap_or(false)
+        })
+    {
+        let rel = entry
+            .path()
+            .strip_prefix(pack_path)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        checked += 1;
+        if let Some(reason) = parse_error::<HistoryEntry>(entry.path()) {
+            remove(rel, entry.path(), reason, &mut fixes);
+        }
+    }
+
+    // 删除历史记录后统计信息也会过时，一并重新生成
+    let removed_history = fixes
+        .iter()
+        .any(|fix| fix.action == "removed" && fix.path.starts_with(".history/"));
+    if meta_file.is_file() {
+        checked += 1;
+        let reason = parse_error::<HistoryMetadata>(&meta_file)
+            .or_else(|| removed_history.then(|| "History entries were removed".to_string()));
+        if let Some(reason) = reason {
+            if !dry_run {
+                crate::history_manager::rebuild_metadata(pack_path, max_history_count)?;
+            }
+            fixes.push(MetadataFix {
+                path: ".history/history_meta.json".to_string(),
+                action: "regenerated".to_string(),
+                reason,
+            });
+        }
+    }
+
+    Ok(MetadataRepairReport {
+        dry_run,
+        checked,
+        fixes,
+    })
+}
@@ -0,0 +1,367 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 模型坐标允许的范围
+const COORD_MIN: f32 = -16.0;
+const COORD_MAX: f32 = 32.0;
+
+/// 向上查找父模型的最大层数，防止循环引用
+const MAX_PARENT_DEPTH: usize = 16;
+
+const FACE_NAMES: [&str; 6] = ["north", "south", "east", "west", "up", "down"];
+
+/// 元素的旋转
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementRotation {
+    pub origin: [f32; 3],
+    /// x、y 或 z
+    pub axis: String,
+    pub angle: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rescale: Option<bool>,
+}
+
+/// 元素的一个面
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElementFace {
+    /// [x1, y1, x2, y2]，省略时按元素位置自动计算
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uv: Option<[f32; 4]>,
+    /// 贴图变量，如 #north
+    pub texture: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cullface: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tintindex: Option<i32>,
+}
+
+/// 模型 elements 中的一个长方体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelElement {
+    pub from: [f32; 3],
+    pub to: [f32; 3],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<ElementRotation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shade: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub light_emission: Option<u8>,
+    /// 键为 north、south、east、west、up、down
+    pub faces: BTreeMap<String, ElementFace>,
+}
+
+/// 保存模型的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelUpdateResult {
+    pub path: String,
+    /// 不影响保存的问题，如无法解析的贴图变量
+    pub warnings: Vec<String>,
+}
+
+/// 模型 ID（如 minecraft:block/stone）对应的文件路径
+pub fn model_file(pack_path: &Path, model_id: &str) -> PathBuf {
+    let (namespace, path) = model_id.split_once(':').unwrap_or(("minecraft", model_id));
+    pack_path
+        .join("assets")
+        .join(namespace)
+        .join("models")
+        .join(format!("{}.json", path))
+}
+
+pub(crate) fn read_model(path: &Path) -> Result<serde_json::Map<String, Value>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read model: {}", e))?;
+    match serde_json::from_str(&content) {
+        Ok(Value::Object(model)) => Ok(model),
+        Ok(_) => Err("Model must be a JSON object".to_string()),
+        Err(e) => Err(format!("Failed to parse model: {}", e)),
+    }
+}
+
+pub(crate) fn write_model(
+    path: &Path,
+    model: serde_json::Map<String, Value>,
+) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(&Value::Object(model))
+        .map_err(|e| format!("Failed to serialize model: {}", e))?;
+    fs::write(path, content).map_err(|e| crate::error::io_error("Failed to write model", e))
+}
+
+/// 读取模型的 elements，没有时返回空列表
+pub fn get_model_elements(path: &Path) -> Result<Vec<ModelElement>, String> {
+    match read_model(path)?.remove("elements") {
+        Some(elements) => {
+            serde_json::from_value(elements).map_err(|e| format!("Invalid elements section: {}", e))
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+fn validate_element(index: usize, element: &ModelElement) -> Result<(), String> {
+    let mut coords = element.from.iter().chain(element.to.iter());
+    if coords.any(|c| !c.is_finite() || *c < COORD_MIN || *c > COORD_MAX) {
+        return Err(format!(
+            "Element {} has coordinates outside {}..{}",
+            index, COORD_MIN, COORD_MAX
+        ));
+    }
+    if let Some(rotation) = &element.rotation {
+        if !matches!(rotation.axis.as_str(), "x" | "y" | "z") {
+            return Err(format!(
+                "Element {} has an invalid rotation axis: {}",
+                index, rotation.axis
+            ));
+        }
+    }
+    for (name, face) in &element.faces {
+        if !FACE_NAMES.contains(&name.as_str()) {
+            return Err(format!("Element {} has an invalid face: {}", index, name));
+        }
+        if let Some(cullface) = &face.cullface {
+            if !FACE_NAMES.contains(&cullface.as_str()) && cullface != "bottom" {
+                return Err(format!(
+                    "Element {} face {} has an invalid cullface: {}",
+                    index, name, cullface
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 收集模型及其在材质包中的父模型声明的贴图变量
+fn declared_textures(pack_path: &Path, model: &serde_json::Map<String, Value>) -> HashSet<String> {
+    let mut declared = HashSet::new();
+    let mut current = Some(model.clone());
+    for _ in 0..MAX_PARENT_DEPTH {
+        let Some(model) = current.take() else { break };
+        if let Some(Value::Object(textures)) = model.get("textures") {
+            declared.extend(textures.keys().cloned());
+        }
+        current = model
+            .get("parent")
+            .and_then(Value::as_str)
+            .and_then(|parent| read_model(&model_file(pack_path, parent)).ok());
+    }
+    declared
+}
+
+/// 校验并写入模型的 elements，保留模型中的其他字段
+///
+/// 坐标超出范围或面名称无效时报错；无法在模型及其父模型中找到的贴图变量作为警告返回
+pub fn update_model_elements(
+    pack_path: &Path,
+    model_path: &str,
+    elements: &[ModelElement],
+) -> Result<ModelUpdateResult, String> {
+    for (index, element) in elements.iter().enumerate() {
+        validate_element(index, element)?;
+    }
+
+    let path = pack_path.join(model_path);
+    let mut model = read_model(&path)?;
+    let declared = declared_textures(pack_path, &model);
+    let mut warnings = Vec::new();
+    for face in elements.iter().flat_map(|element| element.faces.values()) {
+        let Some(variable) = face.texture.strip_prefix('#') else {
+            continue;
+        };
+        let message = format!("Texture variable not declared: #{}", variable);
+        if !declared.contains(variable) && !warnings.contains(&message) {
+            warnings.push(message);
+        }
+    }
+
+    if elements.is_empty() {
+        model.remove("elements");
+    } else {
+        let elements = serde_json::to_value(elements)
+            .map_err(|e| format!("Failed to serialize elements: {}", e))?;
+        model.insert("elements".to_string(), elements);
+    }
+    write_model(&path, model)?;
+
+    Ok(ModelUpdateResult {
+        path: model_path.to_string(),
+        warnings,
+    })
+}
+
+/// 物品模型 overrides 支持的谓词
+const OVERRIDE_PREDICATES: &[&str] = &[
+    "angle",
+    "blocking",
+    "broken",
+    "brushing",
+    "cast",
+    "charged",
+    "cooldown",
+    "custom_model_data",
+    "damage",
+    "damaged",
+    "filled",
+    "firework",
+    "honey_level",
+    "lefthanded",
+    "level",
+    "pull",
+    "pulling",
+    "throwing",
+    "time",
+    "tooting",
+    "trim_type",
+];
+
+/// 物品模型 overrides 中的一项，谓词全部满足时使用 `model`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemOverride {
+    /// 如 {"custom_model_data": 1}、{"pulling": 1, "pull": 0.65}
+    pub predicate: BTreeMap<String, serde_json::Number>,
+    pub model: String,
+}
+
+impl ItemOverride {
+    fn custom_model_data(&self) -> Option<i64> {
+        self.predicate.get("custom_model_data")?.as_i64()
+    }
+}
+
+/// 修改 overrides 的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemOverridesResult {
+    pub overrides: Vec<ItemOverride>,
+    /// 材质包中找不到的模型引用（可能来自原版）
+    pub warnings: Vec<String>,
+}
+
+/// 整理 custom_model_data 时的一项改动
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomModelDataChange {
+    pub model: String,
+    pub old_value: i64,
+    pub new_value: i64,
+}
+
+fn parse_overrides(model: &serde_json::Map<String, Value>) -> Result<Vec<ItemOverride>, String> {
+    match model.get("overrides") {
+        Some(overrides) => serde_json::from_value(overrides.clone())
+            .map_err(|e| format!("Invalid overrides section: {}", e)),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn write_overrides(
+    path: &Path,
+    mut model: serde_json::Map<String, Value>,
+    overrides: &[ItemOverride],
+) -> Result<(), String> {
+    let overrides = serde_json::to_value(overrides)
+        .map_err(|e| format!("Failed to serialize overrides: {}", e))?;
+    model.insert("overrides".to_string(), overrides);
+    write_model(path, model)
+}
+
+fn missing_override_models(pack_path: &Path, overrides: &[ItemOverride]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for item in overrides {
+        let message = format!("Model not found in pack: {}", item.model);
+        if !model_file(pack_path, &item.model).is_file() && !warnings.contains(&message) {
+            warnings.push(message);
+        }
+    }
+    warnings
+}
+
+/// 读取物品模型的 overrides
+pub fn list_item_overrides(path: &Path) -> Result<Vec<ItemOverride>, String> {
+    parse_overrides(&read_model(path)?)
+}
+
+/// 添加一项 override
+///
+/// 游戏使用最后一个匹配的项，因此只有 custom_model_data 的项会按数值升序插入，避免被较小的值覆盖
+pub fn add_item_override(
+    pack_path: &Path,
+    model_path: &str,
+    item: ItemOverride,
+) -> Result<ItemOverridesResult, String> {
+    if item.predicate.is_empty() {
+        return Err("Predicate cannot be empty".to_string());
+    }
+    // 带命名空间的谓词来自模组，不做检查
+    if let Some(key) = item
+        .predicate
+        .keys()
+        .find(|key| !key.contains(':') && !OVERRIDE_PREDICATES.contains(&key.as_str()))
+    {
+        return Err(format!("Unknown predicate: {}", key));
+    }
+    if item.model.trim().is_empty() {
+        return Err("Override model cannot be empty".to_string());
+    }
+
+    let path = pack_path.join(model_path);
+    let model = read_model(&path)?;
+    let mut overrides = parse_overrides(&model)?;
+
+    let position = match item.custom_model_data() {
+        Some(value) if item.predicate.len() == 1 => overrides
+            .iter()
+            .position(|o| o.predicate.len() == 1 && o.custom_model_data() > Some(value))
+            .unwrap_or(overrides.len()),
+        _ => overrides.len(),
+    };
+    overrides.insert(position, item);
+    write_overrides(&path, model, &overrides)?;
+
+    Ok(ItemOverridesResult {
+        warnings: missing_override_models(pack_path, &overrides),
+        overrides,
+    })
+}
+
+/// 将 custom_model_data 重新编号为从 1 开始的连续整数，重复的值也会分配不同的编号
+///
+/// 按原数值和原顺序排列；`dry_run` 为 true 时只返回改动而不写入
+pub fn compact_custom_model_data(
+    pack_path: &Path,
+    model_path: &str,
+    dry_run: bool,
+) -> Result<Vec<CustomModelDataChange>, String> {
+    let path = pack_path.join(model_path);
+    let model = read_model(&path)?;
+    let mut overrides = parse_overrides(&model)?;
+
+    let mut numbered: Vec<(usize, i64)> = overrides
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| Some((index, item.custom_model_data()?)))
+        .collect();
+    numbered.sort_by_key(|&(index, value)| (value, index));
+
+    let mut changes = Vec::new();
+    for (new_value, (index, old_value)) in (1..).zip(numbered) {
+        if new_value == old_value {
+            continue;
+        }
+        let item = &mut overrides[index];
+        item.predicate.insert(
+            "custom_model_data".to_string(),
+            serde_json::Number::from(new_value),
+        );
+        changes.push(CustomModelDataChange {
+            model: item.model.clone(),
+            old_value,
+            new_value,
+        });
+    }
+
+    if !dry_run && !changes.is_empty() {
+        write_overrides(&path, model, &overrides)?;
+    }
+    Ok(changes)
+}
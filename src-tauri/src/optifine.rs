@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// OptiFine 未配置时使用的默认发光后缀
+pub const DEFAULT_EMISSIVE_SUFFIX: &str = "_e";
+
+/// 发光贴图问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmissiveIssue {
+    pub path: String,
+    pub base_path: String,
+    pub kind: String,
+    pub message: String,
+}
+
+/// 发光贴图校验报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmissiveReport {
+    pub suffix: String,
+    pub properties_found: bool,
+    pub emissive_count: usize,
+    pub issues: Vec<EmissiveIssue>,
+}
+
+/// 解析 .properties 文件中的某个键
+fn read_property(content: &str, key: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        let (k, v) = line.split_once('=')?;
+        (k.trim() == key).then(|| v.trim().to_string())
+    })
+}
+
+/// 读取 optifine/emissive.properties 中配置的发光后缀
+pub fn read_emissive_suffix(pack_path: &Path) -> Option<String> {
+    let properties = pack_path
+        .join("assets")
+        .join("minecraft")
+        .join("optifine")
+        .join("emissive.properties");
+    let content = fs::read_to_string(properties).ok()?;
+    read_property(&content, "suffix.emissive").filter(|s| !s.is_empty())
+}
+
+/// 检查所有发光贴图是否有对应的基础贴图且尺寸一致
+pub fn validate_emissive(pack_path: &Path) -> Result<EmissiveReport, String> {
+    let configured = read_emissive_suffix(pack_path);
+    let properties_found = configured.is_some();
+    let suffix = configured.unwrap_or_else(|| DEFAULT_EMISSIVE_SUFFIX.to_string());
+    let png_suffix = format!("{}.png", suffix);
+
+    let assets = pack_path.join("assets");
+    let mut emissive_count = 0;
+    let mut issues = Vec::new();
+
+    for entry in WalkDir::new(&assets).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let file_name = entry.file_name().to_string_lossy();
+        let stem_len = match file_name.len().checked_sub(png_suffix.len()) {
+            Some(len) if len > 0 && file_name.ends_with(&png_suffix) => len,
+            _ => continue,
+        };
+        emissive_count += 1;
+
+        let base = entry
+            .path()
+            .with_file_name(format!("{}.png", &file_name[..stem_len]));
+        let rel = |p: &Path| {
+            p.strip_prefix(pack_path)
+                .unwrap_or(p)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+        let path_str = rel(entry.path());
+        let base_str = rel(&base);
+
+        if !base.is_file() {
+            issues.push(EmissiveIssue {
+                path: path_str,
+                base_path: base_str,
+                kind: "orphaned".to_string(),
+                message: "Base texture not found".to_string(),
+            });
+            continue;
+        }
+
+        let emissive_size = image::image_dimensions(entry.path())
+            .map_err(|e| format!("Failed to read {}: {}", path_str, e));
+        let base_size = image::image_dimensions(&base)
+            .map_err(|e| format!("Failed to read {}: {}", base_str, e));
+
+        match (emissive_size, base_size) {
+            (Ok(e), Ok(b)) if e != b => issues.push(EmissiveIssue {
+                path: path_str,
+                base_path: base_str,
+                kind: "size_mismatch".to_string(),
+                message: format!(
+                    "Emissive texture is {}x{}, base texture is {}x{}",
+                    e.0, e.1, b.0, b.1
+                ),
+            }),
+            (Err(message), _) | (_, Err(message)) => issues.push(EmissiveIssue {
+                path: path_str,
+                base_path: base_str,
+                kind: "unreadable".to_string(),
+                message,
+            }),
+            _ => {}
+        }
+    }
+
+    Ok(EmissiveReport {
+        suffix,
+        properties_found,
+        emissive_count,
+        issues,
+    })
+}
@@ -1,5 +1,6 @@
 use std::fs;
 use std::path::Path;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 /// 创建新的材质包
@@ -14,26 +15,26 @@ pub fn create_new_pack(
         .map_err(|e| format!("Failed to create pack directory: {}", e))?;
 
     // 创建 pack.mcmeta
-    let pack_mcmeta = json!({
-        "pack": {
-            "pack_format": pack_format,
-            "description": description
-        }
-    });
-
-    let mcmeta_path = output_path.join("pack.mcmeta");
-    fs::write(
-        mcmeta_path,
-        serde_json::to_string_pretty(&pack_mcmeta)
-            .map_err(|e| format!("Failed to serialize pack.mcmeta: {}", e))?,
-    )
-    .map_err(|e| format!("Failed to write pack.mcmeta: {}", e))?;
+    write_pack_mcmeta(output_path, pack_format, description)?;
 
     // 创建 assets/minecraft 目录结构
     let assets_path = output_path.join("assets").join("minecraft");
     
     // 创建标准目录
-    let directories = vec![
+    for dir in standard_directories(pack_format) {
+        fs::create_dir_all(assets_path.join(dir))
+            .map_err(|e| format!("Failed to create directory {}: {}", dir, e))?;
+    }
+
+    // 创建 pack.png (可选的图标)
+    create_default_pack_icon(output_path)?;
+
+    Ok(())
+}
+
+/// 原版资源的标准目录（相对 assets/minecraft），1.21.4+ 额外包含 items
+fn standard_directories(pack_format: i32) -> Vec<&'static str> {
+    let mut dirs = vec![
         "textures/block",
         "textures/item",
         "textures/entity",
@@ -46,22 +47,240 @@ pub fn create_new_pack(
         "font",
         "shaders",
     ];
-
-    // 如果是1.21.4+版本,创建items目录
     if pack_format >= 35 {
-        fs::create_dir_all(assets_path.join("items"))
-            .map_err(|e| format!("Failed to create items directory: {}", e))?;
+        dirs.push("items");
+    }
+    dirs
+}
+
+/// 写入 pack.mcmeta
+pub(crate) fn write_pack_mcmeta(output_path: &Path, pack_format: i32, description: &str) -> Result<(), String> {
+    let pack_mcmeta = json!({
+        "pack": {
+            "pack_format": pack_format,
+            "description": description
+        }
+    });
+
+    let mcmeta_path = output_path.join("pack.mcmeta");
+    fs::write(
+        mcmeta_path,
+        serde_json::to_string_pretty(&pack_mcmeta)
+            .map_err(|e| format!("Failed to serialize pack.mcmeta: {}", e))?,
+    )
+    .map_err(|e| format!("Failed to write pack.mcmeta: {}", e))
+}
+
+/// 内置的材质包模板
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PackTemplate {
+    Empty,
+    MinimalGui,
+    ItemTextures,
+    BlockPackStarter,
+    FontPackStarter,
+    FullVanillaMirror,
+}
+
+/// 模板信息，供前端展示模板列表
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackTemplateInfo {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub directories: Vec<String>,
+}
+
+impl PackTemplate {
+    pub const ALL: [PackTemplate; 6] = [
+        PackTemplate::Empty,
+        PackTemplate::MinimalGui,
+        PackTemplate::ItemTextures,
+        PackTemplate::BlockPackStarter,
+        PackTemplate::FontPackStarter,
+        PackTemplate::FullVanillaMirror,
+    ];
+
+    /// 模板 ID
+    pub fn id(&self) -> &'static str {
+        match self {
+            PackTemplate::Empty => "empty",
+            PackTemplate::MinimalGui => "minimal-gui",
+            PackTemplate::ItemTextures => "item-textures",
+            PackTemplate::BlockPackStarter => "block-pack-starter",
+            PackTemplate::FontPackStarter => "font-pack-starter",
+            PackTemplate::FullVanillaMirror => "full-vanilla-mirror",
+        }
+    }
+
+    /// 根据模板 ID 查找模板
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|template| template.id() == id)
+    }
+
+    /// 模板需要创建的目录（相对 assets/minecraft）
+    fn directories(&self, pack_format: i32) -> Vec<&'static str> {
+        match self {
+            PackTemplate::Empty => Vec::new(),
+            PackTemplate::MinimalGui => {
+                let mut dirs = vec!["textures/gui", "textures/gui/container"];
+                // 1.20.2+ 的 GUI 使用 sprites 目录
+                if pack_format >= 18 {
+                    dirs.push("textures/gui/sprites");
+                }
+                dirs
+            }
+            PackTemplate::ItemTextures => {
+                let mut dirs = vec!["textures/item", "models/item", "lang"];
+                if pack_format >= 35 {
+                    dirs.push("items");
+                }
+                dirs
+            }
+            PackTemplate::BlockPackStarter => vec![
+                "textures/block",
+                "textures/item",
+                "models/block",
+                "models/item",
+                "blockstates",
+                "lang",
+            ],
+            PackTemplate::FontPackStarter => vec!["font", "textures/font"],
+            PackTemplate::FullVanillaMirror => standard_directories(pack_format),
+        }
     }
 
-    for dir in directories {
+    /// 模板附带的示例文件（相对 assets/minecraft）及内容，贴图为 None 时生成占位图
+    fn starter_files(&self, pack_format: i32) -> Vec<(String, Option<serde_json::Value>)> {
+        let mut files = Vec::new();
+        let item = matches!(self, PackTemplate::ItemTextures | PackTemplate::FullVanillaMirror);
+        let block = matches!(self, PackTemplate::BlockPackStarter | PackTemplate::FullVanillaMirror);
+
+        if item {
+            files.push(("textures/item/example_item.png".to_string(), None));
+            files.push((
+                "models/item/example_item.json".to_string(),
+                Some(json!({
+                    "parent": "minecraft:item/generated",
+                    "textures": { "layer0": "minecraft:item/example_item" }
+                })),
+            ));
+        }
+        if block {
+            files.push(("textures/block/example_block.png".to_string(), None));
+            files.push((
+                "models/block/example_block.json".to_string(),
+                Some(json!({
+                    "parent": "minecraft:block/cube_all",
+                    "textures": { "all": "minecraft:block/example_block" }
+                })),
+            ));
+        }
+        if item || block {
+            // 1.13 之前的语言文件为 .lang 格式，这里只为 JSON 格式创建示例
+            if pack_format >= 4 {
+                files.push(("lang/en_us.json".to_string(), Some(json!({}))));
+            }
+        }
+        if *self == PackTemplate::FontPackStarter {
+            // 空的默认字体定义，供用户添加 provider
+            files.push(("font/default.json".to_string(), Some(json!({ "providers": [] }))));
+        }
+        files
+    }
+
+    /// 模板信息
+    pub fn info(&self) -> PackTemplateInfo {
+        let (name, description) = match self {
+            PackTemplate::Empty => ("空白材质包", "只包含 pack.mcmeta 和 pack.png"),
+            PackTemplate::MinimalGui => ("GUI 材质包", "包含 GUI 贴图目录，用于修改界面"),
+            PackTemplate::ItemTextures => {
+                ("物品材质包", "包含物品贴图和模型目录、示例物品模型及语言文件")
+            }
+            PackTemplate::BlockPackStarter => {
+                ("方块材质包", "包含方块和物品的贴图、模型及方块状态目录、示例方块模型")
+            }
+            PackTemplate::FontPackStarter => ("字体材质包", "包含字体定义和字体贴图目录"),
+            PackTemplate::FullVanillaMirror => {
+                ("完整结构材质包", "包含原版的全部资源目录及示例模型和语言文件")
+            }
+        };
+
+        PackTemplateInfo {
+            id: self.id().to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            directories: self
+                .directories(i32::MAX)
+                .into_iter()
+                .map(|dir| format!("assets/minecraft/{}", dir))
+                .collect(),
+        }
+    }
+}
+
+/// 列出所有内置模板
+pub fn list_pack_templates() -> Vec<PackTemplateInfo> {
+    PackTemplate::ALL.iter().map(|template| template.info()).collect()
+}
+
+/// 根据模板创建新的材质包，返回创建的目录和文件（相对材质包根目录）
+pub fn create_pack_from_template(
+    output_path: &Path,
+    template: PackTemplate,
+    pack_format: i32,
+    description: &str,
+) -> Result<Vec<String>, String> {
+    fs::create_dir_all(output_path)
+        .map_err(|e| format!("Failed to create pack directory: {}", e))?;
+
+    write_pack_mcmeta(output_path, pack_format, description)?;
+    create_default_pack_icon(output_path)?;
+    let mut created = vec!["pack.mcmeta".to_string(), "pack.png".to_string()];
+
+    let assets_path = output_path.join("assets").join("minecraft");
+    for dir in template.directories(pack_format) {
         fs::create_dir_all(assets_path.join(dir))
             .map_err(|e| format!("Failed to create directory {}: {}", dir, e))?;
+        created.push(format!("assets/minecraft/{}", dir));
     }
 
-    // 创建 pack.png (可选的图标)
-    create_default_pack_icon(output_path)?;
+    for (file, content) in template.starter_files(pack_format) {
+        let path = assets_path.join(&file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory for {}: {}", file, e))?;
+        }
+        match content {
+            Some(content) => fs::write(
+                &path,
+                serde_json::to_string_pretty(&content)
+                    .map_err(|e| format!("Failed to serialize {}: {}", file, e))?,
+            )
+            .map_err(|e| format!("Failed to write {}: {}", file, e))?,
+            None => create_placeholder_texture(&path)?,
+        }
+        created.push(format!("assets/minecraft/{}", file));
+    }
 
-    Ok(())
+    created.sort();
+    Ok(created)
+}
+
+/// 创建 16x16 的紫黑棋盘格占位贴图
+fn create_placeholder_texture(path: &Path) -> Result<(), String> {
+    use image::{ImageBuffer, Rgba};
+
+    let img = ImageBuffer::from_fn(16, 16, |x, y| {
+        if (x / 8 + y / 8) % 2 == 0 {
+            Rgba([248, 0, 248, 255])
+        } else {
+            Rgba([0, 0, 0, 255])
+        }
+    });
+    img.save(path)
+        .map_err(|e| format!("Failed to save placeholder texture: {}", e))
 }
 
 /// 创建默认的材质包图标
@@ -84,125 +303,113 @@ fn create_default_pack_icon(output_path: &Path) -> Result<(), String> {
     Ok(())
 }
 
-/// 为指定物品创建默认模型文件
-pub fn create_item_model(
-    pack_path: &Path,
-    item_id: &str,
-    pack_format: i32,
-) -> Result<(), String> {
-    let assets_path = pack_path.join("assets").join("minecraft");
-
+/// 物品的默认模型文件（相对材质包根目录的路径和内容）
+fn item_model_contents(item_id: &str, pack_format: i32) -> Vec<(String, serde_json::Value)> {
     if pack_format >= 35 {
         // 1.21.4+ 使用 items/ 文件夹
-        let items_path = assets_path.join("items");
-        fs::create_dir_all(&items_path)
-            .map_err(|e| format!("Failed to create items directory: {}", e))?;
-
-        let model_content = json!({
-            "model": {
-                "type": "minecraft:model",
-                "model": format!("minecraft:item/{}", item_id)
-            }
-        });
-
-        let model_path = items_path.join(format!("{}.json", item_id));
-        fs::write(
-            model_path,
-            serde_json::to_string_pretty(&model_content)
-                .map_err(|e| format!("Failed to serialize item model: {}", e))?,
-        )
-        .map_err(|e| format!("Failed to write item model: {}", e))?;
+        vec![(
+            format!("assets/minecraft/items/{}.json", item_id),
+            json!({
+                "model": {
+                    "type": "minecraft:model",
+                    "model": format!("minecraft:item/{}", item_id)
+                }
+            }),
+        )]
     } else {
         // 旧版本使用 models/item/ 文件夹
-        let models_path = assets_path.join("models").join("item");
-        fs::create_dir_all(&models_path)
-            .map_err(|e| format!("Failed to create models directory: {}", e))?;
-
-        let model_content = json!({
-            "parent": "item/generated",
-            "textures": {
-                "layer0": format!("minecraft:item/{}", item_id)
-            }
-        });
+        vec![(
+            format!("assets/minecraft/models/item/{}.json", item_id),
+            json!({
+                "parent": "item/generated",
+                "textures": {
+                    "layer0": format!("minecraft:item/{}", item_id)
+                }
+            }),
+        )]
+    }
+}
+
+/// 方块的默认方块状态、方块模型和物品模型文件（相对材质包根目录的路径和内容）
+fn block_model_contents(block_id: &str) -> Vec<(String, serde_json::Value)> {
+    vec![
+        (
+            format!("assets/minecraft/blockstates/{}.json", block_id),
+            json!({
+                "variants": {
+                    "": {
+                        "model": format!("minecraft:block/{}", block_id)
+                    }
+                }
+            }),
+        ),
+        (
+            format!("assets/minecraft/models/block/{}.json", block_id),
+            json!({
+                "parent": "block/cube_all",
+                "textures": {
+                    "all": format!("minecraft:block/{}", block_id)
+                }
+            }),
+        ),
+        // 方块的物品形式
+        (
+            format!("assets/minecraft/models/item/{}.json", block_id),
+            json!({
+                "parent": format!("minecraft:block/{}", block_id)
+            }),
+        ),
+    ]
+}
 
-        let model_path = models_path.join(format!("{}.json", item_id));
+fn write_model_files(pack_path: &Path, files: Vec<(String, serde_json::Value)>) -> Result<(), String> {
+    for (relative, content) in files {
+        let path = pack_path.join(&relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create directory for {}: {}", relative, e))?;
+        }
         fs::write(
-            model_path,
-            serde_json::to_string_pretty(&model_content)
-                .map_err(|e| format!("Failed to serialize item model: {}", e))?,
+            &path,
+            serde_json::to_string_pretty(&content)
+                .map_err(|e| format!("Failed to serialize {}: {}", relative, e))?,
         )
-        .map_err(|e| format!("Failed to write item model: {}", e))?;
+        .map_err(|e| format!("Failed to write {}: {}", relative, e))?;
     }
-
     Ok(())
 }
 
+/// create_item_model 会写入的文件（相对材质包根目录）
+pub fn item_model_files(item_id: &str, pack_format: i32) -> Vec<String> {
+    item_model_contents(item_id, pack_format)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// create_block_model 会写入的文件（相对材质包根目录）
+pub fn block_model_files(block_id: &str) -> Vec<String> {
+    block_model_contents(block_id)
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// 为指定物品创建默认模型文件
+pub fn create_item_model(
+    pack_path: &Path,
+    item_id: &str,
+    pack_format: i32,
+) -> Result<(), String> {
+    write_model_files(pack_path, item_model_contents(item_id, pack_format))
+}
+
 /// 为指定方块创建默认模型和方块状态文件
 pub fn create_block_model(
     pack_path: &Path,
     block_id: &str,
 ) -> Result<(), String> {
-    let assets_path = pack_path.join("assets").join("minecraft");
-
-    // 创建方块状态文件
-    let blockstates_path = assets_path.join("blockstates");
-    fs::create_dir_all(&blockstates_path)
-        .map_err(|e| format!("Failed to create blockstates directory: {}", e))?;
-
-    let blockstate_content = json!({
-        "variants": {
-            "": {
-                "model": format!("minecraft:block/{}", block_id)
-            }
-        }
-    });
-
-    let blockstate_path = blockstates_path.join(format!("{}.json", block_id));
-    fs::write(
-        blockstate_path,
-        serde_json::to_string_pretty(&blockstate_content)
-            .map_err(|e| format!("Failed to serialize blockstate: {}", e))?,
-    )
-    .map_err(|e| format!("Failed to write blockstate: {}", e))?;
-
-    // 创建方块模型文件
-    let models_path = assets_path.join("models").join("block");
-    fs::create_dir_all(&models_path)
-        .map_err(|e| format!("Failed to create models directory: {}", e))?;
-
-    let model_content = json!({
-        "parent": "block/cube_all",
-        "textures": {
-            "all": format!("minecraft:block/{}", block_id)
-        }
-    });
-
-    let model_path = models_path.join(format!("{}.json", block_id));
-    fs::write(
-        model_path,
-        serde_json::to_string_pretty(&model_content)
-            .map_err(|e| format!("Failed to serialize block model: {}", e))?,
-    )
-    .map_err(|e| format!("Failed to write block model: {}", e))?;
-
-    // 创建物品模型(方块的物品形式)
-    let item_models_path = assets_path.join("models").join("item");
-    fs::create_dir_all(&item_models_path)
-        .map_err(|e| format!("Failed to create item models directory: {}", e))?;
-
-    let item_model_content = json!({
-        "parent": format!("minecraft:block/{}", block_id)
-    });
-
-    let item_model_path = item_models_path.join(format!("{}.json", block_id));
-    fs::write(
-        item_model_path,
-        serde_json::to_string_pretty(&item_model_content)
-            .map_err(|e| format!("Failed to serialize item model: {}", e))?,
-    )
-    .map_err(|e| format!("Failed to write item model: {}", e))?;
-
-    Ok(())
+    write_model_files(pack_path, block_model_contents(block_id))
 }
 
 /// 批量创建物品模型
@@ -368,7 +368,7 @@ pub async fn execute_merge_async(
         .map_err(|e| format!("无法创建临时目录: {}", e))?;
 
     let zip_temp_path = temp_dir.join("result.zip");
-    create_zip(&output_base, &zip_temp_path)
+    create_zip(&output_base, &zip_temp_path, Default::default())
         .map_err(|e| format!("无法创建 ZIP: {}", e))?;
     fs::copy(&zip_temp_path, &zip_path)
         .map_err(|e| format!("无法保存 ZIP: {}", e))?;
@@ -386,6 +386,195 @@ pub async fn execute_merge_async(
     })
 }
 
+/// 合并到当前材质包时的冲突策略
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// 保留当前材质包的文件
+    KeepMine,
+    /// 用来源包的文件覆盖
+    TakeTheirs,
+    /// 两者都保留，来源包的文件以 _2、_3 等后缀重命名，贴图的 .mcmeta 随贴图一起重命名
+    KeepBoth,
+}
+
+/// 合并到当前材质包的结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackMergeReport {
+    pub dry_run: bool,
+    /// 写入当前材质包的文件（包括覆盖和重命名后的文件）
+    pub copied: Vec<String>,
+    /// 两边内容相同而未写入的文件
+    pub skipped: Vec<String>,
+    /// 两边都存在且内容不同的文件，按策略处理（KeepMine 时未写入），不会同时出现在 skipped 中
+    pub conflicted: Vec<String>,
+    /// KeepBoth 时的 (原路径, 新路径)
+    pub renamed: Vec<(String, String)>,
+}
+
+/// 将另一个材质包（zip 或文件夹）合并到 target，写入的文件记录到操作历史以便撤销；
+/// `dry_run` 为 true 时只返回合并结果而不写入
+pub fn merge_into_pack(
+    target: &Path,
+    other_pack_path: &Path,
+    policy: ConflictPolicy,
+    dry_run: bool,
+) -> Result<PackMergeReport, String> {
+    let (source_root, temp_dir) = if other_pack_path.is_file() {
+        let temp_dir = get_temp_extract_dir()
+            .join("merge_temp")
+            .join(uuid::Uuid::new_v4().to_string());
+        extract_zip(other_pack_path, &temp_dir)
+            .map_err(|e| format!("无法解压 ZIP: {}", e))?;
+        let root = find_pack_mcmeta_dir(&temp_dir).unwrap_or_else(|| temp_dir.clone());
+        (root, Some(temp_dir))
+    } else if other_pack_path.is_dir() {
+        let root = find_pack_mcmeta_dir(other_pack_path)
+            .unwrap_or_else(|| other_pack_path.to_path_buf());
+        if fs::canonicalize(&root).ok() == fs::canonicalize(target).ok() {
+            return Err("不能将材质包合并到自身".to_string());
+        }
+        (root, None)
+    } else {
+        return Err(format!("来源材质包不存在: {}", other_pack_path.display()));
+    };
+
+    let name = other_pack_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let result = merge_from_dir(target, &source_root, policy, &name, dry_run);
+    if let Some(temp_dir) = temp_dir {
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+    result
+}
+
+fn merge_from_dir(
+    target: &Path,
+    source_root: &Path,
+    policy: ConflictPolicy,
+    name: &str,
+    dry_run: bool,
+) -> Result<PackMergeReport, String> {
+    let mut report = PackMergeReport { dry_run, ..Default::default() };
+    let mut writes: Vec<(PathBuf, String)> = Vec::new();
+    // KeepBoth 时重命名过的文件，原路径 -> 新路径
+    let mut renamed_to: HashMap<String, String> = HashMap::new();
+
+    let mut files = scan_all_files(source_root)?;
+    files.sort();
+    for file in files {
+        let relative = file.strip_prefix(source_root).unwrap_or(&file);
+        // 保留当前材质包的 pack.mcmeta 和编辑器数据
+        if relative == Path::new("pack.mcmeta")
+            || crate::zip_handler::is_export_excluded(relative)
+        {
+            continue;
+        }
+        let rel = relative.to_string_lossy().replace('\\', "/");
+        let dest = target.join(relative);
+
+        // 贴图被重命名时，其 .mcmeta 跟随贴图，避免附加到当前材质包的同名贴图上
+        if let Some(owner) = rel.strip_suffix(".mcmeta").and_then(|owner| renamed_to.get(owner)) {
+            let renamed = format!("{}.mcmeta", owner);
+            if dest.exists() && !files_equal(&file, &dest) {
+                report.conflicted.push(rel.clone());
+            }
+            report.copied.push(renamed.clone());
+            report.renamed.push((rel, renamed.clone()));
+            writes.push((file, renamed));
+            continue;
+        }
+
+        if !dest.exists() {
+            report.copied.push(rel.clone());
+            writes.push((file, rel));
+            continue;
+        }
+        if files_equal(&file, &dest) {
+            report.skipped.push(rel);
+            continue;
+        }
+
+        report.conflicted.push(rel.clone());
+        match policy {
+            ConflictPolicy::KeepMine => {}
+            ConflictPolicy::TakeTheirs => {
+                report.copied.push(rel.clone());
+                writes.push((file, rel));
+            }
+            ConflictPolicy::KeepBoth => {
+                let renamed = available_copy_name(target, &rel, &writes);
+                renamed_to.insert(rel.clone(), renamed.clone());
+                report.copied.push(renamed.clone());
+                report.renamed.push((rel, renamed.clone()));
+                writes.push((file, renamed));
+            }
+        }
+    }
+
+    if dry_run || writes.is_empty() {
+        return Ok(report);
+    }
+
+    let paths: Vec<String> = writes.iter().map(|(_, rel)| rel.clone()).collect();
+    let operation =
+        crate::history_manager::begin_operation(target, format!("Merge {}", name), &paths)?;
+    let copied = writes.iter().try_for_each(|(src, rel)| {
+        let dest = target.join(rel);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("无法创建父目录: {}", e))?;
+        }
+        fs::copy(src, &dest)
+            .map(|_| ())
+            .map_err(|e| format!("无法复制文件 {}: {}", rel, e))
+    });
+    // 复制中途失败时也记录已写入的部分，保证可以撤销
+    crate::history_manager::commit_operation(target, operation)?;
+    copied?;
+
+    Ok(report)
+}
+
+fn files_equal(a: &Path, b: &Path) -> bool {
+    let same_len = match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.len() == b.len(),
+        _ => false,
+    };
+    same_len && matches!((fs::read(a), fs::read(b)), (Ok(a), Ok(b)) if a == b)
+}
+
+/// 为保留两者的冲突文件生成不与现有文件重名的路径，如 stone_2.png
+///
+/// 后缀加在第一个点之前，stone.png.mcmeta 变为 stone_2.png.mcmeta；
+/// 同时避开候选路径的 .mcmeta，使贴图的 .mcmeta 可以跟随重命名
+fn available_copy_name(target: &Path, rel: &str, writes: &[(PathBuf, String)]) -> String {
+    let (parent, file_name) = match rel.rsplit_once('/') {
+        Some((parent, file_name)) => (Some(parent), file_name),
+        None => (None, rel),
+    };
+    let (stem, ext) = match file_name.find('.') {
+        Some(dot) if dot > 0 => file_name.split_at(dot),
+        _ => (file_name, ""),
+    };
+    let taken = |candidate: &str| {
+        target.join(candidate).exists() || writes.iter().any(|(_, rel)| rel == candidate)
+    };
+
+    (2..)
+        .map(|n| {
+            let file_name = format!("{}_{}{}", stem, n, ext);
+            match parent {
+                Some(parent) => format!("{}/{}", parent, file_name),
+                None => file_name,
+            }
+        })
+        .find(|candidate| !taken(candidate) && !taken(&format!("{}.mcmeta", candidate)))
+        .unwrap_or_else(|| rel.to_string())
+}
+
 fn find_pack_mcmeta_dir(dir: &Path) -> Option<PathBuf> {
     for entry in WalkDir::new(dir)
         .max_depth(10)
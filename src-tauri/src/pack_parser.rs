@@ -28,6 +28,226 @@ pub struct PackMetaInfo {
     pub description: String,
 }
 
+/// 格式范围：单个格式、[最低, 最高] 或 {min_inclusive, max_inclusive}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FormatRange {
+    Single(i32),
+    Range([i32; 2]),
+    Bounds { min_inclusive: i32, max_inclusive: i32 },
+}
+
+impl FormatRange {
+    /// 范围是否包含指定的 pack_format
+    pub fn contains(&self, format: i32) -> bool {
+        match *self {
+            FormatRange::Single(single) => single == format,
+            FormatRange::Range([min, max])
+            | FormatRange::Bounds { min_inclusive: min, max_inclusive: max } => {
+                (min..=max).contains(&format)
+            }
+        }
+    }
+}
+
+/// overlays.entries 中的一项：在指定格式范围内额外加载的目录
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverlayEntry {
+    pub formats: FormatRange,
+    pub directory: String,
+}
+
+/// pack.mcmeta 中与多版本支持相关的字段
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackFormats {
+    pub pack_format: Option<i32>,
+    /// pack.supported_formats
+    pub supported_formats: Option<FormatRange>,
+    /// overlays.entries
+    pub overlays: Vec<OverlayEntry>,
+}
+
+impl PackFormats {
+    /// pack_format 相同或 supported_formats 覆盖时，游戏不会提示不兼容
+    pub fn supports(&self, format: i32) -> bool {
+        self.pack_format == Some(format)
+            || self
+                .supported_formats
+                .as_ref()
+                .map(|range| range.contains(format))
+                .unwrap_or(false)
+    }
+}
+
+/// 从 pack.mcmeta 中读取 supported_formats 和 overlays，无法解析的部分忽略
+pub fn parse_pack_formats(mcmeta: &serde_json::Value) -> PackFormats {
+    PackFormats {
+        pack_format: mcmeta
+            .pointer("/pack/pack_format")
+            .and_then(|v| v.as_i64())
+            .map(|v| v as i32),
+        supported_formats: mcmeta
+            .pointer("/pack/supported_formats")
+            .and_then(|v| serde_json::from_value(v.clone()).ok()),
+        overlays: mcmeta
+            .pointer("/overlays/entries")
+            .and_then(|v| v.as_array())
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter_map(|entry| serde_json::from_value(entry.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+fn read_mcmeta_value(root_path: &Path) -> Result<serde_json::Value, String> {
+    let content = std::fs::read_to_string(root_path.join("pack.mcmeta"))
+        .map_err(|e| format!("Failed to read pack.mcmeta: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse pack.mcmeta: {}", e))
+}
+
+/// 读取材质包的 supported_formats 和 overlays
+pub fn read_pack_formats(root_path: &Path) -> Result<PackFormats, String> {
+    read_mcmeta_value(root_path).map(|mcmeta| parse_pack_formats(&mcmeta))
+}
+
+fn write_mcmeta_value(root_path: &Path, mcmeta: &serde_json::Value) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(mcmeta)
+        .map_err(|e| format!("Failed to serialize pack.mcmeta: {}", e))?;
+    std::fs::write(root_path.join("pack.mcmeta"), content)
+        .map_err(|e| format!("Failed to write pack.mcmeta: {}", e))
+}
+
+/// pack.mcmeta 中 overlays.entries 的原始数组，不存在时创建
+fn overlay_entries_mut(
+    root: &mut serde_json::Map<String, serde_json::Value>,
+) -> &mut Vec<serde_json::Value> {
+    let overlays = root
+        .entry("overlays")
+        .or_insert_with(|| serde_json::json!({}));
+    if !overlays.is_object() {
+        *overlays = serde_json::json!({});
+    }
+    let entries = overlays
+        .as_object_mut()
+        .expect("overlays is an object")
+        .entry("entries")
+        .or_insert_with(|| serde_json::json!([]));
+    if !entries.is_array() {
+        *entries = serde_json::json!([]);
+    }
+    entries.as_array_mut().expect("entries is an array")
+}
+
+/// 写入 supported_formats 和 overlays，保留 pack.mcmeta 中的其他字段
+///
+/// `supported_formats` 为 None 时移除该字段。overlays 按目录名与原有条目对应，
+/// 保留原条目中未知的字段；无法解析的条目（如新版本的格式）原样保留
+pub fn write_pack_formats(
+    root_path: &Path,
+    supported_formats: Option<&FormatRange>,
+    overlays: &[OverlayEntry],
+) -> Result<PackFormats, String> {
+    let mut mcmeta = read_mcmeta_value(root_path)?;
+    let root = mcmeta.as_object_mut().ok_or("pack.mcmeta is not a JSON object")?;
+
+    let pack = root
+        .get_mut("pack")
+        .and_then(|p| p.as_object_mut())
+        .ok_or("pack.mcmeta has no pack object")?;
+    match supported_formats {
+        Some(formats) => {
+            let value = serde_json::to_value(formats)
+                .map_err(|e| format!("Failed to serialize supported_formats: {}", e))?;
+            pack.insert("supported_formats".to_string(), value);
+        }
+        None => {
+            pack.remove("supported_formats");
+        }
+    }
+
+    let existing = std::mem::take(overlay_entries_mut(root));
+    let mut entries = Vec::new();
+    for overlay in overlays {
+        let mut entry = existing
+            .iter()
+            .find(|entry| {
+                serde_json::from_value::<OverlayEntry>((*entry).clone())
+                    .is_ok_and(|parsed| parsed.directory == overlay.directory)
+            })
+            .cloned()
+            .unwrap_or_else(|| serde_json::json!({}));
+        let formats = serde_json::to_value(&overlay.formats)
+            .map_err(|e| format!("Failed to serialize overlays: {}", e))?;
+        if let Some(obj) = entry.as_object_mut() {
+            obj.insert("formats".to_string(), formats);
+            obj.insert("directory".to_string(), overlay.directory.clone().into());
+        }
+        entries.push(entry);
+    }
+    entries.extend(
+        existing
+            .into_iter()
+            .filter(|entry| serde_json::from_value::<OverlayEntry>(entry.clone()).is_err()),
+    );
+
+    if entries.is_empty() {
+        let overlays = root.get_mut("overlays").and_then(|o| o.as_object_mut());
+        if let Some(overlays) = overlays {
+            overlays.remove("entries");
+            if overlays.is_empty() {
+                root.remove("overlays");
+            }
+        }
+    } else {
+        *overlay_entries_mut(root) = entries;
+    }
+
+    write_mcmeta_value(root_path, &mcmeta)?;
+    Ok(parse_pack_formats(&mcmeta))
+}
+
+/// 注册新的 overlay 目录并创建该目录，目录名只能包含 a-z、0-9、_ 和 -
+///
+/// 直接在原有的 overlays.entries 后追加，不改动其他条目和 supported_formats
+pub fn add_pack_overlay(
+    root_path: &Path,
+    directory: &str,
+    formats: FormatRange,
+) -> Result<PackFormats, String> {
+    let valid = !directory.is_empty()
+        && directory
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-'));
+    if !valid {
+        return Err(format!("Invalid overlay directory name: {}", directory));
+    }
+
+    let mut mcmeta = read_mcmeta_value(root_path)?;
+    let root = mcmeta.as_object_mut().ok_or("pack.mcmeta is not a JSON object")?;
+    let entries = overlay_entries_mut(root);
+    let registered = entries
+        .iter()
+        .any(|entry| entry.get("directory").and_then(|d| d.as_str()) == Some(directory));
+    if registered {
+        return Err(format!("Overlay already registered: {}", directory));
+    }
+
+    let entry = serde_json::to_value(OverlayEntry {
+        formats,
+        directory: directory.to_string(),
+    })
+    .map_err(|e| format!("Failed to serialize overlays: {}", e))?;
+    entries.push(entry);
+
+    std::fs::create_dir_all(root_path.join(directory))
+        .map_err(|e| format!("Failed to create overlay directory: {}", e))?;
+    write_mcmeta_value(root_path, &mcmeta)?;
+    Ok(parse_pack_formats(&mcmeta))
+}
+
 /// 资源类型
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum ResourceType {
@@ -64,6 +284,120 @@ pub struct PackInfo {
     pub namespaces: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pack_path: Option<String>,
+    /// 导入时自动展开的顶层目录
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flattened_root: Option<String>,
+    /// 比磁盘文件更新、可恢复的草稿
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recoverable_drafts: Vec<crate::drafts::DraftInfo>,
+    /// 导入时发现的扩展名与内容不一致的图片
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub format_warnings: Vec<crate::image_handler::FormatMismatch>,
+    /// 没有 pack.mcmeta 但包含模组资源时的检测结果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mod_assets: Option<ModAssetsInfo>,
+    /// pack.supported_formats
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supported_formats: Option<FormatRange>,
+    /// overlays.entries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overlays: Vec<OverlayEntry>,
+    /// 从 ZIP 导入时的导入报告
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import_report: Option<crate::zip_handler::ImportReport>,
+}
+
+/// 从模组中解压出的资源（有 assets/<modid>/ 但没有 pack.mcmeta）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModAssetsInfo {
+    pub namespaces: Vec<String>,
+    /// 根据模组元数据文件判断的加载器：forge、neoforge、fabric、quilt
+    pub loader: Option<String>,
+}
+
+/// 模组元数据文件及对应的加载器
+pub const MOD_LOADER_MARKERS: &[(&str, &str)] = &[
+    ("META-INF/neoforge.mods.toml", "neoforge"),
+    ("META-INF/mods.toml", "forge"),
+    ("fabric.mod.json", "fabric"),
+    ("quilt.mod.json", "quilt"),
+];
+
+/// 检测目录是否为模组资源而非材质包，有 pack.mcmeta 时返回 None
+pub fn detect_mod_assets(root_path: &Path) -> Option<ModAssetsInfo> {
+    if root_path.join("pack.mcmeta").exists() {
+        return None;
+    }
+
+    let mut namespaces: Vec<String> = std::fs::read_dir(root_path.join("assets"))
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    if namespaces.is_empty() {
+        return None;
+    }
+    namespaces.sort();
+
+    let loader = MOD_LOADER_MARKERS
+        .iter()
+        .find(|(marker, _)| root_path.join(marker).is_file())
+        .map(|(_, loader)| loader.to_string());
+
+    Some(ModAssetsInfo { namespaces, loader })
+}
+
+/// 资源包 pack_format 与正式版的对应关系：(pack_format, 最早版本, 最晚版本)
+///
+/// 新版本发布时在末尾追加一行即可
+pub const PACK_FORMAT_VERSIONS: &[(i32, &str, &str)] = &[
+    (1, "1.6.1", "1.8.9"),
+    (2, "1.9", "1.10.2"),
+    (3, "1.11", "1.12.2"),
+    (4, "1.13", "1.14.4"),
+    (5, "1.15", "1.16.1"),
+    (6, "1.16.2", "1.16.5"),
+    (7, "1.17", "1.17.1"),
+    (8, "1.18", "1.18.2"),
+    (9, "1.19", "1.19.2"),
+    (12, "1.19.3", "1.19.3"),
+    (13, "1.19.4", "1.19.4"),
+    (15, "1.20", "1.20.1"),
+    (18, "1.20.2", "1.20.2"),
+    (22, "1.20.3", "1.20.4"),
+    (32, "1.20.5", "1.20.6"),
+    (34, "1.21", "1.21.1"),
+    (42, "1.21.2", "1.21.3"),
+    (46, "1.21.4", "1.21.4"),
+    (55, "1.21.5", "1.21.5"),
+    (63, "1.21.6", "1.21.6"),
+    (64, "1.21.7", "1.21.8"),
+];
+
+/// 将 "1.20.1" 解析为可比较的版本号，"1.20" 视为 "1.20.0"
+fn parse_release(version: &str) -> Option<[u32; 3]> {
+    let mut parts = [0u32; 3];
+    let mut count = 0;
+    for (i, part) in version.split('.').enumerate() {
+        *parts.get_mut(i)? = part.parse().ok()?;
+        count += 1;
+    }
+    (count >= 2).then_some(parts)
+}
+
+/// 查询正式版对应的资源包 pack_format，快照等未收录的版本返回 None
+pub fn pack_format_for_version(version_id: &str) -> Option<i32> {
+    let version = parse_release(version_id.trim())?;
+    PACK_FORMAT_VERSIONS
+        .iter()
+        .find(|(_, first, last)| {
+            matches!(
+                (parse_release(first), parse_release(last)),
+                (Some(first), Some(last)) if first <= version && version <= last
+            )
+        })
+        .map(|(format, _, _)| *format)
 }
 
 impl MinecraftVersion {
@@ -153,15 +487,19 @@ pub fn extract_namespace(path: &Path) -> Option<String> {
 pub fn scan_pack_directory(root_path: &Path) -> Result<PackInfo, String> {
     // 读取pack.mcmeta
     let mcmeta_path = root_path.join("pack.mcmeta");
+    let mut pack_formats = PackFormats::default();
     let pack_meta = if mcmeta_path.exists() {
         let content = std::fs::read_to_string(&mcmeta_path)
             .map_err(|e| format!("Failed to read pack.mcmeta: {}", e))?;
+        if let Ok(mcmeta) = serde_json::from_str::<serde_json::Value>(&content) {
+            pack_formats = parse_pack_formats(&mcmeta);
+        }
         
         // 尝试解析pack.mcmeta,如果失败则使用默认值
         match serde_json::from_str::<PackMeta>(&content) {
             Ok(meta) => meta,
             Err(e) => {
-                eprintln!("Warning: Failed to parse pack.mcmeta: {}. Using default values.", e);
+                log::warn!("Failed to parse pack.mcmeta: {}. Using default values.", e);
                 PackMeta {
                     pack: PackMetaInfo {
                         pack_format: 34,
@@ -171,7 +509,7 @@ pub fn scan_pack_directory(root_path: &Path) -> Result<PackInfo, String> {
             }
         }
     } else {
-        eprintln!("Warning: pack.mcmeta not found. Using default values.");
+        log::warn!("pack.mcmeta not found. Using default values.");
         PackMeta {
             pack: PackMetaInfo {
                 pack_format: 34,
@@ -265,5 +603,12 @@ pub fn scan_pack_directory(root_path: &Path) -> Result<PackInfo, String> {
         resources: final_resources,
         namespaces: final_namespaces,
         pack_path: None,
+        flattened_root: None,
+        recoverable_drafts: crate::drafts::list_recoverable_drafts(root_path),
+        format_warnings: Vec::new(),
+        mod_assets: detect_mod_assets(root_path),
+        supported_formats: pack_formats.supported_formats,
+        overlays: pack_formats.overlays,
+        import_report: None,
     })
 }
\ No newline at end of file
@@ -86,6 +86,17 @@ pub fn normalize_path_public(path: &Path) -> PathBuf {
     normalize_path(path)
 }
 
+/// 规范化路径，不存在的部分拼接在最近的已存在上级目录的规范路径之后
+pub fn canonical_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => canonical_path(parent).join(name),
+        _ => normalize_path(path),
+    }
+}
+
 fn normalize_path(path: &Path) -> PathBuf {
     if let Ok(canonical) = path.canonicalize() {
         return canonical;
@@ -146,4 +157,19 @@ mod tests {
         assert!(result.is_ok());
         remove_allowed_directory(Path::new("C:/plugins/myplugin"));
     }
+
+    #[test]
+    fn test_canonical_path_resolves_missing_children_under_existing_parent() {
+        let pack = std::env::temp_dir().join(format!("little100_canonical_{}", std::process::id()));
+        std::fs::create_dir_all(pack.join("assets")).unwrap();
+        let canonical = pack.canonicalize().unwrap();
+
+        assert_eq!(
+            canonical_path(&pack.join("assets/../assets/new/file.png")),
+            canonical.join("assets/new/file.png")
+        );
+        assert!(canonical_path(&pack.join("assets/sub"))
+            .starts_with(canonical_path(&pack.join("assets"))));
+        let _ = std::fs::remove_dir_all(&pack);
+    }
 }
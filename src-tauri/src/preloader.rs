@@ -1,29 +1,151 @@
 use dashmap::DashMap;
 use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::SystemTime;
 use tokio::sync::Semaphore;
 use parking_lot::RwLock;
 use lru::LruCache;
-use std::num::NonZeroUsize;
+use crate::constants::PRELOAD_THUMBNAIL_SIZE;
+
+/// 缓存的缩略图及生成时源文件的修改时间
+#[derive(Clone)]
+struct CachedThumbnail {
+    data: String,
+    modified: Option<SystemTime>,
+}
+
+/// 按最近使用顺序排列的缩略图缓存，记录已占用的字节数
+struct ThumbnailCache {
+    entries: LruCache<String, CachedThumbnail>,
+    bytes: usize,
+}
+
+fn entry_size(key: &str, entry: &CachedThumbnail) -> usize {
+    key.len() + entry.data.len()
+}
+
+impl ThumbnailCache {
+    fn new() -> Self {
+        Self {
+            entries: LruCache::unbounded(),
+            bytes: 0,
+        }
+    }
+
+    /// 插入或替换缓存项
+    fn put(&mut self, key: String, entry: CachedThumbnail) {
+        self.bytes += entry_size(&key, &entry);
+        if let Some(old) = self.entries.put(key.clone(), entry) {
+            self.bytes -= entry_size(&key, &old);
+        }
+    }
+
+    /// 淘汰最久未使用的缓存项，直到占用不超过 `max_bytes`
+    fn evict_to(&mut self, max_bytes: usize) {
+        while self.bytes > max_bytes {
+            match self.entries.pop_lru() {
+                Some((key, entry)) => self.bytes -= entry_size(&key, &entry),
+                None => break,
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+
+    /// 移除相对路径位于 `folder` 下的缓存项，返回移除的数量
+    fn remove_folder(&mut self, folder: &str) -> usize {
+        let keys: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(key, _)| is_in_folder(key, folder))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &keys {
+            if let Some(entry) = self.entries.pop(key) {
+                self.bytes -= entry_size(key, &entry);
+            }
+        }
+        keys.len()
+    }
+}
+
+/// 相对路径是否位于 `folder`（相对材质包根目录，空字符串表示根目录）下
+fn is_in_folder(relative_path: &str, folder: &str) -> bool {
+    let folder = folder.trim_matches(|c| c == '/' || c == '\\');
+    if folder.is_empty() {
+        return true;
+    }
+    let relative_path = relative_path.replace('\\', "/");
+    let folder = folder.replace('\\', "/");
+    relative_path
+        .strip_prefix(folder.as_str())
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// 文件夹预加载结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PreloadResult {
+    pub total: usize,
+    /// 本次新加载的文件数
+    pub loaded: usize,
+    /// 已在缓存中且未被修改的文件数
+    pub cached: usize,
+    pub failed: usize,
+    /// 缓存已达到字节上限而未加载的文件数
+    pub skipped: usize,
+    /// 正被其他预加载调用加载、本次未处理的文件数
+    pub in_progress: usize,
+    /// 预加载被取消，结果只包含取消前处理的文件
+    pub cancelled: bool,
+    /// 缓存已满时为新文件夹腾出空间而移除的上一个文件夹的缓存项数
+    pub evicted: usize,
+}
 
 pub struct ImagePreloader {
-    lru_cache: Arc<RwLock<LruCache<String, String>>>,
+    lru_cache: Arc<RwLock<ThumbnailCache>>,
     loading: Arc<DashMap<String, ()>>,
-    max_cache_size: usize,
+    /// 缓存字节上限
+    max_cache_bytes: Arc<AtomicUsize>,
+    /// 正在进行的激进预加载的取消标记，按文件夹区分
+    preload_cancels: Arc<DashMap<PathBuf, Arc<AtomicBool>>>,
+    /// 上一次激进预加载的文件夹（相对材质包根目录）
+    last_folder: Arc<RwLock<Option<String>>>,
+    /// 预览命令读取缓存的命中和未命中次数
+    hits: Arc<AtomicUsize>,
+    misses: Arc<AtomicUsize>,
     semaphore: Arc<Semaphore>,
 }
 
+/// 预加载缓存统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PreloaderStats {
+    pub cached_entries: usize,
+    pub loading: usize,
+    pub cache_bytes: usize,
+    pub max_cache_bytes: usize,
+    pub hits: usize,
+    pub misses: usize,
+}
+
 impl ImagePreloader {
-    pub fn new(max_cache_size: usize) -> Self {
+    /// `max_cache_bytes` 为缓存缩略图数据的字节上限
+    pub fn new(max_cache_bytes: usize) -> Self {
         let cpu_count = num_cpus::get();
         let concurrent_limit = (cpu_count * 2).max(4);
         
         Self {
-            lru_cache: Arc::new(RwLock::new(
-                LruCache::new(NonZeroUsize::new(max_cache_size).unwrap())
-            )),
+            lru_cache: Arc::new(RwLock::new(ThumbnailCache::new())),
             loading: Arc::new(DashMap::new()),
-            max_cache_size,
+            max_cache_bytes: Arc::new(AtomicUsize::new(max_cache_bytes)),
+            preload_cancels: Arc::new(DashMap::new()),
+            last_folder: Arc::new(RwLock::new(None)),
+            hits: Arc::new(AtomicUsize::new(0)),
+            misses: Arc::new(AtomicUsize::new(0)),
             semaphore: Arc::new(Semaphore::new(concurrent_limit)),
         }
     }
@@ -31,13 +153,68 @@ impl ImagePreloader {
     #[allow(dead_code)]
     pub fn get(&self, path: &str) -> Option<String> {
         let mut lru = self.lru_cache.write();
-        if let Some(data) = lru.get(path) {
-            return Some(data.clone());
+        if let Some(entry) = lru.entries.get(path) {
+            return Some(entry.data.clone());
         }
         
         None
     }
 
+    /// 读取预加载的缩略图，源文件在缓存后被修改时视为未命中，并记录命中统计
+    pub fn get_cached(&self, relative_path: &str, path: &Path) -> Option<String> {
+        let modified = file_modified(path);
+        let data = {
+            let mut cache = self.lru_cache.write();
+            cache
+                .entries
+                .get(relative_path)
+                .filter(|entry| modified.is_none() || entry.modified == modified)
+                .map(|entry| entry.data.clone())
+        };
+        self.record_lookup(data.is_some());
+        data
+    }
+
+    fn record_lookup(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 修改缓存字节上限，超出部分按最近最少使用淘汰
+    pub fn set_max_cache_bytes(&self, max_cache_bytes: usize) {
+        self.max_cache_bytes.store(max_cache_bytes, Ordering::Relaxed);
+        self.lru_cache.write().evict_to(max_cache_bytes);
+    }
+
+    /// 写入缓存，超出字节上限时淘汰最久未使用的缓存项
+    fn insert_evicting(&self, key: String, entry: CachedThumbnail) {
+        let max_bytes = self.max_cache_bytes.load(Ordering::Relaxed);
+        let mut cache = self.lru_cache.write();
+        cache.put(key, entry);
+        cache.evict_to(max_bytes);
+    }
+
+    /// 在字节上限内写入缓存，不淘汰已有缓存项；超出上限时返回 false
+    fn insert_within_budget(&self, key: String, entry: CachedThumbnail) -> bool {
+        let max_bytes = self.max_cache_bytes.load(Ordering::Relaxed);
+        let mut cache = self.lru_cache.write();
+        let replaced = cache
+            .entries
+            .peek(&key)
+            .map(|old| entry_size(&key, old))
+            .unwrap_or(0);
+        if cache.bytes - replaced + entry_size(&key, &entry) > max_bytes {
+            return false;
+        }
+        cache.put(key, entry);
+        true
+    }
+
+    /// 缓存是否已达到字节上限
+    fn is_full(&self) -> bool {
+        self.lru_cache.read().bytes >= self.max_cache_bytes.load(Ordering::Relaxed)
+    }
+
     /// 预加载单个图片
     async fn preload_image(&self, path: PathBuf, base_path: &Path, max_size: u32) -> Result<(), String> {
         let relative_path = path
@@ -47,7 +224,8 @@ impl ImagePreloader {
             .to_string();
 
         // 检查是否缓存
-        if self.lru_cache.read().peek(&relative_path).is_some() {
+        let modified = file_modified(&path);
+        if self.is_fresh(&relative_path, modified) {
             return Ok(());
         }
 
@@ -72,14 +250,13 @@ impl ImagePreloader {
 
         match rx.await {
             Ok(Ok(data)) => {
-                let mut lru = self.lru_cache.write();
-                lru.put(relative_path.clone(), data);
+                self.insert_evicting(relative_path.clone(), CachedThumbnail { data, modified });
             }
             Ok(Err(e)) => {
-                eprintln!("Failed to load image {}: {}", relative_path, e);
+                log::warn!("Failed to load image {}: {}", relative_path, e);
             }
             Err(e) => {
-                eprintln!("Channel error for {}: {}", relative_path, e);
+                log::warn!("Channel error for {}: {}", relative_path, e);
             }
         }
 
@@ -94,24 +271,8 @@ impl ImagePreloader {
         base_path: &Path,
         max_size: u32,
     ) -> Result<usize, String> {
-        use walkdir::WalkDir;
-
         // 收集所有图片文件
-        let image_files: Vec<PathBuf> = WalkDir::new(folder_path)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-            .filter(|e| {
-                if let Some(ext) = e.path().extension() {
-                    let ext_str = ext.to_string_lossy().to_lowercase();
-                    matches!(ext_str.as_str(), "png" | "jpg" | "jpeg" | "gif" | "webp")
-                } else {
-                    false
-                }
-            })
-            .map(|e| e.path().to_path_buf())
-            .collect();
+        let image_files = collect_images_recursive(folder_path);
 
         let count = image_files.len();
 
@@ -133,90 +294,507 @@ impl ImagePreloader {
         Ok(count)
     }
 
+    /// 移除文件夹（相对材质包根目录的前缀）下的缓存项，返回移除的数量
+    pub fn evict_folder(&self, folder_prefix: &str) -> usize {
+        self.lru_cache.write().remove_folder(folder_prefix)
+    }
+
+    /// 切换到新文件夹时，缓存已满则移除上一个文件夹的缓存（两者互相包含时保留），返回移除的数量
+    fn evict_previous_folder(&self, folder: &str) -> usize {
+        let previous = self.last_folder.write().replace(folder.to_string());
+        match previous {
+            Some(previous)
+                if self.is_full()
+                    && !is_in_folder(folder, &previous)
+                    && !is_in_folder(&previous, folder) =>
+            {
+                self.evict_folder(&previous)
+            }
+            _ => 0,
+        }
+    }
+
+    /// 激进预加载文件夹，开始前取消其他正在进行的激进预加载
     pub async fn preload_folder_aggressive(
         &self,
         folder_path: &Path,
         base_path: &Path,
-    ) -> Result<usize, String> {
-        use walkdir::WalkDir;
+        pool: &rayon::ThreadPool,
+    ) -> Result<PreloadResult, String> {
+        self.cancel_preload(None);
+        let folder = folder_path
+            .strip_prefix(base_path)
+            .unwrap_or(folder_path)
+            .to_string_lossy()
+            .to_string();
+        let evicted = self.evict_previous_folder(&folder);
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.preload_cancels
+            .insert(folder_path.to_path_buf(), Arc::clone(&cancel));
+
+        let image_files = collect_images_recursive(folder_path);
+
+        let mut result = self.preload_files_with(&image_files, base_path, pool, &cancel, |path| {
+            crate::image_handler::create_thumbnail(path, PRELOAD_THUMBNAIL_SIZE)
+        });
+        result.evicted = evicted;
+
+        // 只移除自己的标记，同一文件夹可能已开始新的预加载
+        self.preload_cancels
+            .remove_if(folder_path, |_, token| Arc::ptr_eq(token, &cancel));
+
+        log::info!(
+            "[预加载] 完成 {}/{} 个文件（新加载 {}，已缓存 {}，超出缓存上限跳过 {}，其他调用加载中 {}）",
+            result.loaded + result.cached,
+            result.total,
+            result.loaded,
+            result.cached,
+            result.skipped,
+            result.in_progress
+        );
+
+        Ok(result)
+    }
+
+    /// 取消指定文件夹（为 None 时为全部）正在进行的激进预加载，返回取消的数量
+    pub fn cancel_preload(&self, folder_path: Option<&Path>) -> usize {
+        let mut cancelled = 0;
+        for entry in self.preload_cancels.iter() {
+            if folder_path.map(|folder| entry.key() == folder).unwrap_or(true) {
+                entry.value().store(true, Ordering::Relaxed);
+                cancelled += 1;
+            }
+        }
+        cancelled
+    }
+
+    /// 缓存中是否有该文件且源文件未被修改
+    fn is_fresh(&self, relative_path: &str, modified: Option<SystemTime>) -> bool {
+        match self.lru_cache.read().entries.peek(relative_path) {
+            Some(entry) => modified.is_none() || entry.modified == modified,
+            None => false,
+        }
+    }
+
+    /// 并行加载文件到缓存
+    ///
+    /// 每个文件在读取前通过 `loading` 认领，已缓存（且修改时间未变）或正被其他调用加载的文件直接跳过，
+    /// 因此对同一文件夹的并发预加载不会重复读取文件；后者计入 `in_progress` 而不是 `cached`。
+    /// 缓存达到字节上限后不再淘汰已有缓存，剩余文件直接跳过；`cancel` 被设置后尚未处理的文件不再加载
+    fn preload_files_with<F>(
+        &self,
+        files: &[PathBuf],
+        base_path: &Path,
+        pool: &rayon::ThreadPool,
+        cancel: &AtomicBool,
+        load: F,
+    ) -> PreloadResult
+    where
+        F: Fn(&Path) -> Result<String, String> + Sync,
+    {
         use rayon::prelude::*;
 
-        let image_files: Vec<PathBuf> = WalkDir::new(folder_path)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-            .filter(|e| {
-                if let Some(ext) = e.path().extension() {
-                    let ext_str = ext.to_string_lossy().to_lowercase();
-                    matches!(ext_str.as_str(), "png" | "jpg" | "jpeg" | "gif" | "webp")
-                } else {
-                    false
-                }
-            })
-            .map(|e| e.path().to_path_buf())
-            .collect();
+        /// 单个文件的预加载结果
+        enum Outcome {
+            Loaded,
+            Cached,
+            Failed,
+            Skipped,
+            InProgress,
+        }
 
-        let count = image_files.len();
-        
-        let results: Vec<_> = image_files
-            .par_iter()
-            .map(|path| {
-                let relative_path = path
-                    .strip_prefix(base_path)
-                    .unwrap_or(path)
-                    .to_string_lossy()
-                    .to_string();
-
-                if self.lru_cache.read().peek(&relative_path).is_some() {
-                    return Ok(());
-                }
-
-                match crate::image_handler::create_thumbnail(path, 512) {
-                    Ok(data) => {
-                        let mut lru = self.lru_cache.write();
-                        lru.put(relative_path, data);
-                        Ok(())
+        let outcomes: Vec<Outcome> = pool.install(|| {
+            files
+                .par_iter()
+                .filter_map(|path| {
+                    if cancel.load(Ordering::Relaxed) {
+                        return None;
                     }
-                    Err(e) => Err(e),
-                }
-            })
-            .collect();
 
-        let success_count = results.iter().filter(|r| r.is_ok()).count();
-        
-        println!("[预加载] 完成 {}/{} 个文件", success_count, count);
+                    let relative_path = path
+                        .strip_prefix(base_path)
+                        .unwrap_or(path)
+                        .to_string_lossy()
+                        .to_string();
+                    let modified = file_modified(path);
+
+                    if self.is_fresh(&relative_path, modified) {
+                        return Some(Outcome::Cached);
+                    }
+
+                    if self.is_full() {
+                        return Some(Outcome::Skipped);
+                    }
+
+                    // 认领失败说明其他调用正在加载该文件
+                    if self.loading.insert(relative_path.clone(), ()).is_some() {
+                        return Some(Outcome::InProgress);
+                    }
+
+                    // 认领后再检查一次，避免刚被其他调用加载完成的文件被重复读取
+                    if self.is_fresh(&relative_path, modified) {
+                        self.loading.remove(&relative_path);
+                        return Some(Outcome::Cached);
+                    }
+
+                    let outcome = match load(path) {
+                        Ok(data) => {
+                            let entry = CachedThumbnail { data, modified };
+                            if self.insert_within_budget(relative_path.clone(), entry) {
+                                Outcome::Loaded
+                            } else {
+                                Outcome::Skipped
+                            }
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to load image {}: {}", relative_path, e);
+                            Outcome::Failed
+                        }
+                    };
+                    self.loading.remove(&relative_path);
+                    Some(outcome)
+                })
+                .collect()
+        });
 
-        Ok(success_count)
+        let mut result = PreloadResult {
+            total: files.len(),
+            cancelled: cancel.load(Ordering::Relaxed),
+            ..Default::default()
+        };
+        for outcome in outcomes {
+            match outcome {
+                Outcome::Loaded => result.loaded += 1,
+                Outcome::Cached => result.cached += 1,
+                Outcome::Failed => result.failed += 1,
+                Outcome::Skipped => result.skipped += 1,
+                Outcome::InProgress => result.in_progress += 1,
+            }
+        }
+        result
+    }
+
+    /// 批量生成缩略图，预加载尺寸的结果会读写预加载缓存，`write_disk_cache` 为 false 时不写入磁盘缓存
+    pub fn thumbnails_batch(
+        &self,
+        entries: Vec<(String, PathBuf)>,
+        base_path: &Path,
+        max_size: u32,
+        write_disk_cache: bool,
+        pool: &rayon::ThreadPool,
+    ) -> Vec<(String, Result<String, String>)> {
+        use rayon::prelude::*;
+
+        let use_cache = max_size == PRELOAD_THUMBNAIL_SIZE;
+
+        pool.install(|| {
+            entries
+                .into_par_iter()
+                .map(|(key, path)| {
+                    let relative_path = path
+                        .strip_prefix(base_path)
+                        .unwrap_or(&path)
+                        .to_string_lossy()
+                        .to_string();
+
+                    let modified = file_modified(&path);
+                    if use_cache && self.is_fresh(&relative_path, modified) {
+                        if let Some(entry) = self.lru_cache.read().entries.peek(&relative_path) {
+                            self.record_lookup(true);
+                            return (key, Ok(entry.data.clone()));
+                        }
+                    }
+                    if use_cache {
+                        self.record_lookup(false);
+                    }
+
+                    let result = crate::image_handler::create_thumbnail_disk_cached(
+                        base_path,
+                        &path,
+                        max_size,
+                        write_disk_cache,
+                    );
+                    if let (true, Ok(data)) = (use_cache, &result) {
+                        self.insert_evicting(
+                            relative_path,
+                            CachedThumbnail {
+                                data: data.clone(),
+                                modified,
+                            },
+                        );
+                    }
+                    (key, result)
+                })
+                .collect()
+        })
     }
 
     /// 获取缓存统计
     pub async fn get_stats(&self) -> (usize, usize) {
-        (self.lru_cache.read().len(), self.loading.len())
+        (self.lru_cache.read().entries.len(), self.loading.len())
+    }
+
+    /// 缓存数据占用的字节数
+    pub fn cache_bytes(&self) -> usize {
+        self.lru_cache.read().bytes
+    }
+
+    /// 缓存项数、字节数及命中统计
+    pub fn stats(&self) -> PreloaderStats {
+        let (cached_entries, cache_bytes) = {
+            let cache = self.lru_cache.read();
+            (cache.entries.len(), cache.bytes)
+        };
+        PreloaderStats {
+            cached_entries,
+            loading: self.loading.len(),
+            cache_bytes,
+            max_cache_bytes: self.max_cache_bytes.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
     }
 
-    /// 清空缓存
+    /// 清空缓存和命中统计
     pub async fn clear_cache(&self) {
         self.lru_cache.write().clear();
         self.loading.clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
     }
 }
 
+/// 文件的最后修改时间
+fn file_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// 递归收集文件夹下所有支持的图片文件
+pub fn collect_images_recursive(folder_path: &Path) -> Vec<PathBuf> {
+    use walkdir::WalkDir;
+
+    WalkDir::new(folder_path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            // 排除 .history 和 .little100
+            if let Some(name) = e.file_name().to_str() {
+                !matches!(name, ".history" | ".little100")
+            } else {
+                true
+            }
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .map(|ext| crate::constants::is_supported_image_ext(&ext.to_string_lossy()))
+                .unwrap_or(false)
+        })
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
 impl Clone for ImagePreloader {
     fn clone(&self) -> Self {
         Self {
             lru_cache: Arc::clone(&self.lru_cache),
             loading: Arc::clone(&self.loading),
-            max_cache_size: self.max_cache_size,
+            max_cache_bytes: Arc::clone(&self.max_cache_bytes),
+            preload_cancels: Arc::clone(&self.preload_cancels),
+            last_folder: Arc::clone(&self.last_folder),
+            hits: Arc::clone(&self.hits),
+            misses: Arc::clone(&self.misses),
             semaphore: Arc::clone(&self.semaphore),
         }
     }
 }
 
+/// 默认并行线程数（可用 CPU 数）
+pub fn default_parallelism() -> usize {
+    num_cpus::get()
+}
+
+/// 创建专用的 rayon 线程池，线程数限制在 1 到可用 CPU 数之间
+pub fn build_thread_pool(threads: usize) -> Result<rayon::ThreadPool, String> {
+    let threads = threads.clamp(1, default_parallelism());
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("little100-worker-{}", i))
+        .build()
+        .map_err(|e| format!("Failed to build thread pool: {}", e))
+}
+
 mod num_cpus {
     pub fn get() -> usize {
         std::thread::available_parallelism()
             .map(|n| n.get())
             .unwrap_or(4)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[test]
+    fn test_concurrent_preload_reads_each_file_once() {
+        let preloader = ImagePreloader::new(1024 * 1024);
+        let pool = build_thread_pool(4).unwrap();
+        let base = PathBuf::from("/pack");
+        let files: Vec<PathBuf> = (0..32)
+            .map(|i| base.join(format!("assets/minecraft/textures/block/{}.png", i)))
+            .collect();
+        let reads = AtomicUsize::new(0);
+
+        let load = |path: &Path| -> Result<String, String> {
+            reads.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            Ok(path.to_string_lossy().to_string())
+        };
+
+        std::thread::scope(|scope| {
+            let cancel = AtomicBool::new(false);
+            let first =
+                scope.spawn(|| preloader.preload_files_with(&files, &base, &pool, &cancel, load));
+            let second =
+                scope.spawn(|| preloader.preload_files_with(&files, &base, &pool, &cancel, load));
+            let (first, second) = (first.join().unwrap(), second.join().unwrap());
+            assert_eq!(first.loaded + second.loaded, files.len());
+            assert_eq!(first.failed + second.failed, 0);
+        });
+
+        assert_eq!(reads.load(Ordering::SeqCst), files.len());
+        assert_eq!(preloader.lru_cache.read().entries.len(), files.len());
+        assert!(preloader.loading.is_empty());
+    }
+
+    #[test]
+    fn test_files_claimed_elsewhere_are_in_progress_not_cached() {
+        let preloader = ImagePreloader::new(1024 * 1024);
+        let pool = build_thread_pool(1).unwrap();
+        let base = PathBuf::from("/pack");
+        let files = vec![base.join("a.png"), base.join("b.png")];
+        preloader.loading.insert("a.png".to_string(), ());
+
+        let cancel = AtomicBool::new(false);
+        let result = preloader.preload_files_with(&files, &base, &pool, &cancel, |_| Ok("x".into()));
+
+        assert_eq!(result.in_progress, 1);
+        assert_eq!(result.cached, 0);
+        assert_eq!(result.loaded, 1);
+    }
+
+    #[test]
+    fn test_preload_stops_at_byte_budget() {
+        let preloader = ImagePreloader::new(1000);
+        let pool = build_thread_pool(1).unwrap();
+        let base = PathBuf::from("/pack");
+        let files: Vec<PathBuf> = (0..20).map(|i| base.join(format!("{:02}.png", i))).collect();
+
+        let cancel = AtomicBool::new(false);
+        let result =
+            preloader.preload_files_with(&files, &base, &pool, &cancel, |_| Ok("x".repeat(94)));
+
+        // 每项 6 字节键名 + 94 字节数据
+        assert_eq!(result.loaded, 10);
+        assert_eq!(result.skipped, 10);
+        assert_eq!(preloader.cache_bytes(), 1000);
+    }
+
+    #[test]
+    fn test_cancel_stops_remaining_files() {
+        let preloader = ImagePreloader::new(1024 * 1024);
+        let pool = build_thread_pool(1).unwrap();
+        let base = PathBuf::from("/pack");
+        let files: Vec<PathBuf> = (0..20).map(|i| base.join(format!("{}.png", i))).collect();
+        let cancel = AtomicBool::new(false);
+        let reads = AtomicUsize::new(0);
+
+        let result = preloader.preload_files_with(&files, &base, &pool, &cancel, |path| {
+            if reads.fetch_add(1, Ordering::SeqCst) == 4 {
+                cancel.store(true, Ordering::SeqCst);
+            }
+            Ok(path.to_string_lossy().to_string())
+        });
+
+        assert!(result.cancelled);
+        assert_eq!(result.loaded, 5);
+        assert_eq!(reads.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_insert_evicts_least_recently_used() {
+        let preloader = ImagePreloader::new(300);
+        let thumbnail = |data: &str| CachedThumbnail {
+            data: data.repeat(98),
+            modified: None,
+        };
+        preloader.insert_evicting("aa".to_string(), thumbnail("a"));
+        preloader.insert_evicting("bb".to_string(), thumbnail("b"));
+        preloader.insert_evicting("cc".to_string(), thumbnail("c"));
+        assert!(preloader.get("aa").is_some());
+
+        preloader.insert_evicting("dd".to_string(), thumbnail("d"));
+
+        assert!(preloader.get("bb").is_none());
+        assert!(preloader.get("aa").is_some());
+        assert_eq!(preloader.cache_bytes(), 300);
+    }
+
+    #[test]
+    fn test_evict_folder_only_removes_matching_prefix() {
+        let preloader = ImagePreloader::new(1024 * 1024);
+        let thumbnail = || CachedThumbnail {
+            data: "x".repeat(10),
+            modified: None,
+        };
+        for key in [
+            "assets/minecraft/textures/block/stone.png",
+            "assets/minecraft/textures/block/dirt.png",
+            "assets/minecraft/textures/blocks/old.png",
+            "assets/minecraft/textures/item/stick.png",
+        ] {
+            preloader.insert_evicting(key.to_string(), thumbnail());
+        }
+
+        let evicted = preloader.evict_folder("assets/minecraft/textures/block/");
+
+        assert_eq!(evicted, 2);
+        assert!(preloader.get("assets/minecraft/textures/block/stone.png").is_none());
+        assert!(preloader.get("assets/minecraft/textures/blocks/old.png").is_some());
+        assert!(preloader.get("assets/minecraft/textures/item/stick.png").is_some());
+    }
+
+    #[test]
+    fn test_switching_folder_evicts_previous_when_full() {
+        let preloader = ImagePreloader::new(100);
+        preloader.insert_evicting("block/a.png".to_string(), CachedThumbnail {
+            data: "x".repeat(89),
+            modified: None,
+        });
+
+        assert_eq!(preloader.evict_previous_folder("block/sub"), 0);
+        assert_eq!(preloader.evict_previous_folder("block"), 0);
+        assert_eq!(preloader.evict_previous_folder("item"), 1);
+        assert_eq!(preloader.cache_bytes(), 0);
+    }
+
+    #[test]
+    fn test_get_cached_counts_hits_and_misses() {
+        let preloader = ImagePreloader::new(1024);
+        let path = PathBuf::from("/pack/missing.png");
+        preloader.insert_evicting("a.png".to_string(), CachedThumbnail {
+            data: "data".to_string(),
+            modified: None,
+        });
+
+        assert_eq!(preloader.get_cached("a.png", &path).as_deref(), Some("data"));
+        assert!(preloader.get_cached("b.png", &path).is_none());
+
+        let stats = preloader.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+}
@@ -0,0 +1,432 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 声音校验问题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoundIssue {
+    pub severity: String,
+    pub sounds_json: String,
+    pub event: String,
+    pub sound: Option<String>,
+    pub message: String,
+}
+
+/// 声音校验报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoundValidationReport {
+    pub events_checked: usize,
+    pub files_checked: usize,
+    pub issues: Vec<SoundIssue>,
+}
+
+/// sounds.json 中声音条目的完整形式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoundFile {
+    /// 声音文件路径（相对 sounds/，不含 .ogg），type 为 event 时为事件 ID
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pitch: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u32>,
+    /// 流式播放，用于音乐等较长的声音
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attenuation_distance: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preload: Option<bool>,
+    /// file 或 event
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub sound_type: Option<String>,
+    /// 未识别的字段，保存时原样写回
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// 声音条目：只写文件名的简写形式，或完整形式
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SoundEntry {
+    Name(String),
+    File(SoundFile),
+}
+
+impl SoundEntry {
+    fn name(&self) -> &str {
+        match self {
+            SoundEntry::Name(name) => name,
+            SoundEntry::File(file) => &file.name,
+        }
+    }
+
+    fn is_event(&self) -> bool {
+        match self {
+            SoundEntry::Name(_) => false,
+            SoundEntry::File(file) => file.sound_type.as_deref() == Some("event"),
+        }
+    }
+}
+
+/// 声音事件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SoundEvent {
+    /// 为 true 时替换原版的声音列表，否则追加
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replace: Option<bool>,
+    /// 字幕的翻译键
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    #[serde(default)]
+    pub sounds: Vec<SoundEntry>,
+    /// 未识别的字段，保存时原样写回
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// sounds.json：事件名到声音事件的映射，保持文件中的顺序
+pub type SoundsDef = IndexMap<String, SoundEvent>;
+
+/// 保存 sounds.json 的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SoundsUpdateResult {
+    pub path: String,
+    /// 引用的 .ogg 文件不存在等问题
+    pub issues: Vec<SoundIssue>,
+}
+
+fn sounds_json_path(pack_path: &Path, namespace: &str) -> Result<PathBuf, String> {
+    if !crate::blockstates::is_valid_namespace(namespace) {
+        return Err(format!("Invalid namespace: {}", namespace));
+    }
+    Ok(pack_path.join("assets").join(namespace).join("sounds.json"))
+}
+
+/// 读取命名空间的 sounds.json，文件不存在时返回空映射
+pub fn get_sounds_json(pack_path: &Path, namespace: &str) -> Result<SoundsDef, String> {
+    let path = sounds_json_path(pack_path, namespace)?;
+    if !path.is_file() {
+        return Ok(SoundsDef::new());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read sounds.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse sounds.json: {}", e))
+}
+
+/// 检查声音条目的名称及引用的 .ogg 文件是否存在，返回问题的严重程度和描述
+fn sound_file_issue(
+    pack_path: &Path,
+    namespace: &str,
+    sound: &SoundEntry,
+) -> Option<(&'static str, String)> {
+    let name = sound.name();
+    if name.is_empty() {
+        return Some(("error", "Sound entry has no name".to_string()));
+    }
+    // 引用其他事件，不对应文件
+    if sound.is_event() || resolve_sound_path(pack_path, namespace, name).is_file() {
+        return None;
+    }
+    // minecraft 命名空间可能引用原版文件
+    let sound_namespace = name.split_once(':').map(|(ns, _)| ns).unwrap_or(namespace);
+    let severity = if sound_namespace == "minecraft" {
+        "warning"
+    } else {
+        "error"
+    };
+    Some((severity, "Referenced .ogg file not found".to_string()))
+}
+
+/// 检查声音事件引用的 .ogg 文件是否存在
+pub fn missing_sound_files(
+    pack_path: &Path,
+    namespace: &str,
+    sounds: &SoundsDef,
+) -> Vec<SoundIssue> {
+    let sounds_json = format!("assets/{}/sounds.json", namespace);
+    let mut issues = Vec::new();
+
+    for (event_name, event) in sounds {
+        for sound in &event.sounds {
+            if let Some((severity, message)) = sound_file_issue(pack_path, namespace, sound) {
+                let name = sound.name();
+                issues.push(SoundIssue {
+                    severity: severity.to_string(),
+                    sounds_json: sounds_json.clone(),
+                    event: event_name.clone(),
+                    sound: (!name.is_empty()).then(|| name.to_string()),
+                    message,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// 保存命名空间的 sounds.json，返回引用缺失文件的问题
+pub fn update_sounds_json(
+    pack_path: &Path,
+    namespace: &str,
+    sounds: &SoundsDef,
+) -> Result<SoundsUpdateResult, String> {
+    let path = sounds_json_path(pack_path, namespace)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create namespace directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(sounds)
+        .map_err(|e| format!("Failed to serialize sounds.json: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write sounds.json: {}", e))?;
+
+    Ok(SoundsUpdateResult {
+        path: format!("assets/{}/sounds.json", namespace),
+        issues: missing_sound_files(pack_path, namespace, sounds),
+    })
+}
+
+/// 列出 assets/<ns>/sounds/ 下的 .ogg 文件，返回 sounds.json 中使用的名称（不含 .ogg）
+pub fn list_sound_files(pack_path: &Path, namespace: &str) -> Result<Vec<String>, String> {
+    let sounds_dir = sounds_json_path(pack_path, namespace)?.with_file_name("sounds");
+    let mut names: Vec<String> = WalkDir::new(&sounds_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .map(|ext| ext.eq_ignore_ascii_case("ogg"))
+                .unwrap_or(false)
+        })
+        .filter_map(|e| {
+            let rel = e.path().strip_prefix(&sounds_dir).ok()?.with_extension("");
+            Some(rel.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+/// Ogg 文件中实际的编码格式
+#[derive(Debug, Clone, PartialEq)]
+pub enum OggCodec {
+    Vorbis,
+    Opus,
+    Flac,
+    Unknown,
+    NotOgg,
+}
+
+/// 读取 Ogg 第一页判断编码格式
+pub fn detect_ogg_codec(path: &Path) -> Result<OggCodec, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut header = [0u8; 27 + 255 + 8];
+    let read = file
+        .read(&mut header)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    if read < 27 || &header[0..4] != b"OggS" {
+        return Ok(OggCodec::NotOgg);
+    }
+
+    // 第一个数据包紧跟在段表之后
+    let offset = 27 + header[26] as usize;
+    let packet = &header[offset.min(read)..read];
+
+    if packet.starts_with(b"\x01vorbis") {
+        Ok(OggCodec::Vorbis)
+    } else if packet.starts_with(b"OpusHead") {
+        Ok(OggCodec::Opus)
+    } else if packet.starts_with(b"\x7fFLAC") {
+        Ok(OggCodec::Flac)
+    } else {
+        Ok(OggCodec::Unknown)
+    }
+}
+
+/// 将声音名解析为 assets/<ns>/sounds/<path>.ogg
+pub fn resolve_sound_path(pack_path: &Path, default_namespace: &str, name: &str) -> PathBuf {
+    let (namespace, sound_path) = name.split_once(':').unwrap_or((default_namespace, name));
+    pack_path
+        .join("assets")
+        .join(namespace)
+        .join("sounds")
+        .join(format!("{}.ogg", sound_path))
+}
+
+/// 查找材质包中所有 sounds.json（返回命名空间和路径）
+pub fn find_sounds_json_files(pack_path: &Path) -> Vec<(String, PathBuf)> {
+    let assets = pack_path.join("assets");
+    let entries = match fs::read_dir(&assets) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut files: Vec<(String, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| {
+            (
+                e.file_name().to_string_lossy().to_string(),
+                e.path().join("sounds.json"),
+            )
+        })
+        .filter(|(_, path)| path.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+/// 校验所有 sounds.json 引用及 Ogg 编码
+pub fn validate_sounds(pack_path: &Path) -> Result<SoundValidationReport, String> {
+    let mut issues = Vec::new();
+    let mut events_checked = 0;
+    let mut checked_files: HashSet<PathBuf> = HashSet::new();
+
+    for (namespace, json_path) in find_sounds_json_files(pack_path) {
+        let json_rel = json_path
+            .strip_prefix(pack_path)
+            .unwrap_or(&json_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let content = fs::read_to_string(&json_path)
+            .map_err(|e| format!("Failed to read {}: {}", json_rel, e))?;
+        let sounds: SoundsDef = match serde_json::from_str(&content) {
+            Ok(sounds) => sounds,
+            Err(e) => {
+                issues.push(SoundIssue {
+                    severity: "error".to_string(),
+                    sounds_json: json_rel.clone(),
+                    event: String::new(),
+                    sound: None,
+                    message: format!("Invalid sounds.json: {}", e),
+                });
+                continue;
+            }
+        };
+
+        for (event_name, event) in &sounds {
+            events_checked += 1;
+            let mut issue = |severity: &str, sound: Option<&str>, message: String| {
+                issues.push(SoundIssue {
+                    severity: severity.to_string(),
+                    sounds_json: json_rel.clone(),
+                    event: event_name.clone(),
+                    sound: sound.map(|s| s.to_string()),
+                    message,
+                });
+            };
+
+            for sound in &event.sounds {
+                let name = sound.name();
+                if let SoundEntry::File(file) = sound {
+                    if let Some(volume) = file.volume {
+                        if volume <= 0.0 || volume > 1.0 {
+                            issue(
+                                "warning",
+                                Some(name),
+                                format!("Volume {} is outside (0, 1]", volume),
+                            );
+                        }
+                    }
+                    if let Some(pitch) = file.pitch {
+                        if !(0.5..=2.0).contains(&pitch) {
+                            issue(
+                                "warning",
+                                Some(name),
+                                format!("Pitch {} is outside [0.5, 2.0]", pitch),
+                            );
+                        }
+                    }
+                    if file.weight == Some(0) {
+                        issue("warning", Some(name), "Weight 0 is less than 1".to_string());
+                    }
+                }
+
+                if let Some((severity, message)) = sound_file_issue(pack_path, &namespace, sound) {
+                    issue(severity, (!name.is_empty()).then_some(name), message);
+                    continue;
+                }
+                if sound.is_event() {
+                    continue;
+                }
+
+                let file = resolve_sound_path(pack_path, &namespace, name);
+                if !checked_files.insert(file.clone()) {
+                    continue;
+                }
+
+                match detect_ogg_codec(&file) {
+                    Ok(OggCodec::Vorbis) => {}
+                    Ok(OggCodec::Opus) => issue(
+                        "error",
+                        Some(name),
+                        "Ogg file uses Opus, Minecraft only plays Vorbis".to_string(),
+                    ),
+                    Ok(OggCodec::Flac) => issue(
+                        "error",
+                        Some(name),
+                        "Ogg file uses FLAC, Minecraft only plays Vorbis".to_string(),
+                    ),
+                    Ok(OggCodec::Unknown) => issue(
+                        "error",
+                        Some(name),
+                        "Ogg file does not contain a Vorbis stream".to_string(),
+                    ),
+                    Ok(OggCodec::NotOgg) => issue(
+                        "error",
+                        Some(name),
+                        "File is not an Ogg container".to_string(),
+                    ),
+                    Err(e) => issue("error", Some(name), e),
+                }
+            }
+        }
+    }
+
+    Ok(SoundValidationReport {
+        events_checked,
+        files_checked: checked_files.len(),
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sounds_json_round_trip_keeps_order_and_unknown_fields() {
+        let json = r#"{"zombie.ambient":{"sounds":[{"name":"mob/zombie/say1","custom":1}],"category":"hostile"},"block.anvil.land":{"sounds":["random/anvil_land"]}}"#;
+        let sounds: SoundsDef = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            sounds.keys().collect::<Vec<_>>(),
+            vec!["zombie.ambient", "block.anvil.land"]
+        );
+
+        let value: Value = serde_json::from_str(&serde_json::to_string(&sounds).unwrap()).unwrap();
+        assert_eq!(value["zombie.ambient"]["category"], "hostile");
+        assert_eq!(value["zombie.ambient"]["sounds"][0]["custom"], 1);
+    }
+
+    #[test]
+    fn test_missing_sound_files_severity() {
+        let pack = std::env::temp_dir().join(format!("little100_sounds_{}", std::process::id()));
+        let sounds: SoundsDef = serde_json::from_str(
+            r#"{"a":{"sounds":["custom:missing","minecraft:missing",{"name":"b","type":"event"},""]}}"#,
+        )
+        .unwrap();
+
+        let issues = missing_sound_files(&pack, "custom", &sounds);
+        let severities: Vec<_> = issues.iter().map(|i| i.severity.as_str()).collect();
+        assert_eq!(severities, vec!["error", "warning", "error"]);
+        assert_eq!(issues[2].sound, None);
+    }
+}
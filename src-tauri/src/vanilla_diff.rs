@@ -0,0 +1,400 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+use zip::ZipArchive;
+
+use crate::zip_handler::is_export_excluded;
+
+/// 与原版对比的文件状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VanillaStatus {
+    Added,
+    Modified,
+    Identical,
+}
+
+/// 原版资源来源：客户端 jar 或已解压的文件夹
+pub enum VanillaSource {
+    Jar {
+        archive: ZipArchive<File>,
+        entries: HashMap<String, (usize, u64)>,
+    },
+    Folder(PathBuf),
+}
+
+impl VanillaSource {
+    /// 打开原版资源，路径为 .jar 文件或解压后的文件夹
+    pub fn open(path: &Path) -> Result<Self, String> {
+        if path.is_dir() {
+            return Ok(VanillaSource::Folder(path.to_path_buf()));
+        }
+
+        let file = File::open(path).map_err(|e| format!("Failed to open jar: {}", e))?;
+        let mut archive =
+            ZipArchive::new(file).map_err(|e| format!("Failed to read jar: {}", e))?;
+
+        let mut entries = HashMap::new();
+        for i in 0..archive.len() {
+            let entry = archive
+                .by_index_raw(i)
+                .map_err(|e| format!("Failed to read jar entry: {}", e))?;
+            if entry.is_file() {
+                entries.insert(entry.name().to_string(), (i, entry.size()));
+            }
+        }
+
+        Ok(VanillaSource::Jar { archive, entries })
+    }
+
+    /// 读取原版文件内容，不存在时返回 None
+    pub fn read(&mut self, relative: &str) -> Result<Option<Vec<u8>>, String> {
+        match self {
+            VanillaSource::Jar { archive, entries } => {
+                let index = match entries.get(relative) {
+                    Some((index, _)) => *index,
+                    None => return Ok(None),
+                };
+                let mut entry = archive
+                    .by_index(index)
+                    .map_err(|e| format!("Failed to read jar entry: {}", e))?;
+                let mut buffer = Vec::with_capacity(entry.size() as usize);
+                entry
+                    .read_to_end(&mut buffer)
+                    .map_err(|e| format!("Failed to read jar entry: {}", e))?;
+                Ok(Some(buffer))
+            }
+            VanillaSource::Folder(root) => {
+                let path = root.join(relative);
+                if !path.is_file() {
+                    return Ok(None);
+                }
+                fs::read(&path)
+                    .map(Some)
+                    .map_err(|e| format!("Failed to read {}: {}", relative, e))
+            }
+        }
+    }
+
+    /// 列出以 `prefix` 开头的原版文件
+    pub fn list(&self, prefix: &str) -> Vec<String> {
+        let mut files: Vec<String> = match self {
+            VanillaSource::Jar { entries, .. } => entries
+                .keys()
+                .filter(|name| name.starts_with(prefix))
+                .cloned()
+                .collect(),
+            VanillaSource::Folder(root) => WalkDir::new(root)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .filter_map(|e| {
+                    let rel = e.path().strip_prefix(root).ok()?;
+                    let rel = rel.to_string_lossy().replace('\\', "/");
+                    rel.starts_with(prefix).then_some(rel)
+                })
+                .collect(),
+        };
+        files.sort();
+        files
+    }
+
+    /// 原版图片尺寸，不存在或无法解析时返回 None
+    fn image_dimensions(&mut self, relative: &str) -> Result<Option<(u32, u32)>, String> {
+        let data = match self.read(relative)? {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        Ok(image::ImageReader::new(std::io::Cursor::new(data))
+            .with_guessed_format()
+            .ok()
+            .and_then(|reader| reader.into_dimensions().ok()))
+    }
+
+    /// 原版文件大小，不存在时返回 None
+    fn size(&self, relative: &str) -> Option<u64> {
+        match self {
+            VanillaSource::Jar { entries, .. } => entries.get(relative).map(|(_, size)| *size),
+            VanillaSource::Folder(root) => fs::metadata(root.join(relative))
+                .ok()
+                .filter(|m| m.is_file())
+                .map(|m| m.len()),
+        }
+    }
+
+    /// 对比材质包文件与原版，两边的文件各最多读取一次
+    pub fn diff_file(&mut self, relative: &str, pack_file: &Path) -> Result<VanillaFileDiff, String> {
+        let pack_bytes =
+            fs::read(pack_file).map_err(|e| format!("Failed to read {}: {}", relative, e))?;
+        let size = pack_bytes.len() as u64;
+
+        let (status, vanilla_sha1) = match self.size(relative) {
+            None => (VanillaStatus::Added, None),
+            Some(vanilla_size) if vanilla_size != size => (
+                VanillaStatus::Modified,
+                self.read(relative)?.map(|bytes| sha1_hex(&bytes)),
+            ),
+            Some(_) => match self.read(relative)? {
+                Some(vanilla_bytes) if vanilla_bytes == pack_bytes => (VanillaStatus::Identical, None),
+                vanilla_bytes => (
+                    VanillaStatus::Modified,
+                    vanilla_bytes.map(|bytes| sha1_hex(&bytes)),
+                ),
+            },
+        };
+
+        Ok(VanillaFileDiff {
+            path: relative.to_string(),
+            status,
+            size,
+            pack_sha1: sha1_hex(&pack_bytes),
+            vanilla_sha1,
+        })
+    }
+}
+
+/// 单个文件相对原版的差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VanillaFileDiff {
+    pub path: String,
+    pub status: VanillaStatus,
+    pub size: u64,
+    pub pack_sha1: String,
+    /// 仅 modified 时提供，便于确认两边内容不同
+    pub vanilla_sha1: Option<String>,
+}
+
+/// 相对原版的变更清单
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangeManifest {
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    pub identical: Vec<String>,
+    pub identical_bytes: u64,
+    /// 每个文件的状态、大小和 SHA1
+    pub files: Vec<VanillaFileDiff>,
+}
+
+/// 列出材质包 assets/ 下的所有文件（相对路径, 完整路径）
+pub fn collect_asset_files(pack_path: &Path) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<_> = WalkDir::new(pack_path.join("assets"))
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let rel = e.path().strip_prefix(pack_path).ok()?;
+            if is_export_excluded(rel) {
+                return None;
+            }
+            Some((rel.to_string_lossy().replace('\\', "/"), e.path().to_path_buf()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// 将材质包 assets/ 下的每个文件分类为新增、修改或与原版相同
+pub fn generate_change_manifest(
+    pack_path: &Path,
+    vanilla: &mut VanillaSource,
+) -> Result<ChangeManifest, String> {
+    let mut manifest = ChangeManifest::default();
+
+    for (relative, full_path) in collect_asset_files(pack_path) {
+        let diff = vanilla.diff_file(&relative, &full_path)?;
+        match diff.status {
+            VanillaStatus::Added => manifest.added.push(relative),
+            VanillaStatus::Modified => manifest.modified.push(relative),
+            VanillaStatus::Identical => {
+                manifest.identical_bytes += diff.size;
+                manifest.identical.push(relative);
+            }
+        }
+        manifest.files.push(diff);
+    }
+
+    Ok(manifest)
+}
+
+/// 材质包与某个原版版本的对比结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VanillaDiffReport {
+    pub version_id: String,
+    pub files: Vec<VanillaFileDiff>,
+    pub added: usize,
+    pub modified: usize,
+    /// 与原版相同的覆盖文件，可以移除
+    pub identical: usize,
+    pub identical_bytes: u64,
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    use sha1::{Digest, Sha1};
+    hex::encode(Sha1::digest(bytes))
+}
+
+/// 逐个文件对比材质包 assets/ 与原版，修改过的文件附带原版的 SHA1
+pub fn diff_against_vanilla(
+    pack_path: &Path,
+    vanilla: &mut VanillaSource,
+    version_id: &str,
+) -> Result<VanillaDiffReport, String> {
+    let manifest = generate_change_manifest(pack_path, vanilla)?;
+
+    Ok(VanillaDiffReport {
+        version_id: version_id.to_string(),
+        added: manifest.added.len(),
+        modified: manifest.modified.len(),
+        identical: manifest.identical.len(),
+        identical_bytes: manifest.identical_bytes,
+        files: manifest.files,
+    })
+}
+
+/// 移除与原版相同文件的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StripResult {
+    pub dry_run: bool,
+    pub files: Vec<String>,
+    pub files_removed: usize,
+    pub bytes_removed: u64,
+}
+
+/// 删除与原版逐字节相同的文件并记录到操作历史，可以撤销；`dry_run` 时只返回将被删除的文件
+pub fn strip_vanilla_identical(
+    pack_path: &Path,
+    vanilla: &mut VanillaSource,
+    dry_run: bool,
+) -> Result<StripResult, String> {
+    let manifest = generate_change_manifest(pack_path, vanilla)?;
+
+    if !dry_run && !manifest.identical.is_empty() {
+        let description = format!(
+            "Remove {} files identical to vanilla",
+            manifest.identical.len()
+        );
+        let operation =
+            crate::history_manager::begin_operation(pack_path, description, &manifest.identical)?;
+        let removed = manifest.identical.iter().try_for_each(|relative| {
+            fs::remove_file(pack_path.join(relative))
+                .map_err(|e| format!("Failed to delete {}: {}", relative, e))
+        });
+        // 删除中途失败时也记录已删除的部分，保证可以撤销
+        crate::history_manager::commit_operation(pack_path, operation)?;
+        removed?;
+    }
+
+    Ok(StripResult {
+        dry_run,
+        files_removed: manifest.identical.len(),
+        bytes_removed: manifest.identical_bytes,
+        files: manifest.identical,
+    })
+}
+
+const GUI_TEXTURES_PREFIX: &str = "assets/minecraft/textures/gui/";
+const GUI_SPRITES_PREFIX: &str = "assets/minecraft/textures/gui/sprites/";
+
+/// GUI 贴图在两个版本间的变化
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuiLayoutChange {
+    pub path: String,
+    /// resized：尺寸变化；split：拆分或移动到 gui/sprites/；removed：新版本中已不存在；
+    /// modified：尺寸相同但内容变化，区域可能被移动
+    pub kind: String,
+    pub from_size: Option<(u32, u32)>,
+    pub to_size: Option<(u32, u32)>,
+    /// 新版本中可能的替代贴图
+    pub replacements: Vec<String>,
+}
+
+/// GUI 布局迁移检查报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuiLayoutReport {
+    /// 材质包中覆盖了旧版本原版 GUI 贴图的文件数
+    pub checked: usize,
+    pub changes: Vec<GuiLayoutChange>,
+}
+
+/// 新版本中新增的、与旧贴图同名的 gui/sprites/ 贴图
+///
+/// 如 gui/container/inventory.png 对应 gui/sprites/container/inventory/ 下的贴图
+fn sprite_replacements(relative: &str, new_sprites: &[String]) -> Vec<String> {
+    let stem = match relative
+        .strip_prefix(GUI_TEXTURES_PREFIX)
+        .and_then(|rest| rest.strip_suffix(".png"))
+    {
+        Some(stem) => stem,
+        None => return Vec::new(),
+    };
+    let prefix = format!("{}{}", GUI_SPRITES_PREFIX, stem);
+    new_sprites
+        .iter()
+        .filter(|sprite| {
+            sprite
+                .strip_prefix(&prefix)
+                .map(|rest| rest.starts_with('/') || rest.starts_with('_') || rest == ".png")
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect()
+}
+
+/// 找出材质包中需要随版本升级调整的 GUI 贴图：原版对应贴图尺寸变化、被拆分移动或内容变化
+pub fn check_gui_layout(
+    pack_path: &Path,
+    from: &mut VanillaSource,
+    to: &mut VanillaSource,
+) -> Result<GuiLayoutReport, String> {
+    let from_sprites: std::collections::HashSet<String> =
+        from.list(GUI_SPRITES_PREFIX).into_iter().collect();
+    let new_sprites: Vec<String> = to
+        .list(GUI_SPRITES_PREFIX)
+        .into_iter()
+        .filter(|sprite| sprite.ends_with(".png") && !from_sprites.contains(sprite))
+        .collect();
+
+    let mut checked = 0;
+    let mut changes = Vec::new();
+
+    for (relative, _) in collect_asset_files(pack_path) {
+        if !relative.starts_with(GUI_TEXTURES_PREFIX) || !relative.ends_with(".png") {
+            continue;
+        }
+        // 旧版本中没有的贴图是材质包自己的内容
+        let from_size = match from.image_dimensions(&relative)? {
+            Some(size) => size,
+            None => continue,
+        };
+        checked += 1;
+
+        let to_size = to.image_dimensions(&relative)?;
+        let replacements = sprite_replacements(&relative, &new_sprites);
+
+        let kind = match to_size {
+            None if replacements.is_empty() => "removed",
+            None => "split",
+            Some(size) if size != from_size => "resized",
+            Some(_) if !replacements.is_empty() => "split",
+            Some(_) => {
+                if from.read(&relative)? == to.read(&relative)? {
+                    continue;
+                }
+                "modified"
+            }
+        };
+
+        changes.push(GuiLayoutChange {
+            path: relative,
+            kind: kind.to_string(),
+            from_size: Some(from_size),
+            to_size,
+            replacements,
+        });
+    }
+
+    Ok(GuiLayoutReport { checked, changes })
+}
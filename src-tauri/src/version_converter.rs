@@ -268,30 +268,18 @@ pub fn get_supported_versions() -> Vec<(u32, String)> {
         return versions;
     }
     
-    // 如果读取失败使用备用数据
-    vec![
-        (1, "1.6.1 – 1.8.9".to_string()),
-        (2, "1.9 – 1.10.2".to_string()),
-        (3, "1.11 – 1.12.2".to_string()),
-        (4, "1.13 – 1.14.4".to_string()),
-        (5, "1.15 – 1.16.1".to_string()),
-        (6, "1.16.2 – 1.16.5".to_string()),
-        (7, "1.17 – 1.17.1".to_string()),
-        (8, "1.18 – 1.18.2".to_string()),
-        (9, "1.19 – 1.19.2".to_string()),
-        (12, "1.19.3".to_string()),
-        (13, "1.19.4".to_string()),
-        (15, "1.20 – 1.20.1".to_string()),
-        (18, "1.20.2".to_string()),
-        (22, "1.20.3 – 1.20.4".to_string()),
-        (32, "1.20.5 – 1.20.6".to_string()),
-        (34, "1.21 – 1.21.1".to_string()),
-        (42, "1.21.2 – 1.21.3".to_string()),
-        (46, "1.21.4".to_string()),
-        (55, "1.21.5".to_string()),
-        (63, "1.21.6".to_string()),
-        (64, "1.21.7 – 1.21.8".to_string()),
-    ]
+    // 如果读取失败使用 pack_parser 中的对应表
+    crate::pack_parser::PACK_FORMAT_VERSIONS
+        .iter()
+        .map(|(format, first, last)| {
+            let label = if first == last {
+                first.to_string()
+            } else {
+                format!("{} – {}", first, last)
+            };
+            (*format as u32, label)
+        })
+        .collect()
 }
 
 /// 从文件加载版本映射
@@ -325,10 +313,10 @@ fn load_version_map_from_file() -> Result<Vec<(u32, String)>, String> {
             match load_version_map(path) {
                 Ok(versions) => {
                     let canonical_path = path.canonicalize().ok();
-                    eprintln!("✓ 成功从 {:?} 加载版本映射", canonical_path.unwrap_or_else(|| path.clone()));
+                    log::info!("✓ 成功从 {:?} 加载版本映射", canonical_path.unwrap_or_else(|| path.clone()));
                     return Ok(versions);
                 },
-                Err(e) => eprintln!("✗ 从 {:?} 加载失败: {}", path, e),
+                Err(e) => log::warn!("✗ 从 {:?} 加载失败: {}", path, e),
             }
         }
     }
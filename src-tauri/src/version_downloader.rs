@@ -1,5 +1,138 @@
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use crate::constants::{
+    DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_READ_TIMEOUT_SECS, DOWNLOAD_PROGRESS_INTERVAL_MS,
+    VERSION_DETAILS_CACHE_TTL_SECS,
+};
+use crate::error::io_error;
+
+static HTTP_CLIENT: LazyLock<RwLock<reqwest::Client>> = LazyLock::new(|| {
+    RwLock::new(
+        build_http_client(DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_READ_TIMEOUT_SECS)
+            .unwrap_or_default(),
+    )
+});
+
+/// 创建带连接超时和读取超时的 HTTP 客户端
+///
+/// 读取超时针对每次读取，持续有数据的大文件下载不会因总时长过长而中断
+fn build_http_client(connect_secs: u64, read_secs: u64) -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(connect_secs))
+        .read_timeout(Duration::from_secs(read_secs))
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+/// 获取共享的 HTTP 客户端（内部为 Arc，克隆开销很小）
+pub fn http_client() -> reqwest::Client {
+    HTTP_CLIENT.read().clone()
+}
+
+/// 修改共享 HTTP 客户端的超时设置，由前端设置通过 `set_network_timeouts` 同步
+pub fn set_http_timeouts(connect_secs: u64, read_secs: u64) -> Result<(), String> {
+    if connect_secs == 0 || read_secs == 0 {
+        return Err("Timeouts must be greater than zero".to_string());
+    }
+    *HTTP_CLIENT.write() = build_http_client(connect_secs, read_secs)?;
+    Ok(())
+}
+
+/// 格式化网络错误，超时错误以 "Request timed out" 开头以便归类
+pub fn network_error(context: &str, e: reqwest::Error) -> String {
+    if e.is_timeout() {
+        format!("Request timed out: {}: {}", context, e)
+    } else {
+        format!("{}: {}", context, e)
+    }
+}
+
+const BMCLAPI_BASE_URL: &str = "https://bmclapi2.bangbang93.com";
+
+/// 官方地址前缀及其在 BMCLAPI 镜像中对应的路径前缀
+const MIRROR_URL_PREFIXES: &[(&str, &str)] = &[
+    ("https://launchermeta.mojang.com", ""),
+    ("https://launcher.mojang.com", ""),
+    ("https://piston-meta.mojang.com", ""),
+    ("https://piston-data.mojang.com", ""),
+    ("https://resources.download.minecraft.net", "/assets"),
+    ("https://libraries.minecraft.net", "/maven"),
+];
+
+/// 下载源
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum DownloadMirror {
+    #[default]
+    Official,
+    BmclApi,
+    /// 与 BMCLAPI 路径规则相同的自定义镜像，值为镜像根地址
+    Custom(String),
+}
+
+impl DownloadMirror {
+    fn base_url(&self) -> Option<&str> {
+        match self {
+            DownloadMirror::Official => None,
+            DownloadMirror::BmclApi => Some(BMCLAPI_BASE_URL),
+            DownloadMirror::Custom(url) if !url.trim().is_empty() => {
+                Some(url.trim().trim_end_matches('/'))
+            }
+            DownloadMirror::Custom(_) => None,
+        }
+    }
+
+    /// 将官方地址改写为镜像地址，无法改写的地址原样返回
+    pub fn rewrite_url(&self, url: &str) -> String {
+        let base = match self.base_url() {
+            Some(base) => base,
+            None => return url.to_string(),
+        };
+        MIRROR_URL_PREFIXES
+            .iter()
+            .find_map(|(official, path)| {
+                url.strip_prefix(official)
+                    .map(|rest| format!("{}{}{}", base, path, rest))
+            })
+            .unwrap_or_else(|| url.to_string())
+    }
+}
+
+/// 通过镜像请求，失败时使用官方地址重试一次
+async fn with_mirror_fallback<T, F, Fut>(
+    url: &str,
+    mirror: &DownloadMirror,
+    request: F,
+) -> Result<T, String>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Result<T, String>>,
+{
+    let mirrored = mirror.rewrite_url(url);
+    if mirrored == url {
+        return request(mirrored).await;
+    }
+    match request(mirrored.clone()).await {
+        Ok(result) => Ok(result),
+        Err(e) => {
+            log::warn!("Mirror request to {} failed, retrying official source: {}", mirrored, e);
+            request(url.to_string()).await
+        }
+    }
+}
+
+/// 发送 GET 请求，非 2xx 状态码视为失败
+async fn get_checked(url: &str, context: &str) -> Result<reqwest::Response, String> {
+    http_client()
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| network_error(context, e))
+}
 
 /// 版本清单
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,83 +205,267 @@ pub struct DownloadInfo {
 const VERSION_MANIFEST_URL: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
 
 /// 获取版本清单
-pub async fn fetch_version_manifest() -> Result<VersionManifest, String> {
-    let response = reqwest::get(VERSION_MANIFEST_URL)
-        .await
-        .map_err(|e| format!("Failed to fetch version manifest: {}", e))?;
-    
-    let manifest = response
-        .json::<VersionManifest>()
-        .await
-        .map_err(|e| format!("Failed to parse version manifest: {}", e))?;
+pub async fn fetch_version_manifest(mirror: &DownloadMirror) -> Result<VersionManifest, String> {
+    with_mirror_fallback(VERSION_MANIFEST_URL, mirror, |url| async move {
+        get_checked(&url, "Failed to fetch version manifest")
+            .await?
+            .json::<VersionManifest>()
+            .await
+            .map_err(|e| format!("Failed to parse version manifest: {}", e))
+    })
+    .await
+}
+
+/// 版本详细信息的磁盘缓存目录
+fn version_details_cache_dir() -> Option<PathBuf> {
+    let exe_path = std::env::current_exe().ok()?;
+    Some(exe_path.parent()?.join(".little100").join("version_details"))
+}
+
+/// 读取版本详细信息缓存；正式版的详细信息不会变化，缓存不过期
+fn read_cached_version_details(version: &VersionInfo) -> Option<VersionDetails> {
+    let path = version_details_cache_dir()?.join(format!("{}.json", version.id));
+    let metadata = std::fs::metadata(&path).ok()?;
+    
+    if version.version_type != "release" {
+        let age = metadata.modified().ok()?.elapsed().ok()?;
+        if age > Duration::from_secs(VERSION_DETAILS_CACHE_TTL_SECS) {
+            return None;
+        }
+    }
     
-    Ok(manifest)
+    let content = std::fs::read_to_string(&path).ok()?;
+    serde_json::from_str(&content).ok()
 }
 
-/// 获取版本详细信息
-pub async fn fetch_version_details(version_url: &str) -> Result<VersionDetails, String> {
-    let response = reqwest::get(version_url)
-        .await
-        .map_err(|e| format!("Failed to fetch version details: {}", e))?;
+/// 清除版本详细信息缓存
+pub fn clear_version_details_cache() {
+    if let Some(dir) = version_details_cache_dir() {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
+
+/// 获取版本详细信息，优先使用磁盘缓存
+pub async fn fetch_version_details(
+    version: &VersionInfo,
+    mirror: &DownloadMirror,
+) -> Result<VersionDetails, String> {
+    if let Some(details) = read_cached_version_details(version) {
+        log::debug!("Using cached version details for {}", version.id);
+        return Ok(details);
+    }
     
-    let details = response
-        .json::<VersionDetails>()
-        .await
-        .map_err(|e| format!("Failed to parse version details: {}", e))?;
+    let (details, body) = with_mirror_fallback(&version.url, mirror, |url| async move {
+        let body = get_checked(&url, "Failed to fetch version details")
+            .await?
+            .text()
+            .await
+            .map_err(|e| network_error("Failed to read version details", e))?;
+        let details = serde_json::from_str::<VersionDetails>(&body)
+            .map_err(|e| format!("Failed to parse version details: {}", e))?;
+        Ok::<_, String>((details, body))
+    })
+    .await?;
+    
+    // 缓存原始 JSON
+    if let Some(dir) = version_details_cache_dir() {
+        if std::fs::create_dir_all(&dir).is_ok() {
+            let _ = std::fs::write(dir.join(format!("{}.json", version.id)), &body);
+        }
+    }
     
     Ok(details)
 }
 
-/// 下载jar文件
+fn sha1_mismatch(name: &str, expected: &str, actual: &str) -> String {
+    format!("SHA1 mismatch for {}: expected {} got {}", name, expected, actual)
+}
+
+/// 计算内容的 SHA1 并与期望值比较
+fn verify_sha1_bytes(name: &str, data: &[u8], expected: &str) -> Result<(), String> {
+    use sha1::{Digest, Sha1};
+
+    let actual = hex::encode(Sha1::digest(data));
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(sha1_mismatch(name, expected, &actual))
+    }
+}
+
+/// 流式计算文件的 SHA1 并与期望值比较
+pub fn verify_sha1(path: &Path, expected: &str) -> Result<(), String> {
+    use sha1::{Digest, Sha1};
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+    let mut hasher = Sha1::new();
+    std::io::copy(&mut file, &mut hasher)
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+
+    let actual = hex::encode(hasher.finalize());
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(sha1_mismatch(&name, expected, &actual))
+    }
+}
+
+/// 缓存的jar是否完整；校验失败时删除，以便重新下载
+fn cached_jar_is_valid(path: &Path, expected_sha1: &str) -> bool {
+    if !path.exists() {
+        return false;
+    }
+    match verify_sha1(path, expected_sha1) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Cached jar is corrupted, downloading again: {}", e);
+            let _ = std::fs::remove_file(path);
+            false
+        }
+    }
+}
+
+/// 下载jar文件，镜像下载失败时使用官方地址重新下载
+///
+/// 先写入 .part 临时文件，下载完整且 SHA1 校验通过后才重命名为 `output_path`；
+/// 中断的下载保留临时文件，`resume` 为 true 时下次从断点继续
 pub async fn download_jar_with_progress(
     download_url: &str,
     output_path: &Path,
+    on_progress: Option<&(dyn Fn(u64, u64) + Sync)>,
+    mirror: &DownloadMirror,
+    sha1: Option<&str>,
+    resume: bool,
+) -> Result<(), String> {
+    let part = part_path(output_path);
+    if !resume {
+        let _ = std::fs::remove_file(&part);
+    }
+    
+    with_mirror_fallback(download_url, mirror, |url| {
+        let part = part.clone();
+        async move {
+            download_jar_from(&url, &part, on_progress, resume).await?;
+            if let Some(expected) = sha1 {
+                if let Err(e) = verify_sha1(&part, expected) {
+                    let _ = std::fs::remove_file(&part);
+                    return Err(e);
+                }
+            }
+            Ok::<_, String>(())
+        }
+    })
+    .await?;
+    
+    std::fs::rename(&part, output_path).map_err(|e| io_error("Failed to save jar", e))
+}
+
+/// 未完成下载的临时文件路径（<文件名>.part）
+fn part_path(output_path: &Path) -> PathBuf {
+    let mut name = output_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    output_path.with_file_name(name)
+}
+
+/// 下载到 `part`，`resume` 为 true 时用 Range 请求续传已有内容
+///
+/// 服务器不支持续传（返回 200 而不是 206）时清空临时文件重新下载
+async fn download_jar_from(
+    download_url: &str,
+    part: &Path,
+    on_progress: Option<&(dyn Fn(u64, u64) + Sync)>,
+    resume: bool,
 ) -> Result<(), String> {
     use futures_util::StreamExt;
+    use reqwest::header::RANGE;
+    use reqwest::StatusCode;
     use std::io::Write;
     
     // 确保输出目录存在
-    if let Some(parent) = output_path.parent() {
+    if let Some(parent) = part.parent() {
         std::fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create directory: {}", e))?;
     }
     
+    let existing = if resume {
+        std::fs::metadata(part).map(|m| m.len()).unwrap_or(0)
+    } else {
+        0
+    };
+    
     // 下载文件
-    let response = reqwest::get(download_url)
+    let mut request = http_client().get(download_url);
+    if existing > 0 {
+        log::info!("Resuming download of {:?} from byte {}", part, existing);
+        request = request.header(RANGE, format!("bytes={}-", existing));
+    }
+    let response = request
+        .send()
         .await
-        .map_err(|e| format!("Failed to download jar: {}", e))?;
+        .map_err(|e| network_error("Failed to download jar", e))?;
     
-    let total_size = response.content_length().unwrap_or(0);
+    // 临时文件已经完整或超出服务器文件大小，重新下载
+    if existing > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        let _ = std::fs::remove_file(part);
+        return Box::pin(download_jar_from(download_url, part, on_progress, false)).await;
+    }
     
-    // 创建文件
-    let mut file = std::fs::File::create(output_path)
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+    let response = response
+        .error_for_status()
+        .map_err(|e| network_error("Failed to download jar", e))?;
+    let resumed = existing > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    let mut downloaded = if resumed { existing } else { 0 };
+    let total_size = response
+        .content_length()
+        .map(|len| len + downloaded)
+        .unwrap_or(0);
+    
+    // 续传时追加，否则截断重新写入
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(part)
+        .map_err(|e| io_error("Failed to create file", e))?;
     
     // 流式下载
     let mut stream = response.bytes_stream();
-    let mut downloaded: u64 = 0;
     
     while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("Failed to read chunk: {}", e))?;
+        let chunk = chunk.map_err(|e| network_error("Failed to read chunk", e))?;
         file.write_all(&chunk)
-            .map_err(|e| format!("Failed to write chunk: {}", e))?;
+            .map_err(|e| io_error("Failed to write chunk", e))?;
         
         downloaded += chunk.len() as u64;
         
-        // 进度
-        if total_size > 0 {
-            let progress = (downloaded as f64 / total_size as f64 * 100.0) as u32;
-            println!("Download progress: {}%", progress);
+        if let Some(on_progress) = on_progress {
+            on_progress(downloaded, total_size);
         }
     }
     
+    if total_size > 0 && downloaded < total_size {
+        return Err(format!(
+            "Download incomplete: received {} of {} bytes",
+            downloaded, total_size
+        ));
+    }
+    
     Ok(())
 }
 
 /// 获取最新的release版本并下载
-pub async fn download_latest_release(output_dir: &Path) -> Result<String, String> {
+pub async fn download_latest_release(
+    output_dir: &Path,
+    mirror: &DownloadMirror,
+) -> Result<String, String> {
     // 获取版本清单
-    let manifest = fetch_version_manifest().await?;
+    let manifest = fetch_version_manifest(mirror).await?;
     
     // 找到最新的release版本
     let latest_release = manifest.versions
@@ -157,7 +474,7 @@ pub async fn download_latest_release(output_dir: &Path) -> Result<String, String
         .ok_or("Latest release version not found")?;
     
     // 获取版本详细信息
-    let details = fetch_version_details(&latest_release.url).await?;
+    let details = fetch_version_details(latest_release, mirror).await?;
     
     // 获取客户端下载链接
     let client_download = details.downloads.client
@@ -167,23 +484,140 @@ pub async fn download_latest_release(output_dir: &Path) -> Result<String, String
     let output_path = output_dir.join(format!("{}.jar", details.id));
     
     // 检查文件是否已存在(缓存)
-    if output_path.exists() {
-        println!("Using cached jar file: {:?}", output_path);
+    if cached_jar_is_valid(&output_path, &client_download.sha1) {
+        log::info!("Using cached jar file: {:?}", output_path);
         return Ok(details.id);
     }
     
     // 下载jar文件
-    download_jar_with_progress(&client_download.url, &output_path).await?;
+    download_jar_with_progress(
+        &client_download.url,
+        &output_path,
+        None,
+        mirror,
+        Some(&client_download.sha1),
+        true,
+    )
+    .await?;
     
     Ok(details.id)
 }
-/// 下载指定版本
+/// jar 下载进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JarDownloadProgress {
+    pub version_id: String,
+    pub downloaded: u64,
+    pub total: u64,
+    pub percent: f64,
+    pub bytes_per_sec: f64,
+}
+
+/// jar 下载完成
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JarDownloadComplete {
+    pub version_id: String,
+    pub path: String,
+}
+
+/// 将下载进度以 jar-download-progress 事件发送到前端，最多每 100ms 一次
+///
+/// 不使用 download-progress，该事件已用于下载管理器的任务进度
+struct JarProgressEmitter<'a> {
+    app_handle: &'a tauri::AppHandle,
+    version_id: &'a str,
+    started: std::time::Instant,
+    /// 续传时第一次回调的已下载字节数，计算速度时扣除
+    resumed_from: std::sync::OnceLock<u64>,
+    last_emit: parking_lot::Mutex<Option<std::time::Instant>>,
+}
+
+impl<'a> JarProgressEmitter<'a> {
+    fn new(app_handle: &'a tauri::AppHandle, version_id: &'a str) -> Self {
+        Self {
+            app_handle,
+            version_id,
+            started: std::time::Instant::now(),
+            resumed_from: std::sync::OnceLock::new(),
+            last_emit: parking_lot::Mutex::new(None),
+        }
+    }
+
+    fn report(&self, downloaded: u64, total: u64) {
+        use tauri::Emitter;
+
+        let now = std::time::Instant::now();
+        let resumed_from = *self.resumed_from.get_or_init(|| downloaded);
+        {
+            let mut last_emit = self.last_emit.lock();
+            let due = last_emit
+                .map(|last| now.duration_since(last) >= Duration::from_millis(DOWNLOAD_PROGRESS_INTERVAL_MS))
+                .unwrap_or(true);
+            if !due && downloaded < total {
+                return;
+            }
+            *last_emit = Some(now);
+        }
+        
+        let elapsed = now.duration_since(self.started).as_secs_f64();
+        let progress = JarDownloadProgress {
+            version_id: self.version_id.to_string(),
+            downloaded,
+            total,
+            percent: if total > 0 { downloaded as f64 / total as f64 * 100.0 } else { 0.0 },
+            bytes_per_sec: if elapsed > 0.0 {
+                (downloaded - resumed_from) as f64 / elapsed
+            } else {
+                0.0
+            },
+        };
+        let _ = self.app_handle.emit("jar-download-progress", &progress);
+    }
+}
+
+/// 下载指定版本，传入 `app_handle` 时向前端发送下载进度和完成事件
+///
+/// `resume` 为 true 时从上次中断的位置继续下载
 pub async fn download_version(
     version_id: &str,
     output_dir: &Path,
+    mirror: &DownloadMirror,
+    app_handle: Option<&tauri::AppHandle>,
+    resume: bool,
+) -> Result<String, String> {
+    use tauri::Emitter;
+    
+    let app_handle = match app_handle {
+        Some(app_handle) => app_handle,
+        None => {
+            return download_version_with_progress(version_id, output_dir, None, mirror, resume).await
+        }
+    };
+    
+    let emitter = JarProgressEmitter::new(app_handle, version_id);
+    let report = |downloaded: u64, total: u64| emitter.report(downloaded, total);
+    let path =
+        download_version_with_progress(version_id, output_dir, Some(&report), mirror, resume).await?;
+    
+    let _ = app_handle.emit(
+        "jar-download-complete",
+        &JarDownloadComplete {
+            version_id: version_id.to_string(),
+            path: path.clone(),
+        },
+    );
+    Ok(path)
+}
+
+/// 下载指定版本，下载过程中以 (已下载字节, 总字节) 回调进度
+pub async fn download_version_with_progress(
+    version_id: &str,
+    output_dir: &Path,
+    on_progress: Option<&(dyn Fn(u64, u64) + Sync)>,
+    mirror: &DownloadMirror,
+    resume: bool,
 ) -> Result<String, String> {
     // 获取版本清单
-    let manifest = fetch_version_manifest().await?;
+    let manifest = fetch_version_manifest(mirror).await?;
     
     // 找到指定版本
     let version = manifest.versions
@@ -192,7 +626,7 @@ pub async fn download_version(
         .ok_or(format!("Version {} not found", version_id))?;
     
     // 获取版本详细信息
-    let details = fetch_version_details(&version.url).await?;
+    let details = fetch_version_details(version, mirror).await?;
     
     // 获取客户端下载链接
     let client_download = details.downloads.client
@@ -202,67 +636,310 @@ pub async fn download_version(
     let output_path = output_dir.join(format!("{}.jar", details.id));
     
     // 检查文件是否已存在(缓存)
-    if output_path.exists() {
-        println!("Using cached jar file: {:?}", output_path);
+    if cached_jar_is_valid(&output_path, &client_download.sha1) {
+        log::info!("Using cached jar file: {:?}", output_path);
         return Ok(output_path.to_string_lossy().to_string());
     }
     
     // 下载jar文件
-    download_jar_with_progress(&client_download.url, &output_path).await?;
+    download_jar_with_progress(
+        &client_download.url,
+        &output_path,
+        on_progress,
+        mirror,
+        Some(&client_download.sha1),
+        resume,
+    )
+    .await?;
     
     Ok(output_path.to_string_lossy().to_string())
 }
 
-/// 从jar文件中提取assets文件夹
-pub fn extract_assets_from_jar(jar_path: &Path, output_dir: &Path) -> Result<(), String> {
+/// 提取结果统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractionSummary {
+    pub files_extracted: usize,
+    pub bytes_written: u64,
+    pub skipped: usize,
+    /// 被跳过的不安全条目
+    pub skipped_entries: Vec<crate::zip_handler::SkippedEntry>,
+}
+
+/// 默认提取的 jar 根目录
+pub const DEFAULT_EXTRACT_ROOTS: &[&str] = &["assets/"];
+
+/// 规范化要提取的前缀，统一为以 / 结尾；为空时使用默认值
+///
+/// 前缀也可以是 jar 根目录下的单个文件，如 version.json、pack.mcmeta
+pub fn normalize_extract_roots(roots: Option<Vec<String>>) -> Vec<String> {
+    let roots: Vec<String> = roots
+        .unwrap_or_default()
+        .into_iter()
+        .map(|root| root.trim().replace('\\', "/").trim_matches('/').to_string())
+        .filter(|root| !root.is_empty())
+        .map(|root| format!("{}/", root))
+        .collect();
+
+    if roots.is_empty() {
+        DEFAULT_EXTRACT_ROOTS.iter().map(|root| root.to_string()).collect()
+    } else {
+        roots
+    }
+}
+
+/// jar 条目是否属于要提取的前缀：前缀目录下的文件，或与前缀同名的文件
+fn matches_extract_root(name: &str, root: &str) -> bool {
+    name.starts_with(root) || name == root.trim_end_matches('/')
+}
+
+/// 从jar文件中提取指定根目录（如 assets/、data/）下的文件
+///
+/// `on_progress` 以 (已处理条目数, 总条目数) 回调进度
+pub fn extract_from_jar(
+    jar_path: &Path,
+    output_dir: &Path,
+    roots: &[String],
+    on_progress: Option<&dyn Fn(usize, usize)>,
+) -> Result<ExtractionSummary, String> {
     use std::fs::File;
-    use std::io::Read;
-    use zip::ZipArchive;
     
     // 打开jar文件
-    let file = File::open(jar_path)
-        .map_err(|e| format!("Failed to open jar file: {}", e))?;
+    let mut archive = open_jar(jar_path)?;
     
-    let mut archive = ZipArchive::new(file)
-        .map_err(|e| format!("Failed to read jar archive: {}", e))?;
+    let mut summary = ExtractionSummary::default();
     
     // 遍历所有文件
-    for i in 0..archive.len() {
+    let total_entries = archive.len();
+    for i in 0..total_entries {
+        if let Some(on_progress) = on_progress {
+            on_progress(i, total_entries);
+        }
+        
         let mut file = archive.by_index(i)
             .map_err(|e| format!("Failed to read file from archive: {}", e))?;
         
-        let file_path = file.name().to_string();
+        // 只提取指定根目录下的文件
+        if !roots.iter().any(|root| matches_extract_root(file.name(), root)) {
+            continue;
+        }
         
-        // 只提取assets目录下的文件
-        if file_path.starts_with("assets/") {
-            let output_path = output_dir.join(&file_path);
-            
-            if file.is_dir() {
-                // 创建目录
-                std::fs::create_dir_all(&output_path)
-                    .map_err(|e| format!("Failed to create directory: {}", e))?;
-            } else {
-                // 确保父目录存在
-                if let Some(parent) = output_path.parent() {
-                    std::fs::create_dir_all(parent)
-                        .map_err(|e| format!("Failed to create parent directory: {}", e))?;
-                }
-                
-                // 写入文件
-                let mut output_file = File::create(&output_path)
-                    .map_err(|e| format!("Failed to create output file: {}", e))?;
-                
-                let mut buffer = Vec::new();
-                file.read_to_end(&mut buffer)
-                    .map_err(|e| format!("Failed to read file content: {}", e))?;
-                
-                std::io::Write::write_all(&mut output_file, &buffer)
-                    .map_err(|e| format!("Failed to write file: {}", e))?;
+        // 跳过不安全的路径（如包含 ..）
+        let relative = match file.enclosed_name() {
+            Some(path) => path,
+            None => {
+                log::warn!("Skipping unsafe jar entry: {}", file.name());
+                summary.skipped += 1;
+                summary.skipped_entries.push(crate::zip_handler::SkippedEntry {
+                    name: file.name().to_string(),
+                    reason: "Path escapes the extract directory".to_string(),
+                });
+                continue;
+            }
+        };
+        let output_path = output_dir.join(relative);
+        
+        if file.is_dir() {
+            // 创建目录
+            std::fs::create_dir_all(&output_path)
+                .map_err(|e| io_error("Failed to create directory", e))?;
+        } else {
+            // 确保父目录存在
+            if let Some(parent) = output_path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| io_error("Failed to create parent directory", e))?;
             }
+            
+            // 写入文件
+            let mut output_file = File::create(&output_path)
+                .map_err(|e| io_error("Failed to create output file", e))?;
+            
+            let written = std::io::copy(&mut file, &mut output_file)
+                .map_err(|e| io_error("Failed to write file", e))?;
+            
+            summary.files_extracted += 1;
+            summary.bytes_written += written;
         }
     }
     
-    Ok(())
+    if let Some(on_progress) = on_progress {
+        on_progress(total_entries, total_entries);
+    }
+    
+    // 没有任何文件说明 jar 不对或为空
+    if summary.files_extracted == 0 {
+        return Err(format!("No files found under {} in jar: {}", roots.join(", "), jar_path.display()));
+    }
+    
+    log::info!(
+        "Extracted {} files ({} bytes, {} skipped) under {} from {:?}",
+        summary.files_extracted, summary.bytes_written, summary.skipped, roots.join(", "), jar_path
+    );
+    
+    Ok(summary)
+}
+
+/// jar 中的文件条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JarEntryInfo {
+    pub path: String,
+    pub size: u64,
+}
+
+/// 打开 jar 文件
+fn open_jar(jar_path: &Path) -> Result<zip::ZipArchive<std::fs::File>, String> {
+    let file = std::fs::File::open(jar_path)
+        .map_err(|e| format!("Failed to open jar file: {}", e))?;
+    zip::ZipArchive::new(file).map_err(|e| format!("Failed to read jar archive: {}", e))
+}
+
+/// 列出 jar 中指定前缀下的文件（不解压）
+pub fn list_jar_assets(jar_path: &Path, prefix: &str) -> Result<Vec<JarEntryInfo>, String> {
+    let mut archive = open_jar(jar_path)?;
+    
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive.by_index_raw(i)
+            .map_err(|e| format!("Failed to read file from archive: {}", e))?;
+        if entry.is_file() && entry.name().starts_with(prefix) {
+            entries.push(JarEntryInfo {
+                path: entry.name().to_string(),
+                size: entry.size(),
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    
+    Ok(entries)
+}
+
+/// jar 中某类资源的统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JarCategorySummary {
+    pub category: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// 资源所属类别：textures 下按子目录细分（如 textures/block），其余按顶层目录
+fn jar_asset_category(path: &str) -> Option<String> {
+    let rest = path.strip_prefix("assets/")?;
+    let mut parts = rest.split('/').skip(1);
+    let top = parts.next()?;
+    let sub = parts.next()?;
+    
+    if top == "textures" && parts.next().is_some() {
+        Some(format!("textures/{}", sub))
+    } else {
+        Some(top.to_string())
+    }
+}
+
+/// 按资源类别统计 jar 中 assets/ 下的文件数量和大小（不解压）
+pub fn summarize_jar_assets(jar_path: &Path) -> Result<Vec<JarCategorySummary>, String> {
+    use std::collections::BTreeMap;
+    
+    let mut categories: BTreeMap<String, (usize, u64)> = BTreeMap::new();
+    for entry in list_jar_assets(jar_path, "assets/")? {
+        if let Some(category) = jar_asset_category(&entry.path) {
+            let stats = categories.entry(category).or_default();
+            stats.0 += 1;
+            stats.1 += entry.size;
+        }
+    }
+    
+    Ok(categories
+        .into_iter()
+        .map(|(category, (files, bytes))| JarCategorySummary { category, files, bytes })
+        .collect())
+}
+
+/// 从 jar 中读取单个文件的内容（不解压其他文件）
+pub fn read_asset_from_jar(jar_path: &Path, inner_path: &str) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+    
+    let mut archive = open_jar(jar_path)?;
+    let mut entry = archive.by_name(inner_path.trim_start_matches('/'))
+        .map_err(|e| format!("Failed to find {} in jar: {}", inner_path, e))?;
+    
+    let mut buffer = Vec::with_capacity(entry.size() as usize);
+    entry.read_to_end(&mut buffer)
+        .map_err(|e| format!("Failed to read {} from jar: {}", inner_path, e))?;
+    
+    Ok(buffer)
+}
+
+/// 提取的根目录是否包含 assets/
+fn includes_assets(roots: &[String]) -> bool {
+    roots.iter().any(|root| root == "assets/")
+}
+
+/// 下载并提取模板的汇总信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateSummary {
+    pub version_id: String,
+    pub jar_size: u64,
+    pub files_extracted: usize,
+    pub bytes_written: u64,
+    pub skipped: usize,
+    /// 下载的语言文件（相对输出目录）
+    pub lang_files: Vec<String>,
+    /// 当前版本没有 map.json 所用语言时，实际使用的版本
+    pub lang_fallback: Option<String>,
+}
+
+impl TemplateSummary {
+    fn new(version_id: &str, jar_path: &Path, extraction: ExtractionSummary) -> Self {
+        Self {
+            version_id: version_id.to_string(),
+            jar_size: std::fs::metadata(jar_path).map(|m| m.len()).unwrap_or(0),
+            files_extracted: extraction.files_extracted,
+            bytes_written: extraction.bytes_written,
+            skipped: extraction.skipped,
+            lang_files: Vec::new(),
+            lang_fallback: None,
+        }
+    }
+
+    /// 记录语言文件下载结果
+    fn set_lang_result(&mut self, lang_result: Result<(Vec<String>, Option<String>), String>) {
+        match lang_result {
+            Ok((lang_files, fallback)) => {
+                self.lang_files = lang_files;
+                self.lang_fallback = fallback;
+            }
+            Err(e) => log::warn!("Failed to download language file: {}", e),
+        }
+    }
+}
+
+/// 按百分比变化节流地发送阶段进度
+struct PhaseReporter<'a> {
+    manager: &'a crate::download_manager::DownloadManager,
+    task_id: &'a str,
+    phase: crate::download_manager::TemplatePhase,
+    last_percent: std::sync::atomic::AtomicU32,
+}
+
+impl<'a> PhaseReporter<'a> {
+    fn new(
+        manager: &'a crate::download_manager::DownloadManager,
+        task_id: &'a str,
+        phase: crate::download_manager::TemplatePhase,
+    ) -> Self {
+        Self {
+            manager,
+            task_id,
+            phase,
+            last_percent: std::sync::atomic::AtomicU32::new(u32::MAX),
+        }
+    }
+
+    fn report(&self, done: u64, total: u64) {
+        let percent = if total == 0 { 0 } else { (done.min(total) * 100 / total) as u32 };
+        if self.last_percent.swap(percent, std::sync::atomic::Ordering::Relaxed) != percent {
+            self.manager.emit_phase_progress(self.task_id, self.phase, percent as f64);
+        }
+    }
 }
 
 /// 检测语言文件格式
@@ -279,193 +956,323 @@ fn detect_language_file_extension(output_dir: &Path) -> String {
     "json".to_string()
 }
 
-/// 下载语言文件
-async fn download_language_file(
-    version_url: &str,
-    version_id: &str,
-    output_dir: &Path,
-    task_id: Option<String>,
-    manager: Option<crate::download_manager::DownloadManager>,
-) -> Result<(bool, bool, String), String> {
-    use std::collections::HashMap;
-    use crate::download_manager::{DownloadProgress, DownloadStatus};
-    
+/// 默认下载的语言，同时作为 .little100/map.json 的翻译来源
+pub const DEFAULT_MAP_LANGUAGE: &str = "zh_cn";
+
+/// 要下载的语言文件
+#[derive(Debug, Clone)]
+pub struct LanguageSelection {
+    /// 写入 assets/minecraft/lang/ 的语言代码
+    pub languages: Vec<String>,
+    /// 保存为 .little100/map.json 的语言，界面用它显示翻译名称
+    pub map_language: String,
+}
+
+impl LanguageSelection {
+    /// 未指定时只下载 map.json 所用的语言（默认 zh_cn）
+    pub fn new(languages: Option<Vec<String>>, map_language: Option<String>) -> Self {
+        let map_language = map_language
+            .map(|code| code.trim().to_lowercase())
+            .filter(|code| !code.is_empty())
+            .unwrap_or_else(|| DEFAULT_MAP_LANGUAGE.to_string());
+        let languages = languages.unwrap_or_else(|| vec![map_language.clone()]);
+        Self {
+            languages,
+            map_language,
+        }
+    }
+}
+
+/// 获取版本的资源索引，没有 assetIndex 时返回 None
+async fn fetch_asset_objects(
+    version: &VersionInfo,
+    mirror: &DownloadMirror,
+) -> Result<Option<std::collections::HashMap<String, AssetObject>>, String> {
     // 获取版本详细信息
-    let response = reqwest::get(version_url)
-        .await
-        .map_err(|e| format!("Failed to fetch version details: {}", e))?;
-    
-    let details: VersionDetails = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse version details: {}", e))?;
+    let details = fetch_version_details(version, mirror).await?;
     
     // 检查是否有 assetIndex
     let asset_index = match details.asset_index {
         Some(index) => index,
-        None => {
-            println!("No assetIndex found, skipping language file download");
-            return Ok((false, false, version_id.to_string()));
-        }
+        None => return Ok(None),
     };
     
     // 获取资源索引
-    let response = reqwest::get(&asset_index.url)
-        .await
-        .map_err(|e| format!("Failed to fetch asset index: {}", e))?;
+    let asset_index_json = with_mirror_fallback(&asset_index.url, mirror, |url| async move {
+        get_checked(&url, "Failed to fetch asset index")
+            .await?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| format!("Failed to parse asset index: {}", e))
+    })
+    .await?;
     
-    let assets: HashMap<String, AssetObject> = response
-        .json::<serde_json::Value>()
-        .await
-        .map_err(|e| format!("Failed to parse asset index: {}", e))?
+    asset_index_json
         .get("objects")
         .and_then(|v| serde_json::from_value(v.clone()).ok())
-        .ok_or("Failed to parse objects from asset index")?;
+        .map(Some)
+        .ok_or_else(|| "Failed to parse objects from asset index".to_string())
+}
+
+/// 在资源索引中查找语言文件，优先 .json，旧版本为 .lang
+fn find_lang_asset<'a>(
+    assets: &'a std::collections::HashMap<String, AssetObject>,
+    code: &str,
+) -> Option<(String, &'a AssetObject)> {
+    ["json", "lang"].iter().find_map(|ext| {
+        let key = format!("minecraft/lang/{}.{}", code, ext);
+        assets.get(&key).map(|asset| (key, asset))
+    })
+}
+
+/// 资源索引中的所有语言代码
+fn lang_codes(assets: &std::collections::HashMap<String, AssetObject>) -> Vec<String> {
+    let mut codes: Vec<String> = assets
+        .keys()
+        .filter_map(|key| key.strip_prefix("minecraft/lang/"))
+        .filter_map(|name| {
+            name.strip_suffix(".json")
+                .or_else(|| name.strip_suffix(".lang"))
+        })
+        .map(|code| code.to_lowercase())
+        .collect();
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+/// 下载资源索引中的单个文件并校验 SHA1，校验失败也会回退到官方地址
+async fn download_asset_object(
+    key: &str,
+    asset: &AssetObject,
+    mirror: &DownloadMirror,
+) -> Result<Vec<u8>, String> {
+    // 构建下载URL: https://resources.download.minecraft.net/{前2位}/{完整hash}
+    let hash = &asset.hash;
+    let download_url = format!(
+        "https://resources.download.minecraft.net/{}/{}",
+        &hash[0..2],
+        hash
+    );
+    
+    with_mirror_fallback(&download_url, mirror, |url| async move {
+        let content = get_checked(&url, &format!("Failed to download {}", key))
+            .await?
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", key, e))?;
+        verify_sha1_bytes(key, &content, hash)?;
+        Ok::<_, String>(content.to_vec())
+    })
+    .await
+}
+
+/// 列出版本可下载的语言代码，供前端选择
+pub async fn list_available_languages(
+    version_id: &str,
+    mirror: &DownloadMirror,
+) -> Result<Vec<String>, String> {
+    let manifest = fetch_version_manifest(mirror).await?;
+    let version = manifest.versions
+        .iter()
+        .find(|v| v.id == version_id)
+        .ok_or(format!("Version {} not found", version_id))?;
+    
+    Ok(fetch_asset_objects(version, mirror)
+        .await?
+        .map(|assets| lang_codes(&assets))
+        .unwrap_or_default())
+}
+
+/// 下载语言文件到 assets/minecraft/lang/，并将 map_language 保存为 .little100/map.json
+///
+/// 返回写入的文件和 map.json 实际来源的版本（当前版本没有该语言时使用最新 release）
+async fn download_language_files(
+    version: &VersionInfo,
+    output_dir: &Path,
+    task_id: Option<String>,
+    manager: Option<crate::download_manager::DownloadManager>,
+    mirror: &DownloadMirror,
+    selection: &LanguageSelection,
+) -> Result<(Vec<String>, Option<String>), String> {
+    use crate::download_manager::{DownloadProgress, DownloadStatus};
+    
+    let version_id = version.id.as_str();
+    let map_language = selection.map_language.as_str();
+    
+    let assets = match fetch_asset_objects(version, mirror).await? {
+        Some(assets) => assets,
+        None => {
+            log::info!("No assetIndex found, skipping language file download");
+            return Ok((Vec::new(), None));
+        }
+    };
     
     // 检测语言文件扩展名
     let lang_extension = detect_language_file_extension(output_dir);
-    println!("Detected language file extension: .{}", lang_extension);
+    log::info!("Detected language file extension: .{}", lang_extension);
     
-    // 查找中文语言文件
-    let lang_key_json = "minecraft/lang/zh_cn.json";
-    let lang_key_lang = "minecraft/lang/zh_cn.lang";
+    let lang_dir = output_dir.join("assets").join("minecraft").join("lang");
+    std::fs::create_dir_all(&lang_dir)
+        .map_err(|e| format!("Failed to create lang directory: {}", e))?;
     
-    let (lang_asset, actual_key) = if let Some(asset) = assets.get(lang_key_json) {
-        (asset, lang_key_json)
-    } else if let Some(asset) = assets.get(lang_key_lang) {
-        (asset, lang_key_lang)
-    } else {
-        // 如果当前版本没有中文文件使用最新 release版本
-        println!("Chinese language file not found for version {}, trying latest release", version_id);
-        
-        // 更新进度信息
-        if let (Some(tid), Some(mgr)) = (&task_id, &manager) {
-            mgr.update_progress(tid, DownloadProgress {
-                task_id: tid.clone(),
-                status: DownloadStatus::Downloading,
-                current: 3,
-                total: 4,
-                current_file: Some(format!("版本 {} 无中文文件，使用最新版本...", version_id)),
-                speed: 0.0,
-                eta: None,
-                error: None,
-            }).await;
+    let mut lang_files = Vec::new();
+    let mut map_content = None;
+    let mut missing = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    
+    for code in selection.languages.iter().map(|code| code.trim().to_lowercase()) {
+        if !seen.insert(code.clone()) {
+            continue;
         }
+        let (key, asset) = match find_lang_asset(&assets, &code) {
+            Some(found) => found,
+            None => {
+                missing.push(code);
+                continue;
+            }
+        };
         
-        // 获取版本清单
-        let manifest = fetch_version_manifest().await?;
-        let latest_version = manifest.versions
-            .iter()
-            .find(|v| v.id == manifest.latest.release)
-            .ok_or("Latest release version not found")?;
+        log::info!("Downloading language file: {}", key);
+        let content = download_asset_object(&key, asset, mirror).await?;
         
-        if latest_version.id == version_id {
-            return Err(format!("Chinese language file not found for version {} and latest release", version_id));
-        }
+        // 根据检测到的扩展名保存到 assets/minecraft/lang/<code>.?
+        let filename = format!("{}.{}", code, lang_extension);
+        std::fs::write(lang_dir.join(&filename), &content)
+            .map_err(|e| format!("Failed to write {}: {}", filename, e))?;
+        lang_files.push(format!("assets/minecraft/lang/{}", filename));
         
-        return Box::pin(download_language_file(&latest_version.url, &latest_version.id, output_dir, task_id, manager)).await
-            .map(|(success, _, _)| (success, true, latest_version.id.clone()));
-    };
-    
-    // 构建下载URL: https://resources.download.minecraft.net/{前2位}/{完整hash}
-    let hash = &lang_asset.hash;
-    let download_url = format!(
-        "https://resources.download.minecraft.net/{}/{}",
-        &hash[0..2],
-        hash
-    );
-    
-    println!("Downloading Chinese language file from: {}", actual_key);
+        if code == map_language {
+            map_content = Some(content);
+        }
+    }
     
-    // 下载语言文件
-    let response = reqwest::get(&download_url)
-        .await
-        .map_err(|e| format!("Failed to download language file: {}", e))?;
+    let mut map_fallback = None;
+    let map_content = match map_content {
+        Some(content) => content,
+        None => match find_lang_asset(&assets, map_language) {
+            Some((key, asset)) => download_asset_object(&key, asset, mirror).await?,
+            None => {
+                // 如果当前版本没有该语言文件使用最新 release版本
+                log::info!("Language {} not found for version {}, trying latest release", map_language, version_id);
+                
+                // 更新进度信息
+                if let (Some(tid), Some(mgr)) = (&task_id, &manager) {
+                    mgr.update_progress(tid, DownloadProgress {
+                        task_id: tid.clone(),
+                        status: DownloadStatus::Downloading,
+                        current: 3,
+                        total: 4,
+                        current_file: Some(format!("版本 {} 无 {} 语言文件，使用最新版本...", version_id, map_language)),
+                        speed: 0.0,
+                        eta: None,
+                        error: None,
+                    }).await;
+                }
+                
+                // 获取版本清单
+                let manifest = fetch_version_manifest(mirror).await?;
+                let latest_version = manifest.versions
+                    .iter()
+                    .find(|v| v.id == manifest.latest.release)
+                    .ok_or("Latest release version not found")?;
+                
+                let not_found = || format!(
+                    "Language file {} not found for version {} and latest release",
+                    map_language, version_id
+                );
+                if latest_version.id == version_id {
+                    return Err(not_found());
+                }
+                let latest_assets = fetch_asset_objects(latest_version, mirror)
+                    .await?
+                    .ok_or_else(not_found)?;
+                let (key, asset) = find_lang_asset(&latest_assets, map_language)
+                    .ok_or_else(not_found)?;
+                let content = download_asset_object(&key, asset, mirror).await?;
+                
+                // 请求下载的语言同样使用最新版本的文件
+                if missing.iter().any(|code| code == map_language) {
+                    missing.retain(|code| code != map_language);
+                    let filename = format!("{}.{}", map_language, lang_extension);
+                    std::fs::write(lang_dir.join(&filename), &content)
+                        .map_err(|e| format!("Failed to write {}: {}", filename, e))?;
+                    lang_files.push(format!("assets/minecraft/lang/{}", filename));
+                }
+                map_fallback = Some(latest_version.id.clone());
+                content
+            }
+        },
+    };
     
-    let content = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read language file: {}", e))?;
+    if !missing.is_empty() {
+        log::warn!("Language files not found for version {}: {}", version_id, missing.join(", "));
+    }
     
     // 保存为 .little100/map.json
     let little100_dir = output_dir.join(".little100");
     std::fs::create_dir_all(&little100_dir)
         .map_err(|e| format!("Failed to create .little100 directory: {}", e))?;
     
-    let map_json_path = little100_dir.join("map.json");
-    std::fs::write(&map_json_path, &content)
+    std::fs::write(little100_dir.join("map.json"), &map_content)
         .map_err(|e| format!("Failed to write map.json: {}", e))?;
+    lang_files.insert(0, ".little100/map.json".to_string());
     
-    // 根据检测到的扩展名保存到 assets/minecraft/lang/zh_cn.？
-    let lang_dir = output_dir.join("assets").join("minecraft").join("lang");
-    std::fs::create_dir_all(&lang_dir)
-        .map_err(|e| format!("Failed to create lang directory: {}", e))?;
-    
-    let zh_cn_filename = format!("zh_cn.{}", lang_extension);
-    let zh_cn_path = lang_dir.join(&zh_cn_filename);
-    std::fs::write(&zh_cn_path, &content)
-        .map_err(|e| format!("Failed to write {}: {}", zh_cn_filename, e))?;
-    
-    println!("Successfully downloaded and saved language file as {} for version {}", zh_cn_filename, version_id);
-    Ok((true, false, version_id.to_string()))
+    log::info!("Successfully downloaded {} language files for version {}", lang_files.len(), version_id);
+    Ok((lang_files, map_fallback))
 }
 
-/// 下载版本并提取assets
-#[allow(dead_code)]
+/// 下载版本并提取指定根目录下的资源
 pub async fn download_and_extract_version(
     version_id: &str,
     temp_dir: &Path,
     output_dir: &Path,
     keep_cache: bool,
-) -> Result<String, String> {
+    roots: &[String],
+    mirror: &DownloadMirror,
+    app_handle: Option<&tauri::AppHandle>,
+    languages: &LanguageSelection,
+) -> Result<TemplateSummary, String> {
     // 获取版本清单以获取版本URL
-    let manifest = fetch_version_manifest().await?;
+    let manifest = fetch_version_manifest(mirror).await?;
     let version = manifest.versions
         .iter()
         .find(|v| v.id == version_id)
         .ok_or(format!("Version {} not found", version_id))?;
     
     // 下载jar文件
-    let jar_path = download_version(version_id, temp_dir).await?;
-    
-    // 提取assets
-    extract_assets_from_jar(Path::new(&jar_path), output_dir)?;
+    let jar_path = download_version(version_id, temp_dir, mirror, app_handle, true).await?;
     
-    // 下载语言文件并返回结果
-    let lang_result = download_language_file(&version.url, version_id, output_dir, None, None).await;
+    // 提取资源
+    let extraction = extract_from_jar(Path::new(&jar_path), output_dir, roots, None)?;
+    let mut summary = TemplateSummary::new(version_id, Path::new(&jar_path), extraction);
     
-    let result_message = match lang_result {
-        Ok((_, used_latest, actual_version)) => {
-            if used_latest {
-                format!("Successfully extracted assets from version {}|LANG_FALLBACK|{}", version_id, actual_version)
-            } else {
-                format!("Successfully extracted assets from version {}", version_id)
-            }
-        },
-        Err(e) => {
-            println!("Warning: Failed to download language file: {}", e);
-            format!("Successfully extracted assets from version {}", version_id)
-        }
-    };
+    // 只在提取 assets 时下载语言文件
+    if includes_assets(roots) {
+        summary.set_lang_result(download_language_files(version, output_dir, None, None, mirror, languages).await);
+    }
     
     // 根据设置决定是否删除jar文件
     if !keep_cache {
         std::fs::remove_file(&jar_path).ok();
     }
     
-    Ok(result_message)
+    Ok(summary)
 }
 
-/// 下载版本并提取assets
+/// 下载版本并提取指定根目录下的资源
 pub async fn download_and_extract_version_with_progress(
     version_id: &str,
     temp_dir: &Path,
     output_dir: &Path,
     keep_cache: bool,
+    roots: &[String],
     task_id: String,
     manager: crate::download_manager::DownloadManager,
-) -> Result<String, String> {
-    use crate::download_manager::{DownloadProgress, DownloadStatus};
+    mirror: &DownloadMirror,
+    languages: &LanguageSelection,
+) -> Result<TemplateSummary, String> {
+    use crate::download_manager::{DownloadProgress, DownloadStatus, TemplatePhase};
     
     // 获取版本清单
     manager.update_progress(&task_id, DownloadProgress {
@@ -478,7 +1285,7 @@ pub async fn download_and_extract_version_with_progress(
         eta: None,
         error: None,
     }).await;
-    let manifest = fetch_version_manifest().await.map_err(|e| {
+    let manifest = fetch_version_manifest(mirror).await.map_err(|e| {
         let error_msg = format!("获取版本清单失败: {}", e);
         tokio::spawn({
             let manager = manager.clone();
@@ -517,7 +1324,10 @@ pub async fn download_and_extract_version_with_progress(
         eta: None,
         error: None,
     }).await;
-    let jar_path = download_version(version_id, temp_dir).await.map_err(|e| {
+    manager.emit_phase_progress(&task_id, TemplatePhase::DownloadingJar, 0.0);
+    let jar_reporter = PhaseReporter::new(&manager, &task_id, TemplatePhase::DownloadingJar);
+    let report_jar = |downloaded: u64, total: u64| jar_reporter.report(downloaded, total);
+    let jar_path = download_version_with_progress(version_id, temp_dir, Some(&report_jar), mirror, true).await.map_err(|e| {
         let error_msg = format!("下载jar文件失败: {}", e);
         tokio::spawn({
             let manager = manager.clone();
@@ -551,7 +1361,10 @@ pub async fn download_and_extract_version_with_progress(
         eta: None,
         error: None,
     }).await;
-    extract_assets_from_jar(Path::new(&jar_path), output_dir).map_err(|e| {
+    manager.emit_phase_progress(&task_id, TemplatePhase::DownloadingJar, 100.0);
+    let extract_reporter = PhaseReporter::new(&manager, &task_id, TemplatePhase::ExtractingAssets);
+    let report_extract = |done: usize, total: usize| extract_reporter.report(done as u64, total as u64);
+    let extraction = extract_from_jar(Path::new(&jar_path), output_dir, roots, Some(&report_extract)).map_err(|e| {
         let error_msg = format!("提取资源失败: {}", e);
         tokio::spawn({
             let manager = manager.clone();
@@ -574,33 +1387,27 @@ pub async fn download_and_extract_version_with_progress(
         error_msg
     })?;
     
-    // 下载语言文件
-    manager.update_progress(&task_id, DownloadProgress {
-        task_id: task_id.clone(),
-        status: DownloadStatus::Downloading,
-        current: 3,
-        total: 4,
-        current_file: Some("下载中文语言文件...".to_string()),
-        speed: 0.0,
-        eta: None,
-        error: None,
-    }).await;
-    
-    let lang_result = download_language_file(&version.url, version_id, output_dir, Some(task_id.clone()), Some(manager.clone())).await;
-    
-    let result_message = match lang_result {
-        Ok((_, used_latest, actual_version)) => {
-            if used_latest {
-                format!("Successfully extracted assets from version {}|LANG_FALLBACK|{}", version_id, actual_version)
-            } else {
-                format!("Successfully extracted assets from version {}", version_id)
-            }
-        },
-        Err(e) => {
-            println!("Warning: Failed to download language file: {}", e);
-            format!("Successfully extracted assets from version {}", version_id)
-        }
-    };
+    let mut summary = TemplateSummary::new(version_id, Path::new(&jar_path), extraction);
+    
+    // 下载语言文件（只在提取 assets 时）
+    if includes_assets(roots) {
+        manager.update_progress(&task_id, DownloadProgress {
+            task_id: task_id.clone(),
+            status: DownloadStatus::Downloading,
+            current: 3,
+            total: 4,
+            current_file: Some("下载中文语言文件...".to_string()),
+            speed: 0.0,
+            eta: None,
+            error: None,
+        }).await;
+        manager.emit_phase_progress(&task_id, TemplatePhase::DownloadingLanguageFiles, 0.0);
+        
+        summary.set_lang_result(
+            download_language_files(version, output_dir, Some(task_id.clone()), Some(manager.clone()), mirror, languages).await,
+        );
+        manager.emit_phase_progress(&task_id, TemplatePhase::DownloadingLanguageFiles, 100.0);
+    }
     
     // 根据设置决定是否删除jar文件
     if !keep_cache {
@@ -618,12 +1425,16 @@ pub async fn download_and_extract_version_with_progress(
         eta: None,
         error: None,
     }).await;
+    manager.emit_summary(&summary);
     
-    Ok(result_message)
+    Ok(summary)
 }
 
-/// 清理缓存的jar文件
+/// 清理缓存的jar文件和版本详细信息
 pub fn clear_template_cache(temp_dir: &Path) -> Result<(), String> {
+    // 同时清除版本详细信息缓存，下次下载时重新获取
+    clear_version_details_cache();
+    
     if !temp_dir.exists() {
         return Ok(());
     }
@@ -635,7 +1446,8 @@ pub fn clear_template_cache(temp_dir: &Path) -> Result<(), String> {
         let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
         let path = entry.path();
         
-        if path.extension().and_then(|s| s.to_str()) == Some("jar") {
+        // 同时清除未完成下载的 .part 文件
+        if matches!(path.extension().and_then(|s| s.to_str()), Some("jar" | "part")) {
             std::fs::remove_file(&path).ok();
         }
     }
@@ -648,28 +1460,30 @@ pub fn clear_template_cache(temp_dir: &Path) -> Result<(), String> {
 pub async fn download_minecraft_sounds(output_dir: &Path) -> Result<String, String> {
     use std::collections::HashMap;
     
-    println!("[下载声音资源] 开始下载最新版本的声音资源...");
+    log::info!("[下载声音资源] 开始下载最新版本的声音资源...");
     
-    let manifest = fetch_version_manifest().await?;
+    let manifest = fetch_version_manifest(&DownloadMirror::Official).await?;
     let latest_release = manifest.versions
         .iter()
         .find(|v| v.id == manifest.latest.release)
         .ok_or("未找到最新 release 版本")?;
     
-    println!("[下载声音资源] 最新版本: {}", latest_release.id);
+    log::info!("[下载声音资源] 最新版本: {}", latest_release.id);
     
-    let details = fetch_version_details(&latest_release.url).await?;
+    let details = fetch_version_details(latest_release, &DownloadMirror::Official).await?;
     
     // 获取资源索引
     let asset_index = details.asset_index
         .ok_or("该版本没有资源索引")?;
     
-    println!("[下载声音资源] 资源索引 ID: {}", asset_index.id);
+    log::info!("[下载声音资源] 资源索引 ID: {}", asset_index.id);
     
     // 下载资源索引文件
-    let response = reqwest::get(&asset_index.url)
+    let response = http_client()
+        .get(&asset_index.url)
+        .send()
         .await
-        .map_err(|e| format!("下载资源索引失败: {}", e))?;
+        .map_err(|e| network_error("下载资源索引失败", e))?;
     
     let assets: HashMap<String, AssetObject> = response
         .json::<serde_json::Value>()
@@ -687,16 +1501,18 @@ pub async fn download_minecraft_sounds(output_dir: &Path) -> Result<String, Stri
     let sounds_json_asset = assets.get(sounds_json_key)
         .ok_or("未找到 sounds.json")?;
     
-    println!("[下载声音资源] 下载 sounds.json...");
+    log::info!("[下载声音资源] 下载 sounds.json...");
     let sounds_json_url = format!(
         "https://resources.download.minecraft.net/{}/{}",
         &sounds_json_asset.hash[0..2],
         sounds_json_asset.hash
     );
     
-    let sounds_json_content = reqwest::get(&sounds_json_url)
+    let sounds_json_content = http_client()
+        .get(&sounds_json_url)
+        .send()
         .await
-        .map_err(|e| format!("下载 sounds.json 失败: {}", e))?
+        .map_err(|e| network_error("下载 sounds.json 失败", e))?
         .bytes()
         .await
         .map_err(|e| format!("读取 sounds.json 失败: {}", e))?;
@@ -712,7 +1528,7 @@ pub async fn download_minecraft_sounds(output_dir: &Path) -> Result<String, Stri
         .map(|(key, value)| (key.clone(), value))
         .collect();
     
-    println!("[下载声音资源] 找到 {} 个音频文件", ogg_files.len());
+    log::info!("[下载声音资源] 找到 {} 个音频文件", ogg_files.len());
     
     let sounds_dir = little100_dir.join("sounds");
     std::fs::create_dir_all(&sounds_dir)
@@ -738,7 +1554,9 @@ pub async fn download_minecraft_sounds(output_dir: &Path) -> Result<String, Stri
             asset.hash
         );
         
-        let content = reqwest::get(&url)
+        let content = http_client()
+            .get(&url)
+            .send()
             .await
             .map_err(|e| format!("下载文件失败 {}: {}", relative_path, e))?
             .bytes()
@@ -749,13 +1567,13 @@ pub async fn download_minecraft_sounds(output_dir: &Path) -> Result<String, Stri
             .map_err(|e| format!("保存文件失败 {}: {}", relative_path, e))?;
         
         if (index + 1) % 50 == 0 || index == total - 1 {
-            println!("[下载声音资源] 进度: {}/{}", index + 1, total);
+            log::debug!("[下载声音资源] 进度: {}/{}", index + 1, total);
         }
     }
     
-    println!("[下载声音资源] 下载完成！");
-    println!("[下载声音资源] sounds.json 已保存到: {:?}", sounds_json_path);
-    println!("[下载声音资源] 音频文件已保存到: {:?}", sounds_dir);
+    log::info!("[下载声音资源] 下载完成！");
+    log::info!("[下载声音资源] sounds.json 已保存到: {:?}", sounds_json_path);
+    log::info!("[下载声音资源] 音频文件已保存到: {:?}", sounds_dir);
     
     Ok(format!("成功下载 {} 的声音资源 (共 {} 个文件)", latest_release.id, total))
 }
@@ -774,19 +1592,19 @@ pub async fn download_minecraft_sounds_with_progress(
     // 限制线程数在 1-256 之间
     let concurrent_downloads = concurrent_downloads.clamp(1, 256);
     
-    println!("[下载声音资源] 开始下载最新版本的声音资源...");
+    log::info!("[下载声音资源] 开始下载最新版本的声音资源...");
     
     // 创建取消令牌
     let cancel_token = CancellationToken::new();
     manager.register_cancel_token(task_id.clone(), cancel_token.clone()).await;
     
-    let manifest = fetch_version_manifest().await?;
+    let manifest = fetch_version_manifest(&DownloadMirror::Official).await?;
     let latest_release = manifest.versions
         .iter()
         .find(|v| v.id == manifest.latest.release)
         .ok_or("未找到最新 release 版本")?;
     
-    println!("[下载声音资源] 最新版本: {}", latest_release.id);
+    log::info!("[下载声音资源] 最新版本: {}", latest_release.id);
     
     manager.update_progress(&task_id, crate::download_manager::DownloadProgress {
         task_id: task_id.clone(),
@@ -804,12 +1622,12 @@ pub async fn download_minecraft_sounds_with_progress(
         return Err("下载已取消".to_string());
     }
     
-    let details = fetch_version_details(&latest_release.url).await?;
+    let details = fetch_version_details(latest_release, &DownloadMirror::Official).await?;
     
     let asset_index = details.asset_index
         .ok_or("该版本没有资源索引")?;
     
-    println!("[下载声音资源] 资源索引 ID: {}", asset_index.id);
+    log::info!("[下载声音资源] 资源索引 ID: {}", asset_index.id);
     
     manager.update_progress(&task_id, crate::download_manager::DownloadProgress {
         task_id: task_id.clone(),
@@ -822,9 +1640,11 @@ pub async fn download_minecraft_sounds_with_progress(
         error: None,
     }).await;
     
-    let response = reqwest::get(&asset_index.url)
+    let response = http_client()
+        .get(&asset_index.url)
+        .send()
         .await
-        .map_err(|e| format!("下载资源索引失败: {}", e))?;
+        .map_err(|e| network_error("下载资源索引失败", e))?;
     
     let assets: HashMap<String, AssetObject> = response
         .json::<serde_json::Value>()
@@ -859,16 +1679,18 @@ pub async fn download_minecraft_sounds_with_progress(
         error: None,
     }).await;
     
-    println!("[下载声音资源] 下载 sounds.json...");
+    log::info!("[下载声音资源] 下载 sounds.json...");
     let sounds_json_url = format!(
         "https://resources.download.minecraft.net/{}/{}",
         &sounds_json_asset.hash[0..2],
         sounds_json_asset.hash
     );
     
-    let sounds_json_content = reqwest::get(&sounds_json_url)
+    let sounds_json_content = http_client()
+        .get(&sounds_json_url)
+        .send()
         .await
-        .map_err(|e| format!("下载 sounds.json 失败: {}", e))?
+        .map_err(|e| network_error("下载 sounds.json 失败", e))?
         .bytes()
         .await
         .map_err(|e| format!("读取 sounds.json 失败: {}", e))?;
@@ -890,7 +1712,7 @@ pub async fn download_minecraft_sounds_with_progress(
         .map(|(key, value)| (key.clone(), value))
         .collect();
     
-    println!("[下载声音资源] 找到 {} 个音频文件", ogg_files.len());
+    log::info!("[下载声音资源] 找到 {} 个音频文件", ogg_files.len());
     
     // 载所有 .ogg 文件
     let sounds_dir = little100_dir.join("sounds");
@@ -948,7 +1770,7 @@ pub async fn download_minecraft_sounds_with_progress(
                 let mut retry_count = 0;
                 let max_retries = 3;
                 let content = loop {
-                    match reqwest::get(&url).await {
+                    match http_client().get(&url).send().await {
                         Ok(response) => {
                             match response.bytes().await {
                                 Ok(bytes) => break bytes,
@@ -1009,7 +1831,7 @@ pub async fn download_minecraft_sounds_with_progress(
                 }).await;
                 
                 if current % 50 == 0 || current == total {
-                    println!("[下载声音资源] 进度: {}/{}", current, total);
+                    log::debug!("[下载声音资源] 进度: {}/{}", current, total);
                 }
                 
                 Ok::<(), String>(())
@@ -1025,9 +1847,9 @@ pub async fn download_minecraft_sounds_with_progress(
         result?;
     }
     
-    println!("[下载声音资源] 下载完成！");
-    println!("[下载声音资源] sounds.json 已保存到: {:?}", sounds_json_path);
-    println!("[下载声音资源] 音频文件已保存到: {:?}", sounds_dir);
+    log::info!("[下载声音资源] 下载完成！");
+    log::info!("[下载声音资源] sounds.json 已保存到: {:?}", sounds_json_path);
+    log::info!("[下载声音资源] 音频文件已保存到: {:?}", sounds_dir);
     
     Ok(format!("成功下载 {} 的声音资源 (共 {} 个文件)", latest_release.id, total))
 }
\ No newline at end of file
@@ -1,19 +1,106 @@
-use axum::Router;
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Request,
+    },
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use serde::Serialize;
 use tower_http::{
     services::ServeDir,
     cors::{CorsLayer, AllowOrigin},
 };
-use std::net::SocketAddr;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock};
+use tokio::sync::{mpsc, Mutex};
 use tauri::State;
 
+/// 网页预览的 WebSocket 连接，文件修改后推送 file-changed 消息
+pub struct LiveReload {
+    clients: parking_lot::Mutex<HashMap<u64, mpsc::Sender<String>>>,
+    /// 正在提供服务的材质包根目录，服务器停止时为 None
+    pack_path: parking_lot::Mutex<Option<PathBuf>>,
+    next_id: AtomicU64,
+}
+
+impl LiveReload {
+    fn connect(&self) -> (u64, mpsc::Receiver<String>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = mpsc::channel(crate::constants::LIVE_RELOAD_BACKLOG);
+        self.clients.lock().insert(id, sender);
+        (id, receiver)
+    }
+
+    fn disconnect(&self, id: u64) {
+        self.clients.lock().remove(&id);
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.clients.lock().len()
+    }
+
+    /// 向所有客户端广播修改的文件，积压消息过多的客户端会被断开
+    fn broadcast(&self, path: &Path) {
+        let Some(root) = self.pack_path.lock().clone() else {
+            return;
+        };
+        let Ok(relative) = path.strip_prefix(&root) else {
+            return;
+        };
+        let message = serde_json::json!({
+            "type": "file-changed",
+            "path": relative.to_string_lossy().replace('\\', "/"),
+        })
+        .to_string();
+
+        self.clients.lock().retain(|id, sender| match sender.try_send(message.clone()) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                log::warn!("Dropping slow live reload client {}", id);
+                false
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        });
+    }
+
+    /// 停止服务时断开所有客户端
+    fn reset(&self, pack_path: Option<PathBuf>) {
+        *self.pack_path.lock() = pack_path;
+        self.clients.lock().clear();
+    }
+}
+
+static LIVE_RELOAD: LazyLock<Arc<LiveReload>> = LazyLock::new(|| {
+    Arc::new(LiveReload {
+        clients: parking_lot::Mutex::new(HashMap::new()),
+        pack_path: parking_lot::Mutex::new(None),
+        next_id: AtomicU64::new(0),
+    })
+});
+
+/// 通知网页预览文件已被修改，path 为绝对路径，不在当前服务的材质包内时忽略
+pub fn notify_file_changed(path: &Path) {
+    LIVE_RELOAD.broadcast(path);
+}
+
 #[derive(Clone)]
 pub struct WebServerState {
     pub running: Arc<Mutex<bool>>,
     pub handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
     pub token: Arc<Mutex<Option<String>>>,
     pub token_enabled: Arc<Mutex<bool>>,
+    /// 当前运行的服务器是否要求 token
+    pub auth_enabled: Arc<Mutex<bool>>,
+    /// 当前运行的服务器实际绑定的地址
+    pub address: Arc<Mutex<Option<SocketAddr>>>,
+    pub live_reload: Arc<LiveReload>,
 }
 
 impl Default for WebServerState {
@@ -23,10 +110,26 @@ impl Default for WebServerState {
             handle: Arc::new(Mutex::new(None)),
             token: Arc::new(Mutex::new(None)),
             token_enabled: Arc::new(Mutex::new(true)),
+            auth_enabled: Arc::new(Mutex::new(false)),
+            address: Arc::new(Mutex::new(None)),
+            live_reload: LIVE_RELOAD.clone(),
         }
     }
 }
 
+/// get_server_status 的返回值
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerStatus {
+    pub running: bool,
+    pub auth_enabled: bool,
+    /// 实际绑定的地址，如 0.0.0.0:8080，未运行时为 None
+    pub address: Option<String>,
+    /// 本机的局域网 IPv4 地址，用于分享访问链接
+    pub lan_addresses: Vec<String>,
+    /// 已连接的 live reload WebSocket 客户端数量
+    pub connected_clients: usize,
+}
+
 fn generate_token() -> String {
     use std::collections::hash_map::RandomState;
     use std::hash::{BuildHasher, Hasher};
@@ -40,13 +143,153 @@ fn generate_token() -> String {
     result
 }
 
+/// 解析绑定地址，只允许本机（127.0.0.1）或局域网（0.0.0.0）
+fn parse_bind_address(bind_address: &str) -> Result<IpAddr, String> {
+    match bind_address.trim() {
+        "127.0.0.1" | "localhost" => Ok(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+        "0.0.0.0" => Ok(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+        other => Err(format!(
+            "Unsupported bind address: {} (expected 127.0.0.1 or 0.0.0.0)",
+            other
+        )),
+    }
+}
+
+/// 本机非回环、非链路本地的 IPv4 地址
+fn lan_ipv4_addresses() -> Vec<String> {
+    let interfaces = match if_addrs::get_if_addrs() {
+        Ok(interfaces) => interfaces,
+        Err(e) => {
+            log::warn!("Failed to list network interfaces: {}", e);
+            return Vec::new();
+        }
+    };
+    let mut addresses: Vec<String> = interfaces
+        .iter()
+        .filter_map(|interface| match interface.ip() {
+            IpAddr::V4(ip) if !ip.is_loopback() && !ip.is_link_local() => Some(ip.to_string()),
+            _ => None,
+        })
+        .collect();
+    addresses.sort();
+    addresses.dedup();
+    addresses
+}
+
+/// 逐字节比较 token，耗时与不匹配的位置无关
+fn tokens_match(provided: &str, expected: &str) -> bool {
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+fn cookie_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            pair.trim()
+                .strip_prefix(crate::constants::WEB_SERVER_TOKEN_COOKIE)?
+                .strip_prefix('=')
+        })
+}
+
+fn query_token(request: &Request) -> Option<&str> {
+    request
+        .uri()
+        .query()?
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+}
+
+/// 要求请求带有 token，否则返回 401
+///
+/// token 可以放在 `Authorization: Bearer <token>`、cookie 或 `?token=<token>` 中。
+/// 通过查询参数认证后写入 cookie，浏览器之后请求图片和 live reload WebSocket 时会自动带上
+async fn require_token(
+    axum::extract::State(token): axum::extract::State<Arc<str>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let authorized = bearer_token(&request)
+        .or_else(|| cookie_token(&request))
+        .is_some_and(|provided| tokens_match(provided, &token));
+    if authorized {
+        return next.run(request).await;
+    }
+    if query_token(&request).is_some_and(|provided| tokens_match(provided, &token)) {
+        let mut response = next.run(request).await;
+        let cookie = format!(
+            "{}={}; Path=/; HttpOnly; SameSite=Strict",
+            crate::constants::WEB_SERVER_TOKEN_COOKIE,
+            token
+        );
+        if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+            response.headers_mut().append(header::SET_COOKIE, cookie);
+        }
+        return response;
+    }
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        "Unauthorized",
+    )
+        .into_response()
+}
+
+async fn live_reload_socket(
+    ws: WebSocketUpgrade,
+    axum::extract::State(live_reload): axum::extract::State<Arc<LiveReload>>,
+) -> Response {
+    ws.on_upgrade(move |socket| handle_live_reload(socket, live_reload))
+}
+
+async fn handle_live_reload(mut socket: WebSocket, live_reload: Arc<LiveReload>) {
+    let (id, mut receiver) = live_reload.connect();
+    loop {
+        tokio::select! {
+            message = receiver.recv() => match message {
+                Some(text) => {
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                // 客户端已被移除（积压过多或服务器停止）
+                None => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+        }
+    }
+    live_reload.disconnect(id);
+}
+
+/// 启动服务器，返回任务句柄和实际绑定的地址（端口为 0 时由系统分配）
 pub async fn start_web_server(
-    port: u16,
+    addr: SocketAddr,
     pack_path: String,
-    bind_all: bool,
-    _token: Option<String>,
-    _token_enabled: bool,
-) -> Result<tokio::task::JoinHandle<()>, String> {
+    token: Option<String>,
+    live_reload: Arc<LiveReload>,
+) -> Result<(tokio::task::JoinHandle<()>, SocketAddr), String> {
+    let bind_all = addr.ip().is_unspecified();
     let serve_dir = ServeDir::new(pack_path.clone())
         .append_index_html_on_directories(true);
 
@@ -54,39 +297,59 @@ pub async fn start_web_server(
         CorsLayer::new()
             .allow_origin(AllowOrigin::any())
             .allow_methods([axum::http::Method::GET])
+            .allow_headers([header::AUTHORIZATION])
     } else {
         CorsLayer::permissive()
     };
 
+    let mut protected = Router::new()
+        .route(crate::constants::LIVE_RELOAD_PATH, get(live_reload_socket))
+        .with_state(live_reload)
+        .fallback_service(serve_dir);
+    if let Some(token) = token {
+        protected = protected.layer(axum::middleware::from_fn_with_state(
+            Arc::<str>::from(token),
+            require_token,
+        ));
+    }
+
+    // 健康检查不需要认证
     let app = Router::new()
-        .nest_service("/", serve_dir)
+        .route(crate::constants::WEB_SERVER_HEALTH_PATH, get(|| async { "ok" }))
+        .merge(protected)
         .layer(cors);
 
-    let addr = if bind_all {
-        SocketAddr::from(([0, 0, 0, 0], port))
-    } else {
-        SocketAddr::from(([127, 0, 0, 1], port))
-    };
-
-    println!("Starting web server on {}", addr);
-
     let listener = tokio::net::TcpListener::bind(addr)
         .await
-        .map_err(|e| format!("Failed to bind to {}: {}", addr, e))?;
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AddrInUse => {
+                format!("Port {} is already in use, choose another port", addr.port())
+            }
+            std::io::ErrorKind::PermissionDenied => {
+                format!("Permission denied when binding to port {}", addr.port())
+            }
+            _ => format!("Failed to bind to {}: {}", addr, e),
+        })?;
+    let local_addr = listener.local_addr().unwrap_or(addr);
+
+    log::info!("Starting web server on {}", local_addr);
 
     let handle = tokio::spawn(async move {
         if let Err(e) = axum::serve(listener, app).await {
-            eprintln!("Server error: {}", e);
+            log::error!("Server error: {}", e);
         }
     });
 
-    Ok(handle)
+    Ok((handle, local_addr))
 }
 
 #[tauri::command]
 pub async fn start_server(
     port: u16,
     mode: String,
+    bind_address: Option<String>,
+    token: Option<String>,
+    require_auth: Option<bool>,
     state: State<'_, WebServerState>,
     app_state: State<'_, crate::commands::AppState>,
 ) -> Result<String, String> {
@@ -104,43 +367,58 @@ pub async fn start_server(
         }
     };
 
-    let bind_all = mode == "all";
+    // 未指定绑定地址时按 mode 选择，"all" 对局域网开放
+    let ip = match bind_address.as_deref() {
+        Some(bind_address) => parse_bind_address(bind_address)?,
+        None if mode == "all" => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        None => IpAddr::V4(Ipv4Addr::LOCALHOST),
+    };
+    
+    if token.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        return Err("Token cannot be empty".to_string());
+    }
     
-    let token = if bind_all {
+    let token_enabled = *state.token_enabled.lock().await;
+    // 认证需要主动开启：未指定 require_auth 时只有传入 token（且未关闭 token）才启用
+    let auth_enabled = require_auth.unwrap_or(token.is_some() && token_enabled);
+    
+    // 要求认证但未传入 token 时沿用已保存的 token，没有则随机生成
+    let token = if auth_enabled {
         let mut token_guard = state.token.lock().await;
-        if token_guard.is_none() {
-            *token_guard = Some(generate_token());
+        if let Some(token) = token {
+            *token_guard = Some(token.trim().to_string());
         }
-        token_guard.clone()
+        Some(token_guard.get_or_insert_with(generate_token).clone())
     } else {
         None
     };
     
-    let token_enabled = *state.token_enabled.lock().await;
-    
-    match start_web_server(port, pack_path_str, bind_all, token.clone(), token_enabled).await {
-        Ok(handle) => {
+    state.live_reload.reset(Some(PathBuf::from(&pack_path_str)));
+    match start_web_server(
+        SocketAddr::new(ip, port),
+        pack_path_str,
+        token.clone(),
+        state.live_reload.clone(),
+    )
+    .await
+    {
+        Ok((handle, addr)) => {
             *state.handle.lock().await = Some(handle);
+            *state.auth_enabled.lock().await = auth_enabled;
+            *state.address.lock().await = Some(addr);
             *running = true;
             
-            let addr = if bind_all {
-                format!("0.0.0.0:{}", port)
-            } else {
-                format!("127.0.0.1:{}", port)
-            };
-            
             let mut msg = format!("Server started on {}", addr);
-            if bind_all {
-                if let Some(t) = &token {
-                    if token_enabled {
-                        msg.push_str(&format!(" | Access token: {}", t));
-                    }
-                }
+            if let Some(t) = &token {
+                msg.push_str(&format!(" | Access token: {} (open http://{}/?token={})", t, addr, t));
             }
             
             Ok(msg)
         }
-        Err(e) => Err(e),
+        Err(e) => {
+            state.live_reload.reset(None);
+            Err(e)
+        }
     }
 }
 
@@ -155,14 +433,23 @@ pub async fn stop_server(state: State<'_, WebServerState>) -> Result<String, Str
     if let Some(handle) = state.handle.lock().await.take() {
         handle.abort();
     }
+    state.live_reload.reset(None);
+    *state.auth_enabled.lock().await = false;
+    *state.address.lock().await = None;
     
     *running = false;
     Ok("Server stopped".to_string())
 }
 
 #[tauri::command]
-pub async fn get_server_status(state: State<'_, WebServerState>) -> Result<bool, String> {
-    Ok(*state.running.lock().await)
+pub async fn get_server_status(state: State<'_, WebServerState>) -> Result<ServerStatus, String> {
+    Ok(ServerStatus {
+        running: *state.running.lock().await,
+        auth_enabled: *state.auth_enabled.lock().await,
+        address: state.address.lock().await.map(|addr| addr.to_string()),
+        lan_addresses: lan_ipv4_addresses(),
+        connected_clients: state.live_reload.client_count(),
+    })
 }
 
 #[tauri::command]
@@ -1,10 +1,41 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 
+use crate::error::io_error;
+
+/// 解压时跳过的条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedEntry {
+    pub name: String,
+    pub reason: String,
+}
+
+/// 导入 ZIP 材质包的结果，供前端提示用户
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// 路径穿越、绝对路径等被跳过的条目
+    pub skipped_entries: Vec<SkippedEntry>,
+    /// ZIP 中是否有 pack.mcmeta（为 false 时是生成的）
+    pub found_mcmeta: bool,
+}
+
 /// 解压ZIP文件到指定目录
 pub fn extract_zip(zip_path: &Path, extract_to: &Path) -> Result<(), String> {
+    extract_zip_subfolder(zip_path, extract_to, None).map(|_| ())
+}
+
+/// 解压ZIP文件，`subfolder` 不为空时只解压该子目录下的内容并去掉该前缀
+///
+/// 包含 `..`、绝对路径的条目和 macOS 的 __MACOSX 元数据不会被解压，返回这些被跳过的条目
+pub fn extract_zip_subfolder(
+    zip_path: &Path,
+    extract_to: &Path,
+    subfolder: Option<&str>,
+) -> Result<Vec<SkippedEntry>, String> {
     let file = File::open(zip_path)
         .map_err(|e| format!("Failed to open zip file: {}", e))?;
     
@@ -15,13 +46,33 @@ pub fn extract_zip(zip_path: &Path, extract_to: &Path) -> Result<(), String> {
     fs::create_dir_all(extract_to)
         .map_err(|e| format!("Failed to create extract directory: {}", e))?;
 
+    let mut skipped = Vec::new();
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)
             .map_err(|e| format!("Failed to read file from archive: {}", e))?;
         
         let outpath = match file.enclosed_name() {
-            Some(path) => extract_to.join(path),
-            None => continue,
+            Some(path) if path.starts_with("__MACOSX") => {
+                skipped.push(SkippedEntry {
+                    name: file.name().to_string(),
+                    reason: "macOS metadata".to_string(),
+                });
+                continue;
+            }
+            Some(path) => match subfolder {
+                Some(prefix) => match path.strip_prefix(prefix) {
+                    Ok(rel) if !rel.as_os_str().is_empty() => extract_to.join(rel),
+                    _ => continue,
+                },
+                None => extract_to.join(path),
+            },
+            None => {
+                skipped.push(SkippedEntry {
+                    name: file.name().to_string(),
+                    reason: "Path escapes the extract directory".to_string(),
+                });
+                continue;
+            }
         };
 
         if file.name().ends_with('/') {
@@ -48,18 +99,58 @@ pub fn extract_zip(zip_path: &Path, extract_to: &Path) -> Result<(), String> {
         }
     }
 
-    Ok(())
+    Ok(skipped)
+}
+
+/// 本身已压缩的格式，再次压缩几乎没有收益
+const PRECOMPRESSED_EXTENSIONS: &[&str] = &["png", "ogg", "jpg", "jpeg", "gif", "webp", "zip"];
+
+/// 导出时的压缩级别
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionChoice {
+    /// 不压缩
+    Stored,
+    Fast,
+    #[default]
+    Default,
+    Best,
+}
+
+impl CompressionChoice {
+    /// 文件的压缩选项，已压缩格式的文件直接存储
+    pub fn options_for(self, name: &str) -> zip::write::SimpleFileOptions {
+        let options = zip::write::SimpleFileOptions::default().unix_permissions(0o755);
+        let precompressed = Path::new(name)
+            .extension()
+            .map(|ext| PRECOMPRESSED_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+            .unwrap_or(false);
+
+        if self == CompressionChoice::Stored || precompressed {
+            return options.compression_method(zip::CompressionMethod::Stored);
+        }
+        let level = match self {
+            CompressionChoice::Fast => Some(1),
+            CompressionChoice::Best => Some(9),
+            _ => None,
+        };
+        options
+            .compression_method(zip::CompressionMethod::Deflated)
+            .compression_level(level)
+    }
 }
 
-/// 将目录打包为ZIP文件
-pub fn create_zip(source_dir: &Path, output_path: &Path) -> Result<(), String> {
+/// 将目录打包为ZIP文件，返回生成的文件大小
+pub fn create_zip(
+    source_dir: &Path,
+    output_path: &Path,
+    compression: CompressionChoice,
+) -> Result<u64, String> {
     let file = File::create(output_path)
-        .map_err(|e| format!("Failed to create zip file: {}", e))?;
+        .map_err(|e| io_error("Failed to create zip file", e))?;
     
     let mut zip = zip::ZipWriter::new(file);
-    let options = zip::write::FileOptions::<()>::default()
-        .compression_method(zip::CompressionMethod::Deflated)
-        .unix_permissions(0o755);
+    let dir_options = compression.options_for("");
 
     let walkdir = walkdir::WalkDir::new(source_dir);
     let it = walkdir.into_iter().filter_map(|e| e.ok());
@@ -74,10 +165,15 @@ pub fn create_zip(source_dir: &Path, output_path: &Path) -> Result<(), String> {
             continue;
         }
 
+        // 跳过编辑器元数据
+        if is_export_excluded(name) {
+            continue;
+        }
+
         let name_str = name.to_string_lossy().replace('\\', "/");
 
         if path.is_file() {
-            zip.start_file(&name_str, options)
+            zip.start_file(&name_str, compression.options_for(&name_str))
                 .map_err(|e| format!("Failed to start file in zip: {}", e))?;
             
             let mut f = File::open(path)
@@ -88,20 +184,350 @@ pub fn create_zip(source_dir: &Path, output_path: &Path) -> Result<(), String> {
                 .map_err(|e| format!("Failed to read file: {}", e))?;
             
             zip.write_all(&buffer)
-                .map_err(|e| format!("Failed to write to zip: {}", e))?;
+                .map_err(|e| io_error("Failed to write to zip", e))?;
         } else if path.is_dir() {
-            zip.add_directory(&name_str, options)
+            zip.add_directory(&name_str, dir_options)
                 .map_err(|e| format!("Failed to add directory to zip: {}", e))?;
         }
     }
 
-    zip.finish()
-        .map_err(|e| format!("Failed to finish zip: {}", e))?;
+    zip.finish().map_err(|e| match e {
+        zip::result::ZipError::Io(e) => io_error("Failed to finish zip", e),
+        e => format!("Failed to finish zip: {}", e),
+    })?;
 
-    Ok(())
+    fs::metadata(output_path)
+        .map(|m| m.len())
+        .map_err(|e| io_error("Failed to read zip size", e))
+}
+
+/// 部分导出的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectedExportResult {
+    pub files: usize,
+    pub size: u64,
+}
+
+/// 自某时间以来修改过的文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifiedFiles {
+    /// RFC 3339 时间
+    pub since: String,
+    pub files: Vec<String>,
+}
+
+fn last_export_file(pack_path: &Path) -> PathBuf {
+    pack_path.join(".little100").join("last_export")
+}
+
+/// 记录导出时间，供 `modified_files_since` 默认使用
+pub fn record_export_time(pack_path: &Path) -> Result<(), String> {
+    let file = last_export_file(pack_path);
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error("Failed to create directory", e))?;
+    }
+    fs::write(&file, chrono::Utc::now().to_rfc3339())
+        .map_err(|e| io_error("Failed to record export time", e))
+}
+
+/// 只导出指定的文件或文件夹（相对材质包根目录），并始终包含 pack.mcmeta 和 pack.png
+pub fn export_selected(
+    source_dir: &Path,
+    output_path: &Path,
+    paths: &[String],
+    compression: CompressionChoice,
+) -> Result<SelectedExportResult, String> {
+    if !source_dir.join("pack.mcmeta").is_file() {
+        return Err("pack.mcmeta not found".to_string());
+    }
+
+    let mut files = std::collections::BTreeMap::new();
+    let roots = ["pack.mcmeta", "pack.png"]
+        .into_iter()
+        .map(String::from)
+        .chain(paths.iter().map(|p| p.trim().replace('\\', "/").trim_matches('/').to_string()));
+    for rel in roots {
+        let rel_path = Path::new(&rel);
+        let escapes = rel_path
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)));
+        if rel.is_empty() || escapes {
+            return Err(format!("Invalid path: {}", rel));
+        }
+        if is_export_excluded(rel_path) {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(source_dir.join(rel_path))
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            if let Ok(name) = entry.path().strip_prefix(source_dir) {
+                files.insert(name.to_string_lossy().replace('\\', "/"), entry.path().to_path_buf());
+            }
+        }
+    }
+
+    let file = File::create(output_path)
+        .map_err(|e| io_error("Failed to create zip file", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    for (name, path) in &files {
+        zip.start_file(name.as_str(), compression.options_for(name))
+            .map_err(|e| format!("Failed to start file in zip: {}", e))?;
+        let mut f = File::open(path)
+            .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+        std::io::copy(&mut f, &mut zip)
+            .map_err(|e| io_error("Failed to write to zip", e))?;
+    }
+    zip.finish().map_err(|e| match e {
+        zip::result::ZipError::Io(e) => io_error("Failed to finish zip", e),
+        e => format!("Failed to finish zip: {}", e),
+    })?;
+
+    Ok(SelectedExportResult {
+        files: files.len(),
+        size: fs::metadata(output_path).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+/// 列出修改时间晚于 `since`（RFC 3339）的文件，为 None 时使用上次导出的时间
+pub fn modified_files_since(pack_path: &Path, since: Option<&str>) -> Result<ModifiedFiles, String> {
+    let since = match since {
+        Some(since) => since.to_string(),
+        None => fs::read_to_string(last_export_file(pack_path))
+            .map(|s| s.trim().to_string())
+            .map_err(|_| "No previous export recorded".to_string())?,
+    };
+    let since_time = chrono::DateTime::parse_from_rfc3339(&since)
+        .map_err(|e| format!("Invalid timestamp {}: {}", since, e))?;
+
+    let mut files: Vec<String> = walkdir::WalkDir::new(pack_path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let rel = e.path().strip_prefix(pack_path).ok()?;
+            if is_export_excluded(rel) {
+                return None;
+            }
+            let modified = chrono::DateTime::<chrono::Utc>::from(e.metadata().ok()?.modified().ok()?);
+            (modified > since_time).then(|| rel.to_string_lossy().replace('\\', "/"))
+        })
+        .collect();
+    files.sort();
+
+    Ok(ModifiedFiles { since, files })
+}
+
+/// 多版本导出的一个变体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportVariant {
+    /// 变体名称，同时作为 zip 文件名
+    pub name: String,
+    pub pack_format: i32,
+    /// 相对材质包根目录的覆盖目录，其中的文件覆盖到该变体的根目录；
+    /// 所有变体的覆盖目录本身都不会原样导出
+    #[serde(default)]
+    pub overlay_dirs: Vec<String>,
+}
+
+/// 单个变体的导出结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantExportResult {
+    pub name: String,
+    pub output_path: String,
+    pub files: usize,
+    pub size: u64,
+}
+
+/// 多版本导出的总体进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariantExportProgress {
+    pub variant: String,
+    pub current: usize,
+    pub total: usize,
+    pub percent: f64,
+}
+
+/// 统一覆盖目录写法为不带首尾 / 的相对路径
+fn normalize_overlay_dir(dir: &str) -> String {
+    dir.trim().replace('\\', "/").trim_matches('/').to_string()
+}
+
+/// 按多个 pack_format 批量导出，每个变体生成一个 `<name>.zip`
+///
+/// `on_progress` 以所有变体的文件总数报告进度
+pub fn export_variants(
+    source_dir: &Path,
+    output_dir: &Path,
+    variants: &[ExportVariant],
+    on_progress: Option<&dyn Fn(&VariantExportProgress)>,
+) -> Result<Vec<VariantExportResult>, String> {
+    if variants.is_empty() {
+        return Err("No variants to export".to_string());
+    }
+
+    let mut names = HashSet::new();
+    for variant in variants {
+        let name = variant.name.trim();
+        if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+            return Err(format!("Invalid variant name: {}", variant.name));
+        }
+        if !names.insert(name.to_lowercase()) {
+            return Err(format!("Duplicate variant name: {}", variant.name));
+        }
+    }
+
+    let mcmeta_content = fs::read_to_string(source_dir.join("pack.mcmeta"))
+        .map_err(|e| format!("Failed to read pack.mcmeta: {}", e))?;
+    let mcmeta: serde_json::Value = serde_json::from_str(&mcmeta_content)
+        .map_err(|e| format!("Failed to parse pack.mcmeta: {}", e))?;
+
+    let overlay_roots: HashSet<String> = variants
+        .iter()
+        .flat_map(|v| v.overlay_dirs.iter().map(|d| normalize_overlay_dir(d)))
+        .filter(|d| !d.is_empty())
+        .collect();
+    for overlay in &overlay_roots {
+        if !source_dir.join(overlay).is_dir() {
+            return Err(format!("Overlay directory not found: {}", overlay));
+        }
+    }
+    let in_overlay = |rel: &str| {
+        overlay_roots
+            .iter()
+            .any(|root| rel == root || rel.starts_with(&format!("{}/", root)))
+    };
+
+    // 基础文件：排除编辑器元数据、pack.mcmeta 和所有覆盖目录
+    let mut base_files = std::collections::BTreeMap::new();
+    for entry in walkdir::WalkDir::new(source_dir).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = match entry.path().strip_prefix(source_dir) {
+            Ok(rel) if !is_export_excluded(rel) => rel.to_string_lossy().replace('\\', "/"),
+            _ => continue,
+        };
+        if rel == "pack.mcmeta" || in_overlay(&rel) {
+            continue;
+        }
+        base_files.insert(rel, entry.path().to_path_buf());
+    }
+
+    // 每个变体的文件列表：基础文件加上按顺序覆盖的覆盖目录
+    let mut plans = Vec::with_capacity(variants.len());
+    for variant in variants {
+        let mut files = base_files.clone();
+        for overlay in variant.overlay_dirs.iter().map(|d| normalize_overlay_dir(d)) {
+            if overlay.is_empty() {
+                continue;
+            }
+            let overlay_path = source_dir.join(&overlay);
+            for entry in walkdir::WalkDir::new(&overlay_path).into_iter().filter_map(|e| e.ok()) {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                if let Ok(rel) = entry.path().strip_prefix(&overlay_path) {
+                    let rel = rel.to_string_lossy().replace('\\', "/");
+                    if rel != "pack.mcmeta" {
+                        files.insert(rel, entry.path().to_path_buf());
+                    }
+                }
+            }
+        }
+        plans.push(files);
+    }
+
+    let total: usize = plans.iter().map(|files| files.len() + 1).sum();
+    let mut current = 0;
+    let report = |variant: &str, current: usize| {
+        if let Some(on_progress) = on_progress {
+            on_progress(&VariantExportProgress {
+                variant: variant.to_string(),
+                current,
+                total,
+                percent: current as f64 / total as f64 * 100.0,
+            });
+        }
+    };
+
+    fs::create_dir_all(output_dir)
+        .map_err(|e| io_error("Failed to create output directory", e))?;
+
+    let options = zip::write::FileOptions::<()>::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o755);
+
+    let mut results = Vec::with_capacity(variants.len());
+    for (variant, files) in variants.iter().zip(plans) {
+        let name = variant.name.trim();
+        let output_path = output_dir.join(format!("{}.zip", name));
+
+        let mut variant_mcmeta = mcmeta.clone();
+        variant_mcmeta
+            .get_mut("pack")
+            .and_then(|pack| pack.as_object_mut())
+            .ok_or_else(|| "pack.mcmeta is missing the pack object".to_string())?
+            .insert("pack_format".to_string(), serde_json::json!(variant.pack_format));
+        let mcmeta_bytes = serde_json::to_vec_pretty(&variant_mcmeta)
+            .map_err(|e| format!("Failed to serialize pack.mcmeta: {}", e))?;
+
+        let file = File::create(&output_path)
+            .map_err(|e| io_error("Failed to create zip file", e))?;
+        let mut zip = zip::ZipWriter::new(file);
+
+        zip.start_file("pack.mcmeta", options)
+            .map_err(|e| format!("Failed to start file in zip: {}", e))?;
+        zip.write_all(&mcmeta_bytes)
+            .map_err(|e| io_error("Failed to write to zip", e))?;
+        current += 1;
+
+        for (i, (rel, path)) in files.iter().enumerate() {
+            zip.start_file(rel.as_str(), options)
+                .map_err(|e| format!("Failed to start file in zip: {}", e))?;
+            let mut f = File::open(path)
+                .map_err(|e| format!("Failed to open {}: {}", rel, e))?;
+            std::io::copy(&mut f, &mut zip)
+                .map_err(|e| io_error("Failed to write to zip", e))?;
+
+            current += 1;
+            if i % 50 == 0 {
+                report(name, current);
+            }
+        }
+
+        zip.finish().map_err(|e| match e {
+            zip::result::ZipError::Io(e) => io_error("Failed to finish zip", e),
+            e => format!("Failed to finish zip: {}", e),
+        })?;
+        report(name, current);
+
+        results.push(VariantExportResult {
+            name: name.to_string(),
+            output_path: output_path.to_string_lossy().to_string(),
+            files: files.len() + 1,
+            size: fs::metadata(&output_path).map(|m| m.len()).unwrap_or(0),
+        });
+    }
+
+    Ok(results)
+}
+
+/// 判断相对路径是否属于导出时排除的目录
+pub fn is_export_excluded(relative: &Path) -> bool {
+    relative
+        .components()
+        .next()
+        .map(|c| {
+            let name = c.as_os_str().to_string_lossy();
+            crate::constants::EXPORT_EXCLUDED_DIRS.contains(&name.as_ref())
+        })
+        .unwrap_or(false)
 }
 
 /// 验证是否为有效的材质包ZIP
+#[allow(dead_code)]
 pub fn validate_pack_zip(zip_path: &Path) -> Result<bool, String> {
     let file = File::open(zip_path)
         .map_err(|e| format!("Failed to open zip file: {}", e))?;
@@ -122,6 +548,130 @@ pub fn validate_pack_zip(zip_path: &Path) -> Result<bool, String> {
     Ok(false)
 }
 
+/// 导入ZIP材质包时的错误
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum ImportError {
+    /// 文件不是有效的ZIP
+    NotAZip { message: String },
+    /// 找不到 pack.mcmeta
+    NoMcmeta,
+    /// pack.mcmeta 位于子目录中，可从该目录导入
+    McmetaNotAtRoot { root: String },
+    /// 没有 pack.mcmeta，但根目录有 assets/<modid>/（如模组 jar），可包装为材质包导入
+    ModAssets {
+        namespaces: Vec<String>,
+        loader: Option<String>,
+    },
+    /// ZIP 已加密
+    EncryptedZip,
+    /// 解压或读写失败
+    ExtractionIo { message: String },
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportError::NotAZip { message } => write!(f, "Not a zip file: {}", message),
+            ImportError::NoMcmeta => write!(f, "Invalid resource pack: pack.mcmeta not found"),
+            ImportError::McmetaNotAtRoot { root } => {
+                write!(f, "pack.mcmeta is inside subfolder '{}'", root)
+            }
+            ImportError::ModAssets { namespaces, .. } => write!(
+                f,
+                "No pack.mcmeta found, but the archive contains mod assets: {}",
+                namespaces.join(", ")
+            ),
+            ImportError::EncryptedZip => write!(f, "Zip file is encrypted"),
+            ImportError::ExtractionIo { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<String> for ImportError {
+    fn from(message: String) -> Self {
+        ImportError::ExtractionIo { message }
+    }
+}
+
+/// 检查ZIP材质包结构
+///
+/// pack.mcmeta 位于根目录时返回 `Ok(None)`；所有内容都位于同一个包含 pack.mcmeta 的顶层目录时
+/// 返回 `Ok(Some(root))`，可直接将该目录视为材质包根目录；其他嵌套情况返回 `McmetaNotAtRoot`
+pub fn inspect_pack_zip(zip_path: &Path) -> Result<Option<String>, ImportError> {
+    let file = File::open(zip_path).map_err(|e| ImportError::ExtractionIo {
+        message: format!("Failed to open zip file: {}", e),
+    })?;
+
+    let mut archive = ZipArchive::new(file).map_err(|e| ImportError::NotAZip {
+        message: e.to_string(),
+    })?;
+
+    let mut has_root_mcmeta = false;
+    let mut nested_root = None;
+    let mut top_level = HashSet::new();
+    let mut mod_namespaces = std::collections::BTreeSet::new();
+    let mut loader = None;
+
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i).map_err(|e| ImportError::NotAZip {
+            message: e.to_string(),
+        })?;
+
+        if file.encrypted() {
+            return Err(ImportError::EncryptedZip);
+        }
+
+        let name = file.name();
+        if name == "pack.mcmeta" {
+            has_root_mcmeta = true;
+        }
+
+        if let Some((namespace, rest)) = name
+            .strip_prefix("assets/")
+            .and_then(|rest| rest.split_once('/'))
+        {
+            if !namespace.is_empty() && !rest.is_empty() {
+                mod_namespaces.insert(namespace.to_string());
+            }
+        }
+        if loader.is_none() {
+            loader = crate::pack_parser::MOD_LOADER_MARKERS
+                .iter()
+                .find(|(marker, _)| name == *marker)
+                .map(|(_, loader)| loader.to_string());
+        }
+
+        // macOS 压缩时附带的元数据目录不算作内容
+        let top = name.split('/').next().unwrap_or("");
+        if top != "__MACOSX" && !top.is_empty() {
+            top_level.insert(top.to_string());
+        }
+
+        if nested_root.is_none() {
+            if let Some((root, rest)) = name.split_once('/') {
+                if rest == "pack.mcmeta" && !root.is_empty() {
+                    nested_root = Some(root.to_string());
+                }
+            }
+        }
+    }
+
+    if has_root_mcmeta {
+        return Ok(None);
+    }
+
+    match nested_root {
+        Some(root) if top_level.len() == 1 => Ok(Some(root)),
+        Some(root) => Err(ImportError::McmetaNotAtRoot { root }),
+        None if !mod_namespaces.is_empty() => Err(ImportError::ModAssets {
+            namespaces: mod_namespaces.into_iter().collect(),
+            loader,
+        }),
+        None => Err(ImportError::NoMcmeta),
+    }
+}
+
 /// 获取临时解压目录
 pub fn get_temp_extract_dir() -> PathBuf {
     let temp_dir = std::env::temp_dir();
@@ -135,11 +685,11 @@ pub fn cleanup_temp_files() -> Result<(), String> {
     let system_temp = std::env::temp_dir();
     
     if temp_dir.exists() && temp_dir.starts_with(&system_temp) {
-        eprintln!("Cleaning up temp directory: {:?}", temp_dir);
+        log::info!("Cleaning up temp directory: {:?}", temp_dir);
         fs::remove_dir_all(&temp_dir)
             .map_err(|e| format!("Failed to cleanup temp files: {}", e))?;
     } else {
-        eprintln!("Skipping cleanup: temp_dir is not in system temp or doesn't exist");
+        log::debug!("Skipping cleanup: temp_dir is not in system temp or doesn't exist");
     }
     Ok(())
 }
\ No newline at end of file
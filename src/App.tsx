@@ -20,6 +20,9 @@ import {
   getServerStatus,
   getSystemFonts,
   openFolder,
+  setHistorySettings,
+  setNetworkTimeouts,
+  formatError,
 } from "./utils/tauri-api";
 import type { MergeSource, PackInfo, ResourceType } from "./types/pack";
 import { VERSION_DESCRIPTIONS, RESOURCE_TYPE_NAMES } from "./types/pack";
@@ -30,7 +33,12 @@ import { checkForUpdates } from "./components/UpdateDialog";
 import { UpdateDialogProvider } from "./components/UpdateDialog";
 import { Icon, Button, ConfirmDialog, useToast } from "@mpe/ui";
 
-import { DEFAULT_PORT, DEFAULT_MAX_HISTORY_COUNT } from './core/constants';
+import {
+  DEFAULT_PORT,
+  DEFAULT_MAX_HISTORY_COUNT,
+  DEFAULT_CONNECT_TIMEOUT_SECS,
+  DEFAULT_READ_TIMEOUT_SECS,
+} from './core/constants';
 import { logger } from './utils/logger';
 
 type Theme = "light" | "dark" | "system";
@@ -45,6 +53,7 @@ const loadSettings = () => {
   const savedMaxHistoryCount = localStorage.getItem('maxHistoryCount');
   const savedTemplateCacheEnabled = localStorage.getItem('templateCacheEnabled');
   const savedDebugMode = localStorage.getItem('debugMode');
+  const savedReadTimeout = localStorage.getItem('readTimeoutSecs');
   return {
     theme: savedTheme || 'system',
     fontFamily: savedFont || 'system',
@@ -54,6 +63,7 @@ const loadSettings = () => {
     maxHistoryCount: savedMaxHistoryCount ? parseInt(savedMaxHistoryCount) : DEFAULT_MAX_HISTORY_COUNT,
     templateCacheEnabled: savedTemplateCacheEnabled === 'true',
     debugMode: savedDebugMode === 'true',
+    readTimeoutSecs: savedReadTimeout ? parseInt(savedReadTimeout) : DEFAULT_READ_TIMEOUT_SECS,
   };
 };
 
@@ -97,6 +107,7 @@ function App() {
   const [maxHistoryCount, setMaxHistoryCount] = useState<number>(settings.maxHistoryCount);
   const [templateCacheEnabled, setTemplateCacheEnabled] = useState<boolean>(settings.templateCacheEnabled);
   const [debugMode, setDebugMode] = useState<boolean>(settings.debugMode);
+  const [readTimeoutSecs, setReadTimeoutSecs] = useState<number>(settings.readTimeoutSecs);
   const [editorPackStats, setEditorPackStats] = useState({ packSize: 0, historySize: 0 });
 
   const handlePackStatsChange = useCallback((packSize: number, historySize: number) => {
@@ -182,6 +193,10 @@ function App() {
     localStorage.setItem('maxHistoryCount', String(maxHistoryCount));
   }, [maxHistoryCount]);
 
+  useEffect(() => {
+    setHistorySettings(historyEnabled, maxHistoryCount).catch(console.error);
+  }, [historyEnabled, maxHistoryCount]);
+
   useEffect(() => {
     localStorage.setItem('templateCacheEnabled', String(templateCacheEnabled));
   }, [templateCacheEnabled]);
@@ -190,6 +205,11 @@ function App() {
     localStorage.setItem('debugMode', String(debugMode));
   }, [debugMode]);
 
+  useEffect(() => {
+    localStorage.setItem('readTimeoutSecs', String(readTimeoutSecs));
+    setNetworkTimeouts(DEFAULT_CONNECT_TIMEOUT_SECS, readTimeoutSecs).catch(console.error);
+  }, [readTimeoutSecs]);
+
   const notifyZipImportedAsFolder = useCallback(
     (info: PackInfo) => {
       if (!info.pack_path) return;
@@ -222,7 +242,7 @@ function App() {
         setLoading(false);
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
       setLoading(false);
     }
   };
@@ -251,7 +271,7 @@ function App() {
       }
     } catch (err) {
       logger.error('Import folder error:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
       setLoading(false);
     }
   };
@@ -268,7 +288,7 @@ function App() {
       setPendingFolderPath(null);
     } catch (err) {
       logger.error('Import folder error:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
     } finally {
       setLoading(false);
     }
@@ -345,7 +365,7 @@ function App() {
       }
     } catch (err) {
       logger.error('拖拽导入失败:', err);
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
     } finally {
       setLoading(false);
     }
@@ -387,7 +407,7 @@ function App() {
                 }
               } catch (err) {
                 logger.error('拖拽导入失败:', err);
-                setError(err instanceof Error ? err.message : String(err));
+                setError(formatError(err));
               } finally {
                 setLoading(false);
               }
@@ -420,7 +440,7 @@ function App() {
         setLoading(false);
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
       setLoading(false);
     }
   };
@@ -456,7 +476,7 @@ function App() {
             setServerMessage(msg);
             setServerRunning(true);
           } catch (err) {
-            setServerMessage(err instanceof Error ? err.message : String(err));
+            setServerMessage(formatError(err));
             setWebService('off');
           }
         }
@@ -470,7 +490,7 @@ function App() {
     const checkStatus = async () => {
       try {
         const status = await getServerStatus();
-        setServerRunning(status);
+        setServerRunning(status.running);
       } catch (err) {
         logger.error('Failed to check server status:', err);
       }
@@ -757,6 +777,27 @@ function App() {
             </p>
           </div>
 
+          <div className="setting-group">
+            <label>下载超时</label>
+            <div className="history-count-setting">
+              <label>无数据传输超过 {readTimeoutSecs} 秒时中断下载</label>
+              <input
+                type="range"
+                min="15"
+                max="300"
+                step="15"
+                value={readTimeoutSecs}
+                onChange={(e) => setReadTimeoutSecs(parseInt(e.target.value))}
+                className="history-slider"
+              />
+              <div className="range-labels">
+                <span>15</span>
+                <span>150</span>
+                <span>300</span>
+              </div>
+            </div>
+          </div>
+
           <div className="setting-group">
             <label>调试模式</label>
             <div className="setting-options">
@@ -904,7 +945,7 @@ function App() {
               const info = await importPackFolder(packPath);
               setPackInfo(info);
             } catch (err) {
-              setError(err instanceof Error ? err.message : String(err));
+              setError(formatError(err));
             } finally {
               setLoading(false);
             }
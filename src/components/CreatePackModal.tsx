@@ -7,6 +7,7 @@ import {
   getMinecraftVersions,
   downloadAndExtractTemplate,
   type VersionInfo,
+  formatError,
 } from "../utils/tauri-api";
 import "./CreatePackModal.css";
 import { Icon } from "@mpe/ui";
@@ -213,7 +214,7 @@ export default function CreatePackModal({
         setOutputPath(folder);
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
     }
   };
 
@@ -248,7 +249,7 @@ export default function CreatePackModal({
 
       onSuccess(fullPath);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
     } finally {
       setLoading(false);
     }
@@ -262,7 +263,7 @@ export default function CreatePackModal({
       setVersions(manifest.versions);
       setStep(2);
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
     } finally {
       setLoading(false);
     }
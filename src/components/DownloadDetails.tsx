@@ -5,6 +5,7 @@ import { Icon, useToast, ConfirmDialog } from '@mpe/ui';
 import { logger } from '../utils/logger';
 import { formatSpeed, formatETA } from '../utils/shared';
 import type { DownloadProgress, DownloadTask } from '../types/download';
+import { formatError } from '../utils/tauri-api';
 import './DownloadDetails.css';
 
 interface DownloadDetailsProps {
@@ -70,7 +71,7 @@ export default function DownloadDetails({ onClose }: DownloadDetailsProps) {
       await invoke('cancel_download_task', { taskId });
     } catch (error) {
       logger.error('取消下载失败:', error);
-      toast({ message: `取消下载失败: ${error}`, type: 'error' });
+      toast({ message: `取消下载失败: ${formatError(error)}`, type: 'error' });
     }
   };
 
@@ -83,7 +84,7 @@ export default function DownloadDetails({ onClose }: DownloadDetailsProps) {
           await invoke('delete_download_task', { taskId });
         } catch (error) {
           logger.error('删除任务失败:', error);
-          toast({ message: `删除任务失败: ${error}`, type: 'error' });
+          toast({ message: `删除任务失败: ${formatError(error)}`, type: 'error' });
         }
       },
     });
@@ -2,7 +2,7 @@ import { useState, useEffect, useRef, useMemo, useCallback } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import "./ImageViewer.css";
 import { imageCache } from "../utils/image-cache";
-import { readFileBinary } from "../utils/tauri-api";
+import { readFileBinary, formatError } from "../utils/tauri-api";
 import {
   createGPUContext,
   enableCanvasAcceleration,
@@ -950,7 +950,7 @@ export default function ImageViewer({
               setHasChanges(false);
               toast({ message: '保存成功!', type: 'success' });
           }
-      } catch (err) { logger.error('保存失败:', err); toast({ message: `保存失败: ${err}`, type: 'error' }); }
+      } catch (err) { logger.error('保存失败:', err); toast({ message: `保存失败: ${formatError(err)}`, type: 'error' }); }
   };
   
   useEffect(() => {
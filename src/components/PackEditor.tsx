@@ -10,7 +10,7 @@ import SearchModal from "./SearchModal";
 import DownloadIndicator from "./DownloadIndicator";
 import DownloadDetails from "./DownloadDetails";
 import DownloadSettingsDialog from "./DownloadSettingsDialog";
-import { readFileContent, writeFileContent, searchFiles, type SearchResponse } from "../utils/tauri-api";
+import { readFileContent, writeFileContent, searchFiles, type SearchResponse, formatError } from "../utils/tauri-api";
 import { Icon, Button, ConfirmDialog, Dialog, DialogBody, DialogFooter, useToast } from "@mpe/ui";
 import { logger } from "../utils/logger";
 
@@ -107,6 +107,7 @@ interface FileTreeNode {
   children?: FileTreeNode[];
   file_count?: number;
   loaded?: boolean;
+  has_children?: boolean;
 }
 
 interface PackEditorProps {
@@ -533,7 +534,7 @@ export default function PackEditor({
         const endTime = performance.now();
         const duration = (endTime - startTime).toFixed(2);
         logger.error(`[性能]  加载文件树失败! 耗时: ${duration}ms`, error);
-        toast({ message: `加载文件树失败: ${error}`, type: 'error' });
+        toast({ message: `加载文件树失败: ${formatError(error)}`, type: 'error' });
       }
     };
 
@@ -636,7 +637,7 @@ export default function PackEditor({
       return content;
     } catch (error) {
       logger.error('加载文件失败:', error);
-      toast({ message: `加载文件失败: ${error}`, type: 'error' });
+      toast({ message: `加载文件失败: ${formatError(error)}`, type: 'error' });
       return '';
     } finally {
       setIsLoading(false);
@@ -818,7 +819,7 @@ export default function PackEditor({
       setOpenTabs(newTabs);
     } catch (error) {
       logger.error('保存文件失败:', error);
-      toast({ message: `保存文件失败: ${error}`, type: 'error' });
+      toast({ message: `保存文件失败: ${formatError(error)}`, type: 'error' });
     }
   };
 
@@ -907,7 +908,8 @@ export default function PackEditor({
       const packDir = await invoke<string>('get_current_pack_path');
 
       await invoke('clear_all_history', {
-        packDir: packDir
+        packDir: packDir,
+        dryRun: false
       });
 
       setShowClearHistoryDialog(false);
@@ -917,7 +919,7 @@ export default function PackEditor({
       await refreshFileTree();
     } catch (error) {
       logger.error('清理历史记录失败:', error);
-      toast({ message: `清理失败: ${error}`, type: 'error' });
+      toast({ message: `清理失败: ${formatError(error)}`, type: 'error' });
     }
   };
 
@@ -1252,7 +1254,7 @@ const finishRename = async () => {
         });
         await refreshFileTree();
       } catch (error) {
-        toast({ message: `重命名失败: ${error}`, type: 'error' });
+        toast({ message: `重命名失败: ${formatError(error)}`, type: 'error' });
       }
     }
   }
@@ -1292,7 +1294,7 @@ const startDownload = async (threads: number) => {
     setShowDownloadDetails(true);
   } catch (error) {
     logger.error('创建下载任务失败:', error);
-    toast({ message: `下载失败: ${error}`, type: 'error' });
+    toast({ message: `下载失败: ${formatError(error)}`, type: 'error' });
   }
 };
 
@@ -1304,7 +1306,7 @@ const handleMenuAction = async (action: string) => {
       try {
         await invoke('open_in_explorer', { filePath: contextMenu.path });
       } catch (error) {
-        toast({ message: `打开资源管理器失败: ${error}`, type: 'error' });
+        toast({ message: `打开资源管理器失败: ${formatError(error)}`, type: 'error' });
       }
       break;
     case 'downloadSounds':
@@ -1323,7 +1325,7 @@ const handleMenuAction = async (action: string) => {
             await invoke('delete_file', { filePath: deletePath });
             await refreshFileTree();
           } catch (error) {
-            toast({ message: `删除失败: ${error}`, type: 'error' });
+            toast({ message: `删除失败: ${formatError(error)}`, type: 'error' });
           }
         },
       });
@@ -1347,7 +1349,7 @@ const handleMenuAction = async (action: string) => {
               await invoke('create_new_file', { filePath, content: '' });
               await refreshFileTree();
             } catch (error) {
-              toast({ message: `创建文件失败: ${error}`, type: 'error' });
+              toast({ message: `创建文件失败: ${formatError(error)}`, type: 'error' });
             }
           }
         },
@@ -1369,7 +1371,7 @@ const handleMenuAction = async (action: string) => {
               await invoke('create_new_folder', { folderPath });
               await refreshFileTree();
             } catch (error) {
-              toast({ message: `创建文件夹失败: ${error}`, type: 'error' });
+              toast({ message: `创建文件夹失败: ${formatError(error)}`, type: 'error' });
             }
           }
         },
@@ -1403,7 +1405,7 @@ const handleMenuAction = async (action: string) => {
         // 自动打开创建的文件
         openFileInTab(filePath);
       } catch (error) {
-        toast({ message: `创建 sounds.json 失败: ${error}`, type: 'error' });
+        toast({ message: `创建 sounds.json 失败: ${formatError(error)}`, type: 'error' });
       }
       break;
     case 'copy':
@@ -1431,7 +1433,7 @@ const handleCreatePng = async (width: number, height: number, fileName: string)
 
     openFileInTab(filePath);
   } catch (error) {
-    toast({ message: `创建PNG失败: ${error}`, type: 'error' });
+    toast({ message: `创建PNG失败: ${formatError(error)}`, type: 'error' });
   }
 };
 
@@ -1773,7 +1775,7 @@ return (
               setSearchResults(results);
             } catch (error) {
               logger.error('搜索失败:', error);
-              toast({ message: `搜索失败: ${error}`, type: 'error' });
+              toast({ message: `搜索失败: ${formatError(error)}`, type: 'error' });
             } finally {
               setIsSearching(false);
             }
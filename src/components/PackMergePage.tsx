@@ -9,6 +9,7 @@ import {
   readMergeSourceFileBase64,
   openFolder,
   parentDirPath,
+  formatError,
 } from '../utils/tauri-api';
 import { getAllPackFormatsWithReleases } from '../utils/version-map';
 import { mergePathMatchesQuery } from '../utils/merge-helpers';
@@ -619,7 +620,7 @@ export default function PackMergePage({ onClose, initialSources, onRepick, onMer
         setExcludeSet(new Set());
         setPreview(result);
       } catch (err) {
-        if (!cancelled) setPreviewError(String(err));
+        if (!cancelled) setPreviewError(formatError(err));
       } finally {
         if (!cancelled) setPreviewLoading(false);
       }
@@ -635,7 +636,7 @@ export default function PackMergePage({ onClose, initialSources, onRepick, onMer
       const d = await selectOutputFolder();
       if (d) setOutputDir(d);
     } catch (err) {
-      toast({ message: `无法打开文件夹对话框：${String(err)}`, type: 'error' });
+      toast({ message: `无法打开文件夹对话框：${formatError(err)}`, type: 'error' });
     }
   }, [toast]);
 
@@ -675,7 +676,7 @@ export default function PackMergePage({ onClose, initialSources, onRepick, onMer
       setJobPhase('review');
       toast({ message: '已写入磁盘，请完成导出核对', type: 'success' });
     } catch (err) {
-      setError(String(err));
+      setError(formatError(err));
       toast({ message: '融合失败', type: 'error' });
       setJobPhase('idle');
     } finally {
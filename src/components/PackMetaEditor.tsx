@@ -4,7 +4,7 @@ import { invoke } from "@tauri-apps/api/core";
 import "./PackMetaEditor.css";
 import SyntaxHighlighter from "./SyntaxHighlighter";
 import PackMetaVisualEditor from "./PackMetaVisualEditor";
-import { writeFileContent } from "../utils/tauri-api";
+import { writeFileContent, formatError } from "../utils/tauri-api";
 import { getCompletions, validateJson } from "../utils/json-schema-helper";
 import { getVersionsByPackFormat } from "../utils/version-map";
 import { Icon, useToast } from '@mpe/ui';
@@ -491,7 +491,7 @@ export default function PackMetaEditor({ content, filePath, onChange, onSave }:
         await saveHistoryToBackend();
       } catch (error) {
         logger.error('保存文件失败:', error);
-        toast({ message: `保存文件失败: ${error}`, type: 'error' });
+        toast({ message: `保存文件失败: ${formatError(error)}`, type: 'error' });
       }
     }
   };
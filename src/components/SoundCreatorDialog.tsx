@@ -4,6 +4,7 @@ import { convertFileSrc } from '@tauri-apps/api/core';
 import { Icon, Button, useToast } from '@mpe/ui';
 import './SoundCreatorDialog.css';
 import { logger } from '../utils/logger';
+import { formatError } from '../utils/tauri-api';
 
 interface SoundCreatorDialogProps {
   onClose: () => void;
@@ -371,7 +372,7 @@ export default function SoundCreatorDialog({ onClose, onSave }: SoundCreatorDial
       onClose();
     } catch (error) {
       logger.error('保存失败:', error);
-      toast({ message: `保存失败: ${error}`, type: 'error' });
+      toast({ message: `保存失败: ${formatError(error)}`, type: 'error' });
     }
   };
 
@@ -577,7 +578,7 @@ export default function SoundCreatorDialog({ onClose, onSave }: SoundCreatorDial
       onClose();
     } catch (error) {
       logger.error('保存原版音效失败:', error);
-      toast({ message: `保存失败: ${error}`, type: 'error' });
+      toast({ message: `保存失败: ${formatError(error)}`, type: 'error' });
     }
   };
 
@@ -7,7 +7,7 @@ import "./TextEditor.css";
 import "./MonacoEditor.css";
 import SoundCreatorDialog from "./SoundCreatorDialog";
 import AudioHoverPlayer from "./AudioHoverPlayer";
-import { readFileContent, writeFileContent } from "../utils/tauri-api";
+import { readFileContent, writeFileContent, formatError } from "../utils/tauri-api";
 import { Icon, useToast } from '@mpe/ui';
 import { logger } from '../utils/logger';
 import { useThemeDetector } from '../hooks/useThemeDetector';
@@ -320,7 +320,7 @@ export default function TextEditor({
         await saveHistoryToBackend();
       } catch (error) {
         logger.error('保存文件失败:', error);
-        toast({ message: `保存文件失败: ${error}`, type: 'error' });
+        toast({ message: `保存文件失败: ${formatError(error)}`, type: 'error' });
       }
     }
   };
@@ -1,6 +1,6 @@
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { open } from '@tauri-apps/plugin-shell';
-import { openDevtools } from '../utils/tauri-api';
+import { openDevtools, formatError } from '../utils/tauri-api';
 import { useState, useEffect } from 'react';
 import './TitleBar.css';
 import logoImg from '../assets/logo.png';
@@ -178,7 +178,7 @@ const TitleBar = ({
       await openDevtools();
     } catch (error) {
       logger.error('[Debug] Failed to open devtools:', error);
-      toast({ message: '无法打开开发者工具: ' + error, type: 'error' });
+      toast({ message: '无法打开开发者工具: ' + formatError(error), type: 'error' });
     }
   };
 
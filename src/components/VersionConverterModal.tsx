@@ -1,6 +1,6 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { selectZipFile, selectFolder, selectOutputFolder } from '../utils/tauri-api';
+import { selectZipFile, selectFolder, selectOutputFolder, formatError } from '../utils/tauri-api';
 import { getVersionRange, getVersionsWithType, isReleaseVersion, getVersionsByPackFormat } from '../utils/version-map';
 import { Icon, useToast } from '@mpe/ui';
 import './VersionConverterModal.css';
@@ -113,7 +113,7 @@ const VersionConverterModal = ({ onClose }: VersionConverterModalProps) => {
         await analyzeRef.current(firstPath, 'folder');
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
     } finally {
       setLoading(false);
     }
@@ -150,7 +150,7 @@ const VersionConverterModal = ({ onClose }: VersionConverterModalProps) => {
                   await analyzeRef.current(firstPath, 'folder');
                 }
               } catch (err) {
-                setError(err instanceof Error ? err.message : String(err));
+                setError(formatError(err));
               } finally {
                 setLoading(false);
               }
@@ -208,7 +208,7 @@ const VersionConverterModal = ({ onClose }: VersionConverterModalProps) => {
         await analyzeRef.current(zipPath, 'zip');
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
     } finally {
       setLoading(false);
     }
@@ -229,7 +229,7 @@ const VersionConverterModal = ({ onClose }: VersionConverterModalProps) => {
         await analyzeRef.current(folderPath, 'folder');
       }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(formatError(err));
     } finally {
       setLoading(false);
     }
@@ -299,7 +299,7 @@ const VersionConverterModal = ({ onClose }: VersionConverterModalProps) => {
         setError(null);
       }
     } catch (err) {
-      setError('选择输出路径失败: ' + (err instanceof Error ? err.message : String(err)));
+      setError('选择输出路径失败: ' + (formatError(err)));
     }
   };
 
@@ -365,7 +365,7 @@ const VersionConverterModal = ({ onClose }: VersionConverterModalProps) => {
       logger.debug('[VersionConverter] 转换结果:', result);
       setConversionSuccess(true);
     } catch (err) {
-      setError('转换失败: ' + (err instanceof Error ? err.message : String(err)));
+      setError('转换失败: ' + (formatError(err)));
     } finally {
       setConverting(false);
     }
@@ -527,7 +527,7 @@ const VersionConverterModal = ({ onClose }: VersionConverterModalProps) => {
       }
 
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = formatError(err);
       
       if (errorMsg.includes('pack.mcmeta') || errorMsg.includes('not found') || errorMsg.includes('找不到')) {
         // 找不到pack.mcmeta显示错误
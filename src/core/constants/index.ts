@@ -16,6 +16,8 @@ export const HIGH_PERF_CANVAS_MAX_SIZE = 200 * 1024 * 1024;
 
 export const DEFAULT_PORT = '3000';
 export const DEFAULT_MAX_HISTORY_COUNT = 30;
+export const DEFAULT_CONNECT_TIMEOUT_SECS = 15;
+export const DEFAULT_READ_TIMEOUT_SECS = 60;
 
 export const GITEE_API_BASE = 'https://gitee.com/api/v5';
 export const REPO_OWNER = 'little_100';
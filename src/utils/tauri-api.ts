@@ -2,6 +2,28 @@ import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
 import type { PackInfo, ImageInfo, MergePreview, MergeConfig, MergeResult } from "../types/pack";
 
+// 后端命令返回的结构化错误
+export interface AppError {
+  kind: string;
+  message: string;
+  context?: string | null;
+}
+
+export function isAppError(error: unknown): error is AppError {
+  return typeof error === "object" && error !== null && "kind" in error && "message" in error;
+}
+
+// 将命令错误转换为可显示的文本，兼容字符串错误和 AppError
+export function formatError(error: unknown): string {
+  if (isAppError(error)) {
+    return error.context ? `${error.context}: ${error.message}` : error.message;
+  }
+  if (error instanceof Error) {
+    return error.message;
+  }
+  return String(error);
+}
+
 // 导入材质包
 export async function importPackZip(zipPath: string): Promise<PackInfo> {
   return await invoke<PackInfo>("import_pack_zip", { zipPath });
@@ -35,9 +57,9 @@ export async function getImageDetails(imagePath: string): Promise<ImageInfo> {
   return await invoke<ImageInfo>("get_image_details", { imagePath });
 }
 
-// 导出材质包
-export async function exportPack(outputPath: string): Promise<void> {
-  return await invoke<void>("export_pack", { outputPath });
+// 导出材质包，preflight 默认为 true，导出前检查到错误时拒绝导出
+export async function exportPack(outputPath: string, preflight?: boolean): Promise<void> {
+  return await invoke<void>("export_pack", { outputPath, preflight });
 }
 
 // 清理临时文件
@@ -260,12 +282,15 @@ export function parentDirPath(filePath: string): string {
   return i > 0 ? filePath.slice(0, i) : filePath;
 }
 
-// 启动 Web 服务器
+// 启动 Web 服务器，requireAuth 为 true 或传入 token 时才要求认证
 export async function startWebServer(
   port: number,
-  mode: "lan" | "all"
+  mode: "lan" | "all",
+  token?: string,
+  requireAuth?: boolean,
+  bindAddress?: "127.0.0.1" | "0.0.0.0"
 ): Promise<string> {
-  return await invoke<string>("start_server", { port, mode });
+  return await invoke<string>("start_server", { port, mode, bindAddress, token, requireAuth });
 }
 
 // 停止 Web 服务器
@@ -273,9 +298,17 @@ export async function stopWebServer(): Promise<string> {
   return await invoke<string>("stop_server");
 }
 
+export interface ServerStatus {
+  running: boolean;
+  auth_enabled: boolean;
+  address: string | null;
+  lan_addresses: string[];
+  connected_clients: number;
+}
+
 // 获取服务器状态
-export async function getServerStatus(): Promise<boolean> {
-  return await invoke<boolean>("get_server_status");
+export async function getServerStatus(): Promise<ServerStatus> {
+  return await invoke<ServerStatus>("get_server_status");
 }
 
 export interface VersionManifest {
@@ -345,20 +378,44 @@ export interface SearchResponse {
   filename_matches: SearchResult[];
   content_matches: SearchResult[];
   total_count: number;
+  truncated: boolean;
+}
+
+export interface FileFilter {
+  extensions?: string[];
+  folder?: string;
 }
 
 export async function searchFiles(
   query: string,
   caseSensitive: boolean,
-  useRegex: boolean
+  useRegex: boolean,
+  filter?: FileFilter,
+  packId?: string
 ): Promise<SearchResponse> {
   return await invoke<SearchResponse>("search_files", {
     query,
     caseSensitive,
     useRegex,
+    filter,
+    packId,
   });
 }
 
 export async function openDevtools(): Promise<void> {
   return await invoke<void>("open_devtools");
-}
\ No newline at end of file
+}
+export async function setHistorySettings(
+  enabled: boolean,
+  maxCount: number
+): Promise<void> {
+  return await invoke<void>("set_history_settings", { enabled, maxCount });
+}
+
+// 设置下载的连接超时和读取超时（秒），读取超时针对每次读取而不是整个下载
+export async function setNetworkTimeouts(
+  connectSecs: number,
+  readSecs: number
+): Promise<void> {
+  return await invoke<void>("set_network_timeouts", { connectSecs, readSecs });
+}